@@ -2,7 +2,8 @@
     clippy::inherent_to_string,
     clippy::large_enum_variant,
     clippy::new_without_default,
-    clippy::toplevel_ref_arg
+    clippy::toplevel_ref_arg,
+    clippy::unnecessary_map_or
 )]
 
 mod gen;
@@ -21,6 +22,7 @@ use structopt::StructOpt;
     usage = "\
     cxxbridge <input>.rs              Emit .cc file for bridge to stdout
     cxxbridge <input>.rs --header     Emit .h file for bridge to stdout
+    cxxbridge <input>.rs --signatures Emit JSON description of bridge to stdout
     cxxbridge --header                Emit rust/cxx.h header to stdout",
     help_message = "Print help information",
     version_message = "Print version information"
@@ -34,9 +36,37 @@ struct Opt {
     #[structopt(long)]
     header: bool,
 
+    /// Emit a JSON description of the bridged functions and types instead
+    /// of C++ code
+    #[structopt(long, conflicts_with = "header", requires = "input")]
+    signatures: bool,
+
     /// Any additional headers to #include
     #[structopt(short, long)]
     include: Vec<String>,
+
+    /// C++ type to use for usize in place of size_t, such as uintptr_t
+    #[structopt(long, value_name = "type")]
+    cxx_usize: Option<String>,
+
+    /// C++ type to use for isize in place of ssize_t, such as intptr_t
+    #[structopt(long, value_name = "type")]
+    cxx_isize: Option<String>,
+
+    /// Generate code that compiles with -fno-exceptions; functions returning
+    /// Result become an error
+    #[structopt(long)]
+    no_exceptions: bool,
+
+    /// Emit constexpr size, alignment and field offset constants for each
+    /// shared struct
+    #[structopt(long)]
+    layout_constants: bool,
+
+    /// Turn C++ exceptions not derived from std::exception into an error
+    /// instead of terminating
+    #[structopt(long)]
+    catch_unknown_exceptions: bool,
 }
 
 fn write(content: impl AsRef<[u8]>) {
@@ -48,8 +78,19 @@ fn main() {
 
     let gen = gen::Opt {
         include: opt.include,
+        cxx_usize: opt.cxx_usize,
+        cxx_isize: opt.cxx_isize,
+        no_exceptions: opt.no_exceptions,
+        layout_constants: opt.layout_constants,
+        catch_unknown_exceptions: opt.catch_unknown_exceptions,
     };
 
+    if opt.signatures {
+        let input = opt.input.unwrap(); // enforced by requires
+        write(gen::do_generate_signatures(&input, gen));
+        return;
+    }
+
     match (opt.input, opt.header) {
         (Some(input), true) => write(gen::do_generate_header(&input, gen)),
         (Some(input), false) => write(gen::do_generate_bridge(&input, gen)),
@@ -5,7 +5,7 @@ use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::files::SimpleFiles;
 use codespan_reporting::term::termcolor::{ColorChoice, StandardStream, WriteColor};
 use codespan_reporting::term::{self, Config};
-use std::io::Write;
+use std::io::{self, Write};
 use std::ops::Range;
 use std::path::Path;
 use std::process;
@@ -25,6 +25,36 @@ pub(super) fn format_err(path: &Path, source: &str, error: Error) -> ! {
     process::exit(1);
 }
 
+// One `path:line:column: message` line per error, for callers that report the
+// error themselves instead of exiting.
+#[allow(dead_code)] // only used by cxx::generate_bridge, not the cmd
+pub(super) fn to_io_error(path: &Path, error: Error) -> io::Error {
+    match error {
+        Error::Io(io_error) => io_error,
+        Error::Syn(syn_error) => {
+            let mut message = String::new();
+            for error in syn_error {
+                let start = error.span().start();
+                if !message.is_empty() {
+                    message.push('\n');
+                }
+                message += &format!(
+                    "{}:{}:{}: {}",
+                    path.display(),
+                    start.line,
+                    start.column + 1,
+                    error,
+                );
+            }
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        }
+        _ => io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), error),
+        ),
+    }
+}
+
 fn display_syn_error(stderr: &mut dyn WriteColor, path: &Path, source: &str, error: syn::Error) {
     let span = error.span();
     let start = span.start();
@@ -30,14 +30,24 @@ fn find_line(line: &str) -> Option<usize> {
 pub struct Includes {
     custom: Vec<String>,
     pub array: bool,
+    pub chrono: bool,
     pub cstddef: bool,
     pub cstdint: bool,
     pub cstring: bool,
     pub exception: bool,
+    pub functional: bool,
+    pub map: bool,
     pub memory: bool,
+    pub mutex: bool,
+    pub new: bool,
+    pub optional: bool,
+    pub set: bool,
     pub string: bool,
+    pub string_view: bool,
+    pub tuple: bool,
     pub type_traits: bool,
     pub utility: bool,
+    pub vector: bool,
 }
 
 impl Includes {
@@ -64,6 +74,9 @@ impl Display for Includes {
         if self.array {
             writeln!(f, "#include <array>")?;
         }
+        if self.chrono {
+            writeln!(f, "#include <chrono>")?;
+        }
         if self.cstddef {
             writeln!(f, "#include <cstddef>")?;
         }
@@ -76,18 +89,47 @@ impl Display for Includes {
         if self.exception {
             writeln!(f, "#include <exception>")?;
         }
+        if self.functional {
+            writeln!(f, "#include <functional>")?;
+        }
+        if self.map {
+            writeln!(f, "#include <map>")?;
+        }
         if self.memory {
             writeln!(f, "#include <memory>")?;
         }
+        if self.mutex {
+            writeln!(f, "#include <mutex>")?;
+        }
+        if self.new {
+            writeln!(f, "#include <new>")?;
+        }
+        if self.optional {
+            writeln!(f, "#include <optional>")?;
+        }
+        if self.set {
+            writeln!(f, "#include <set>")?;
+        }
         if self.string {
             writeln!(f, "#include <string>")?;
         }
+        if self.string_view {
+            writeln!(f, "#if __cplusplus >= 201703L")?;
+            writeln!(f, "#include <string_view>")?;
+            writeln!(f, "#endif")?;
+        }
+        if self.tuple {
+            writeln!(f, "#include <tuple>")?;
+        }
         if self.type_traits {
             writeln!(f, "#include <type_traits>")?;
         }
         if self.utility {
             writeln!(f, "#include <utility>")?;
         }
+        if self.vector {
+            writeln!(f, "#include <vector>")?;
+        }
         if *self != Self::default() {
             writeln!(f)?;
         }
@@ -5,17 +5,18 @@ mod error;
 pub(super) mod include;
 mod namespace;
 pub(super) mod out;
+mod signatures;
 mod write;
 
-use self::error::format_err;
-use self::namespace::Namespace;
-use crate::syntax::{self, check, ident, Types};
+use self::error::{format_err, to_io_error};
+use self::namespace::{Namespace, Segment};
+use crate::syntax::{self, check, ident, Api, Type, Types};
 use quote::quote;
 use std::fs;
 use std::io;
 use std::path::Path;
 use syn::parse::ParseStream;
-use syn::{Attribute, File, Item, Token};
+use syn::{Attribute, File, Ident, Item, LitStr, Token};
 use thiserror::Error;
 
 pub(super) type Result<T, E = Error> = std::result::Result<T, E>;
@@ -34,13 +35,25 @@ pub(super) enum Error {
 
 struct Input {
     namespace: Namespace,
+    // The {module} of link names, as in OutFile.
+    link_module: String,
     module: Vec<Item>,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub(super) struct Opt {
     /// Any additional headers to #include
     pub include: Vec<String>,
+    /// C++ type to use for usize in place of size_t
+    pub cxx_usize: Option<String>,
+    /// C++ type to use for isize in place of ssize_t
+    pub cxx_isize: Option<String>,
+    /// Reject anything whose generated code would need C++ exceptions
+    pub no_exceptions: bool,
+    /// Emit constexpr size and field offset constants for shared structs
+    pub layout_constants: bool,
+    /// Turn C++ exceptions not derived from std::exception into an error too
+    pub catch_unknown_exceptions: bool,
 }
 
 pub(super) fn do_generate_bridge(path: &Path, opt: Opt) -> Vec<u8> {
@@ -53,25 +66,102 @@ pub(super) fn do_generate_header(path: &Path, opt: Opt) -> Vec<u8> {
     generate(path, opt, header)
 }
 
+pub(super) fn do_generate_signatures(path: &Path, opt: Opt) -> Vec<u8> {
+    let no_exceptions = opt.no_exceptions;
+    process(path, no_exceptions, |namespace, _module, apis, _types| {
+        signatures::gen(&namespace, apis).into_bytes()
+    })
+}
+
 fn generate(path: &Path, opt: Opt, header: bool) -> Vec<u8> {
+    let no_exceptions = opt.no_exceptions;
+    process(path, no_exceptions, |namespace, module, apis, types| {
+        write::gen(namespace, module, apis, types, opt, header).content()
+    })
+}
+
+// Parses and checks the bridge, then hands the result to the given emitter.
+fn process(
+    path: &Path,
+    no_exceptions: bool,
+    emit: impl FnOnce(Namespace, String, &[Api], &Types) -> Vec<u8>,
+) -> Vec<u8> {
     let source = match fs::read_to_string(path) {
         Ok(source) => source,
         Err(err) => format_err(path, "", Error::Io(err)),
     };
-    match (|| -> Result<_> {
-        let syntax = syn::parse_file(&source)?;
-        let bridge = find_bridge_mod(syntax)?;
-        let apis = syntax::parse_items(bridge.module)?;
-        let types = Types::collect(&apis)?;
-        check::typecheck(&apis, &types)?;
-        let out = write::gen(bridge.namespace, &apis, &types, opt, header);
-        Ok(out)
-    })() {
-        Ok(out) => out.content(),
+    match try_process(&source, no_exceptions, emit) {
+        Ok(out) => out,
         Err(err) => format_err(path, &source, err),
     }
 }
 
+fn try_process<T>(
+    source: &str,
+    no_exceptions: bool,
+    emit: impl FnOnce(Namespace, String, &[Api], &Types) -> T,
+) -> Result<T> {
+    let syntax = syn::parse_file(source)?;
+    let bridge = find_bridge_mod(syntax)?;
+    let apis = syntax::parse_items(bridge.module)?;
+    let types = Types::collect(&apis)?;
+    check::typecheck(&apis, &types)?;
+    if no_exceptions {
+        check_no_exceptions(&apis)?;
+    }
+    Ok(emit(bridge.namespace, bridge.link_module, &apis, &types))
+}
+
+// Same as do_generate_header and do_generate_bridge together, from a single
+// parse, except that errors are returned rather than printed before exiting.
+#[allow(dead_code)] // only used by cxx::generate_bridge, not the cmd
+pub(super) fn try_generate_header_and_bridge(
+    path: &Path,
+    opt: Opt,
+) -> io::Result<(Vec<u8>, Vec<u8>)> {
+    let source = fs::read_to_string(path)?;
+    let no_exceptions = opt.no_exceptions;
+    try_process(&source, no_exceptions, |namespace, module, apis, types| {
+        let header = write::gen(
+            namespace.clone(),
+            module.clone(),
+            apis,
+            types,
+            opt.clone(),
+            true,
+        );
+        let bridge = write::gen(namespace, module, apis, types, opt, false);
+        (header.content(), bridge.content())
+    })
+    .map_err(|err| to_io_error(path, err))
+}
+
+// Result is the only part of the bridge that is implemented with try/catch on
+// the C++ side, in one direction or the other.
+fn check_no_exceptions(apis: &[Api]) -> syn::Result<()> {
+    for api in apis {
+        if let Api::CxxFunction(efn) | Api::RustFunction(efn) = api {
+            if efn.throws {
+                return Err(syn::Error::new_spanned(
+                    &efn.ident,
+                    "functions returning Result are not supported when generating code without C++ exceptions",
+                ));
+            }
+            for arg in &efn.args {
+                if let Type::Fn(f) = &arg.ty {
+                    if f.throws {
+                        return Err(syn::Error::new_spanned(
+                            &arg.ty,
+                            "callbacks returning Result are not supported when generating code without C++ exceptions",
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 fn find_bridge_mod(syntax: File) -> Result<Input> {
     for item in syntax.items {
         if let Item::Mod(item) = item {
@@ -87,9 +177,15 @@ fn find_bridge_mod(syntax: File) -> Result<Input> {
                             )));
                         }
                     };
-                    let namespace_segments = parse_args(attr)?;
-                    let namespace = Namespace::new(namespace_segments);
-                    return Ok(Input { namespace, module });
+                    let args = parse_args(attr)?;
+                    let namespace = Namespace::new(args.namespace);
+                    let ident = item.ident;
+                    let link_module = args.module.unwrap_or_else(|| ident.to_string());
+                    return Ok(Input {
+                        namespace,
+                        link_module,
+                        module,
+                    });
                 }
             }
         }
@@ -97,24 +193,82 @@ fn find_bridge_mod(syntax: File) -> Result<Input> {
     Err(Error::NoBridgeMod)
 }
 
-fn parse_args(attr: &Attribute) -> syn::Result<Vec<String>> {
+// Arguments of the #[cxx::bridge] attribute, parsed the same way as by the
+// macro.
+struct Args {
+    namespace: Vec<Segment>,
+    module: Option<String>,
+}
+
+fn parse_args(attr: &Attribute) -> syn::Result<Args> {
+    let mut args = Args {
+        namespace: Vec::new(),
+        module: None,
+    };
     if attr.tokens.is_empty() {
-        return Ok(Vec::new());
+        return Ok(args);
     }
     attr.parse_args_with(|input: ParseStream| {
         mod kw {
             syn::custom_keyword!(namespace);
+            syn::custom_keyword!(module);
+        }
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::namespace) {
+                input.parse::<kw::namespace>()?;
+                input.parse::<Token![=]>()?;
+                args.namespace = parse_namespace(input)?;
+            } else if lookahead.peek(kw::module) {
+                input.parse::<kw::module>()?;
+                input.parse::<Token![=]>()?;
+                let mut segments = Vec::new();
+                loop {
+                    let ident: Ident = input.parse()?;
+                    ident::check(&ident)?;
+                    segments.push(ident.to_string());
+                    if input.parse::<Option<Token![::]>>()?.is_none() {
+                        break;
+                    }
+                }
+                args.module = Some(segments.join("$"));
+            } else {
+                return Err(lookahead.error());
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
         }
-        input.parse::<kw::namespace>()?;
-        input.parse::<Token![=]>()?;
-        let path = syn::Path::parse_mod_style(input)?;
-        input.parse::<Option<Token![,]>>()?;
-        path.segments
-            .into_iter()
-            .map(|seg| {
-                ident::check(&seg.ident)?;
-                Ok(seg.ident.to_string())
-            })
-            .collect()
+        Ok(args)
     })
 }
+
+fn parse_namespace(input: ParseStream) -> syn::Result<Vec<Segment>> {
+    mod kw {
+        syn::custom_keyword!(inline);
+    }
+    let mut segments = Vec::new();
+    if input.peek(LitStr) {
+        let lit: LitStr = input.parse()?;
+        for (inline, ident) in ident::split_namespace(&lit)? {
+            segments.push(Segment {
+                name: ident.to_string(),
+                inline,
+            });
+        }
+        return Ok(segments);
+    }
+    loop {
+        let inline = input.parse::<Option<kw::inline>>()?.is_some();
+        let ident: Ident = input.parse()?;
+        ident::check(&ident)?;
+        segments.push(Segment {
+            name: ident.to_string(),
+            inline,
+        });
+        if input.parse::<Option<Token![::]>>()?.is_none() {
+            break;
+        }
+    }
+    Ok(segments)
+}
@@ -3,15 +3,24 @@ use std::slice::Iter;
 
 #[derive(Clone)]
 pub struct Namespace {
-    segments: Vec<String>,
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone)]
+pub struct Segment {
+    pub name: String,
+    // Whether the C++ namespace block is declared `inline namespace`. The
+    // segment still participates in symbol names like any other, so distinct
+    // versions of a symbol-versioned library get distinct link names.
+    pub inline: bool,
 }
 
 impl Namespace {
-    pub fn new(segments: Vec<String>) -> Self {
+    pub fn new(segments: Vec<Segment>) -> Self {
         Namespace { segments }
     }
 
-    pub fn iter(&self) -> Iter<String> {
+    pub fn iter(&self) -> Iter<'_, Segment> {
         self.segments.iter()
     }
 }
@@ -19,7 +28,7 @@ impl Namespace {
 impl Display for Namespace {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for segment in self {
-            f.write_str(segment)?;
+            f.write_str(&segment.name)?;
             f.write_str("$")?;
         }
         Ok(())
@@ -27,8 +36,8 @@ impl Display for Namespace {
 }
 
 impl<'a> IntoIterator for &'a Namespace {
-    type Item = &'a String;
-    type IntoIter = Iter<'a, String>;
+    type Item = &'a Segment;
+    type IntoIter = Iter<'a, Segment>;
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
@@ -1,12 +1,21 @@
 use crate::gen::include::Includes;
 use crate::gen::namespace::Namespace;
+use proc_macro2::Ident;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::{self, Arguments, Write};
 
 pub(crate) struct OutFile {
     pub namespace: Namespace,
+    // Ident of the #[cxx::bridge] mod, or its `module = ...` argument with `$`
+    // between segments, which is part of function link names.
+    pub module: String,
     pub header: bool,
     pub include: Includes,
+    pub cxx_usize: Option<String>,
+    pub cxx_isize: Option<String>,
+    // Extern types whose C++ name differs from the Rust one.
+    pub cxx_names: HashMap<Ident, Ident>,
     content: RefCell<Content>,
 }
 
@@ -17,11 +26,15 @@ struct Content {
 }
 
 impl OutFile {
-    pub fn new(namespace: Namespace, header: bool) -> Self {
+    pub fn new(namespace: Namespace, module: String, header: bool) -> Self {
         OutFile {
             namespace,
+            module,
             header,
             include: Includes::new(),
+            cxx_usize: None,
+            cxx_isize: None,
+            cxx_names: HashMap::new(),
             content: RefCell::new(Content {
                 bytes: Vec::new(),
                 section_pending: false,
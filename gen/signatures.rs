@@ -0,0 +1,229 @@
+// A machine-readable description of everything a bridge exposes, for tools
+// that want the FFI surface without parsing Rust themselves. The format is
+// JSON; see the "Signature dump" section of the README for the schema. Fields
+// may be added in later versions but existing ones keep their meaning until
+// "version" changes.
+
+use crate::gen::namespace::Namespace;
+use crate::syntax::{Api, Enum, ExternFn, ExternType, Lang, Signature, Struct, Type, Var};
+use std::fmt::Write;
+
+const VERSION: u32 = 1;
+
+pub(super) fn gen(namespace: &Namespace, apis: &[Api]) -> String {
+    let mut types = Vec::new();
+    let mut functions = Vec::new();
+    for api in apis {
+        match api {
+            Api::Include(_) => {}
+            Api::Struct(strct) => types.push(describe_struct(strct)),
+            Api::Enum(enm) => types.push(describe_enum(enm)),
+            Api::ExceptionEnum(enm) => types.push(format!(
+                "{{\"kind\": \"exception_enum\", \"name\": {}, \"variants\": [{}]}}",
+                string(&enm.ident.to_string()),
+                join(
+                    enm.variants
+                        .iter()
+                        .map(|variant| string(&variant.ident.to_string()))
+                ),
+            )),
+            Api::CxxType(ety) => types.push(describe_extern_type(ety, "cxx_type")),
+            Api::RustType(ety) => types.push(describe_extern_type(ety, "rust_type")),
+            Api::CxxFunction(efn) | Api::RustFunction(efn) => {
+                functions.push(describe_function(efn));
+            }
+        }
+    }
+
+    let namespace = namespace
+        .iter()
+        .map(|segment| segment.name.as_str())
+        .collect::<Vec<_>>()
+        .join("::");
+
+    let mut out = String::new();
+    writeln!(out, "{{").unwrap();
+    writeln!(out, "  \"version\": {},", VERSION).unwrap();
+    writeln!(out, "  \"namespace\": {},", string(&namespace)).unwrap();
+    write_list(&mut out, "types", &types, ",");
+    write_list(&mut out, "functions", &functions, "");
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+fn write_list(out: &mut String, key: &str, entries: &[String], trailer: &str) {
+    if entries.is_empty() {
+        writeln!(out, "  \"{}\": []{}", key, trailer).unwrap();
+        return;
+    }
+    writeln!(out, "  \"{}\": [", key).unwrap();
+    for (i, entry) in entries.iter().enumerate() {
+        let comma = if i + 1 < entries.len() { "," } else { "" };
+        writeln!(out, "    {}{}", entry, comma).unwrap();
+    }
+    writeln!(out, "  ]{}", trailer).unwrap();
+}
+
+fn describe_struct(strct: &Struct) -> String {
+    let kind = if strct.union_token.is_some() {
+        "union"
+    } else {
+        "struct"
+    };
+    format!(
+        "{{\"kind\": \"{}\", \"name\": {}, \"derives\": [{}], \"fields\": [{}]}}",
+        kind,
+        string(&strct.ident.to_string()),
+        join(
+            strct
+                .derives
+                .iter()
+                .map(|derive| string(&derive.to_string()))
+        ),
+        join(strct.fields.iter().map(describe_var)),
+    )
+}
+
+fn describe_enum(enm: &Enum) -> String {
+    let repr = match &enm.repr {
+        Some(repr) => string(&repr.to_string()),
+        None => "null".to_owned(),
+    };
+    format!(
+        "{{\"kind\": \"enum\", \"name\": {}, \"repr\": {}, \"variants\": [{}]}}",
+        string(&enm.ident.to_string()),
+        repr,
+        join(enm.variants.iter().map(|variant| format!(
+            "{{\"name\": {}, \"discriminant\": {}}}",
+            string(&variant.ident.to_string()),
+            variant.discriminant,
+        ))),
+    )
+}
+
+fn describe_extern_type(ety: &ExternType, kind: &str) -> String {
+    let cxx_name = ety.cxx_name.as_ref().unwrap_or(&ety.ident);
+    format!(
+        "{{\"kind\": \"{}\", \"name\": {}, \"cxx_name\": {}}}",
+        kind,
+        string(&ety.ident.to_string()),
+        string(&cxx_name.to_string()),
+    )
+}
+
+fn describe_function(efn: &ExternFn) -> String {
+    let lang = match efn.lang {
+        Lang::Cxx => "cxx",
+        Lang::Rust => "rust",
+    };
+    let cxx_name = efn.cxx_name.as_ref().unwrap_or(&efn.ident);
+    let receiver = match &efn.receiver {
+        Some(receiver) => format!(
+            "{{\"type\": {}, \"mutable\": {}, \"pinned\": {}}}",
+            string(&receiver.ident.to_string()),
+            receiver.mutability.is_some(),
+            receiver.pinned,
+        ),
+        None => "null".to_owned(),
+    };
+    let error_type = match &efn.error_type {
+        Some(error_type) => string(&error_type.to_string()),
+        None => "null".to_owned(),
+    };
+    format!(
+        "{{\"lang\": \"{}\", \"name\": {}, \"cxx_name\": {}, \"receiver\": {}, \"args\": [{}], \"return\": {}, \"throws\": {}, \"error_type\": {}}}",
+        lang,
+        string(&efn.ident.to_string()),
+        string(&cxx_name.to_string()),
+        receiver,
+        join(efn.args.iter().map(describe_var)),
+        return_type(&efn.sig),
+        efn.throws,
+        error_type,
+    )
+}
+
+fn describe_var(var: &Var) -> String {
+    format!(
+        "{{\"name\": {}, \"type\": {}}}",
+        string(&var.ident.to_string()),
+        string(&spell(&var.ty)),
+    )
+}
+
+fn return_type(sig: &Signature) -> String {
+    match &sig.ret {
+        Some(ret) => string(&spell(ret)),
+        None => "null".to_owned(),
+    }
+}
+
+// The type as it is written in the bridge, with the Result of a throwing
+// function left off since "throws" already records it.
+fn spell(ty: &Type) -> String {
+    match ty {
+        Type::Ident(ident) => ident.to_string(),
+        Type::RustBox(ty)
+        | Type::RustArc(ty)
+        | Type::RustVec(ty)
+        | Type::UniquePtr(ty)
+        | Type::UniqueArray(ty)
+        | Type::SharedPtr(ty)
+        | Type::CxxVector(ty)
+        | Type::CxxSet(ty)
+        | Type::Borrowed(ty)
+        | Type::Optional(ty)
+        | Type::SliceBox(ty) => format!("{}<{}>", ty.name, spell(&ty.inner)),
+        Type::CxxMap(ty) => format!("{}<{}, {}>", ty.name, spell(&ty.first), spell(&ty.second)),
+        Type::Ref(r) | Type::Str(r) | Type::SliceRef(r) => {
+            let mutability = if r.mutability.is_some() { "mut " } else { "" };
+            let reference = format!("&{}{}", mutability, spell(&r.inner));
+            if r.pinned {
+                format!("Pin<{}>", reference)
+            } else {
+                reference
+            }
+        }
+        Type::Fn(sig) => {
+            let args = sig
+                .args
+                .iter()
+                .map(|arg| spell(&arg.ty))
+                .collect::<Vec<_>>()
+                .join(", ");
+            match &sig.ret {
+                Some(ret) if sig.throws => format!("fn({}) -> Result<{}>", args, spell(ret)),
+                Some(ret) => format!("fn({}) -> {}", args, spell(ret)),
+                None if sig.throws => format!("fn({}) -> Result<()>", args),
+                None => format!("fn({})", args),
+            }
+        }
+        Type::Slice(slice) => format!("[{}]", spell(&slice.inner)),
+        Type::Tuple(tuple) => {
+            let elems = tuple.elems.iter().map(spell).collect::<Vec<_>>();
+            format!("({})", elems.join(", "))
+        }
+        Type::Array(array) => format!("[{}; {}]", spell(&array.inner), array.len),
+        Type::CxxArray(array) => format!("Array<{}, {}>", spell(&array.inner), array.len),
+        Type::Void(_) => "()".to_owned(),
+    }
+}
+
+fn join(entries: impl Iterator<Item = String>) -> String {
+    entries.collect::<Vec<_>>().join(", ")
+}
+
+fn string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            ch if ch.is_control() => write!(escaped, "\\u{:04x}", ch as u32).unwrap(),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
@@ -2,17 +2,22 @@ use crate::gen::namespace::Namespace;
 use crate::gen::out::OutFile;
 use crate::gen::{include, Opt};
 use crate::syntax::atom::Atom::{self, *};
-use crate::syntax::{Api, ExternFn, Signature, Struct, Type, Types, Var};
+use crate::syntax::{
+    layout, mangle, Api, Deprecated, Enum, ExternFn, Signature, Struct, Type, Types, Var,
+};
 use proc_macro2::Ident;
+use std::collections::HashSet;
+use syn::LitStr;
 
 pub(super) fn gen(
     namespace: Namespace,
+    module: String,
     apis: &[Api],
     types: &Types,
     opt: Opt,
     header: bool,
 ) -> OutFile {
-    let mut out_file = OutFile::new(namespace.clone(), header);
+    let mut out_file = OutFile::new(namespace.clone(), module, header);
     let out = &mut out_file;
 
     if header {
@@ -20,34 +25,91 @@ pub(super) fn gen(
     }
 
     out.include.extend(opt.include);
+    out.cxx_usize = opt.cxx_usize;
+    out.cxx_isize = opt.cxx_isize;
+    let layout_constants = opt.layout_constants;
+    let catch_unknown_exceptions = opt.catch_unknown_exceptions;
     for api in apis {
-        if let Api::Include(include) = api {
-            out.include.insert(include.value());
+        match api {
+            Api::Include(include) => out.include.insert(include.value()),
+            Api::CxxType(ety) | Api::RustType(ety) => {
+                if let Some(cxx_name) = &ety.cxx_name {
+                    out.cxx_names.insert(ety.ident.clone(), cxx_name.clone());
+                }
+            }
+            _ => {}
         }
     }
 
+    if out.cxx_usize.is_some() || out.cxx_isize.is_some() {
+        out.include.cstdint = true;
+        out.include.type_traits = true;
+    }
     write_includes(out, types);
-    write_include_cxxbridge(out, apis, types);
+    write_include_cxxbridge(out, apis, types, catch_unknown_exceptions);
 
     out.next_section();
-    for name in &namespace {
-        writeln!(out, "namespace {} {{", name);
+    for segment in &namespace {
+        if segment.inline {
+            write!(out, "inline ");
+        }
+        writeln!(out, "namespace {} {{", segment.name);
     }
 
+    out.next_section();
+    write_integer_layout_asserts(out);
+    write_int128_guard(out, types);
+
     out.next_section();
     for api in apis {
         match api {
+            Api::Struct(strct) if strct.union_token.is_some() => {
+                writeln!(out, "union {};", strct.ident);
+            }
             Api::Struct(strct) => write_struct_decl(out, &strct.ident),
-            Api::CxxType(ety) => write_struct_using(out, &ety.ident),
-            Api::RustType(ety) => write_struct_decl(out, &ety.ident),
+            Api::CxxType(ety) => write_struct_using(out, &cxx_type_name(out, &ety.ident)),
+            Api::RustType(ety) => write_struct_decl(out, &cxx_type_name(out, &ety.ident)),
             _ => {}
         }
     }
 
     for api in apis {
-        if let Api::Struct(strct) = api {
+        if let Api::Enum(enm) = api {
             out.next_section();
-            write_struct(out, strct);
+            write_enum(out, enm);
+        }
+    }
+
+    for api in apis {
+        if let Api::RustType(ety) = api {
+            let methods = apis
+                .iter()
+                .filter_map(|api| match api {
+                    Api::RustFunction(efn) => match &efn.receiver {
+                        Some(receiver) if receiver.ident == ety.ident => Some(efn),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if !methods.is_empty() {
+                out.next_section();
+                write_struct_with_methods(out, &cxx_type_name(out, &ety.ident), &methods);
+            }
+        }
+    }
+
+    let structs = sort_structs(apis, types);
+    for &strct in &structs {
+        out.next_section();
+        write_struct(out, strct);
+        if strct.fixed_width.is_some() || strct.union_token.is_some() {
+            write_fixed_width_asserts(out, strct, types);
+        } else {
+            write_layout_asserts(out, strct, types);
+        }
+        if layout_constants {
+            write_layout_constants(out, strct, types);
         }
     }
 
@@ -58,6 +120,17 @@ pub(super) fn gen(
             let (efn, write): (_, fn(_, _, _)) = match api {
                 Api::CxxFunction(efn) => (efn, write_cxx_function_shim),
                 Api::RustFunction(efn) => (efn, write_rust_function_decl),
+                Api::CxxType(ety) => {
+                    if ety.debug.is_some() {
+                        out.next_section();
+                        write_cxx_type_debug(out, &ety.ident);
+                    }
+                    if ety.placement.is_some() {
+                        out.next_section();
+                        write_cxx_type_placement(out, &ety.ident);
+                    }
+                    continue;
+                }
                 _ => continue,
             };
             out.next_section();
@@ -68,14 +141,24 @@ pub(super) fn gen(
 
     for api in apis {
         if let Api::RustFunction(efn) = api {
+            if efn.receiver.is_some() && header {
+                continue;
+            }
             out.next_section();
             write_rust_function_shim(out, efn, types);
         }
     }
 
     out.next_section();
-    for name in namespace.iter().rev() {
-        writeln!(out, "}} // namespace {}", name);
+    for segment in namespace.iter().rev() {
+        writeln!(out, "}} // namespace {}", segment.name);
+    }
+
+    for &strct in &structs {
+        if strct.derives.iter().any(|derive| derive == "Hash") {
+            out.next_section();
+            write_struct_hash(out, strct, types);
+        }
     }
 
     if !header {
@@ -95,20 +178,42 @@ fn write_includes(out: &mut OutFile, types: &Types) {
                 Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(I8) | Some(I16) | Some(I32)
                 | Some(I64) => out.include.cstdint = true,
                 Some(Usize) => out.include.cstddef = true,
-                Some(CxxString) => out.include.string = true,
-                Some(Bool) | Some(Isize) | Some(F32) | Some(F64) | Some(RustString) | None => {}
+                Some(CxxString) | Some(CxxU16String) => out.include.string = true,
+                Some(Bool) | Some(Char) | Some(U128) | Some(I128) | Some(Isize) | Some(F32)
+                | Some(F64) | Some(RustString) | None => {}
             },
             Type::RustBox(_) => out.include.type_traits = true,
-            Type::UniquePtr(_) => out.include.memory = true,
+            Type::UniquePtr(_) | Type::UniqueArray(_) | Type::SharedPtr(_) => {
+                out.include.memory = true
+            }
+            Type::CxxVector(_) => out.include.vector = true,
+            Type::CxxMap(_) => out.include.map = true,
+            Type::CxxSet(_) => out.include.set = true,
+            Type::Tuple(_) => out.include.tuple = true,
+            Type::CxxArray(_) => out.include.array = true,
+            Type::Optional(ty) if is_optional_value(&ty.inner) => out.include.optional = true,
             _ => {}
         }
     }
+    if !types.structs.is_empty() {
+        // For offsetof in the layout asserts.
+        out.include.cstddef = true;
+    }
 }
 
-fn write_include_cxxbridge(out: &mut OutFile, apis: &[Api], types: &Types) {
+fn write_include_cxxbridge(
+    out: &mut OutFile,
+    apis: &[Api],
+    types: &Types,
+    catch_unknown_exceptions: bool,
+) {
     let mut needs_rust_string = false;
     let mut needs_rust_str = false;
+    let mut needs_rust_slice = false;
+    let mut needs_rust_vec = false;
     let mut needs_rust_box = false;
+    let mut needs_rust_box_slice = false;
+    let mut needs_rust_arc = false;
     let mut needs_rust_fn = false;
     for ty in types {
         match ty {
@@ -116,11 +221,30 @@ fn write_include_cxxbridge(out: &mut OutFile, apis: &[Api], types: &Types) {
                 out.include.type_traits = true;
                 needs_rust_box = true;
             }
+            Type::SliceBox(_) => {
+                out.include.cstddef = true;
+                out.include.type_traits = true;
+                needs_rust_box = true;
+                needs_rust_box_slice = true;
+            }
+            Type::RustArc(_) => needs_rust_arc = true,
             Type::Str(_) => {
                 out.include.cstdint = true;
                 out.include.string = true;
+                out.include.string_view = true;
                 needs_rust_str = true;
             }
+            Type::SliceRef(_) => {
+                out.include.cstddef = true;
+                needs_rust_slice = true;
+            }
+            Type::RustVec(_) => {
+                out.include.array = true;
+                out.include.cstddef = true;
+                out.include.cstdint = true;
+                out.include.utility = true;
+                needs_rust_vec = true;
+            }
             Type::Fn(_) => {
                 needs_rust_fn = true;
             }
@@ -128,6 +252,8 @@ fn write_include_cxxbridge(out: &mut OutFile, apis: &[Api], types: &Types) {
                 out.include.array = true;
                 out.include.cstdint = true;
                 out.include.string = true;
+                out.include.string_view = true;
+                out.include.type_traits = true;
                 needs_rust_string = true;
             }
             _ => {}
@@ -135,6 +261,7 @@ fn write_include_cxxbridge(out: &mut OutFile, apis: &[Api], types: &Types) {
     }
 
     let mut needs_rust_error = false;
+    let mut needs_rust_typed_error = false;
     let mut needs_unsafe_bitcopy = false;
     let mut needs_manually_drop = false;
     let mut needs_maybe_uninit = false;
@@ -151,9 +278,27 @@ fn write_include_cxxbridge(out: &mut OutFile, apis: &[Api], types: &Types) {
                         break;
                     }
                 }
+                for arg in &efn.args {
+                    if let Type::Fn(f) = &arg.ty {
+                        // The trampoline rethrows an Err from the callback.
+                        if f.throws {
+                            out.include.cstdint = true;
+                            out.include.exception = true;
+                            out.include.string = true;
+                            out.include.string_view = true;
+                            needs_rust_str = true;
+                            needs_rust_error = true;
+                            needs_maybe_uninit |= f.ret.is_some();
+                        }
+                    }
+                }
             }
             Api::RustFunction(efn) if !out.header => {
-                if efn.throws {
+                if efn.error_type.is_some() {
+                    out.include.exception = true;
+                    needs_rust_typed_error = true;
+                    needs_maybe_uninit = true;
+                } else if efn.throws {
                     out.include.exception = true;
                     needs_rust_error = true;
                 }
@@ -178,9 +323,13 @@ fn write_include_cxxbridge(out: &mut OutFile, apis: &[Api], types: &Types) {
 
     if needs_rust_string
         || needs_rust_str
+        || needs_rust_slice
+        || needs_rust_vec
         || needs_rust_box
+        || needs_rust_arc
         || needs_rust_fn
         || needs_rust_error
+        || needs_rust_typed_error
         || needs_unsafe_bitcopy
         || needs_manually_drop
         || needs_maybe_uninit
@@ -196,9 +345,14 @@ fn write_include_cxxbridge(out: &mut OutFile, apis: &[Api], types: &Types) {
 
     write_header_section(out, needs_rust_string, "CXXBRIDGE02_RUST_STRING");
     write_header_section(out, needs_rust_str, "CXXBRIDGE02_RUST_STR");
+    write_header_section(out, needs_rust_slice, "CXXBRIDGE02_RUST_SLICE");
+    write_header_section(out, needs_rust_vec, "CXXBRIDGE02_RUST_VEC");
     write_header_section(out, needs_rust_box, "CXXBRIDGE02_RUST_BOX");
+    write_header_section(out, needs_rust_box_slice, "CXXBRIDGE02_RUST_BOX_SLICE");
+    write_header_section(out, needs_rust_arc, "CXXBRIDGE02_RUST_ARC");
     write_header_section(out, needs_rust_fn, "CXXBRIDGE02_RUST_FN");
     write_header_section(out, needs_rust_error, "CXXBRIDGE02_RUST_ERROR");
+    write_header_section(out, needs_rust_typed_error, "CXXBRIDGE02_RUST_TYPED_ERROR");
     write_header_section(out, needs_unsafe_bitcopy, "CXXBRIDGE02_RUST_BITCOPY");
 
     if needs_manually_drop {
@@ -246,11 +400,35 @@ fn write_include_cxxbridge(out: &mut OutFile, apis: &[Api], types: &Types) {
         writeln!(out, "  func();");
         writeln!(out, "}} catch (const ::std::exception &e) {{");
         writeln!(out, "  fail(e.what());");
+        if catch_unknown_exceptions {
+            // Anything else thrown, like a plain int, would otherwise escape
+            // the noexcept function and terminate.
+            writeln!(out, "}} catch (...) {{");
+            writeln!(out, "  fail(\"unknown C++ exception\");");
+        }
         writeln!(out, "}}");
         out.end_block("namespace behavior");
     }
 
     out.end_block("namespace rust");
+
+    let needs_system_abi = apis.iter().any(|api| match api {
+        Api::CxxFunction(efn) | Api::RustFunction(efn) => efn
+            .abi
+            .as_ref()
+            .map_or(false, |abi| abi.value() == "system"),
+        _ => false,
+    });
+    if needs_system_abi {
+        out.next_section();
+        writeln!(out, "#ifndef CXXBRIDGE02_SYSTEM_ABI");
+        writeln!(out, "#ifdef _WIN32");
+        writeln!(out, "#define CXXBRIDGE02_SYSTEM_ABI __stdcall");
+        writeln!(out, "#else");
+        writeln!(out, "#define CXXBRIDGE02_SYSTEM_ABI");
+        writeln!(out, "#endif");
+        writeln!(out, "#endif // CXXBRIDGE02_SYSTEM_ABI");
+    }
 }
 
 fn write_header_section(out: &mut OutFile, needed: bool, section: &str) {
@@ -264,15 +442,389 @@ fn write_header_section(out: &mut OutFile, needed: bool, section: &str) {
     }
 }
 
+// A user-chosen C++ type for usize or isize still has to match the layout of
+// the Rust type, which is pointer-sized.
+fn write_integer_layout_asserts(out: &mut OutFile) {
+    let overrides = [
+        (&out.cxx_usize, "usize", "is_unsigned"),
+        (&out.cxx_isize, "isize", "is_signed"),
+    ];
+    for (cxx, rust, signedness) in &overrides {
+        if let Some(cxx) = cxx {
+            writeln!(
+                out,
+                "static_assert(sizeof({}) == sizeof(void *), \"{} must be pointer-sized\");",
+                cxx, rust,
+            );
+            writeln!(
+                out,
+                "static_assert(alignof({}) == alignof(void *), \"{} must be pointer-aligned\");",
+                cxx, rust,
+            );
+            writeln!(
+                out,
+                "static_assert(::std::{}<{}>::value, \"{} signedness mismatch\");",
+                signedness, cxx, rust,
+            );
+        }
+    }
+}
+
+// There is no standard C++ type for i128 and u128; __int128 is a GCC and Clang
+// extension, so fail clearly on a compiler without it.
+fn write_int128_guard(out: &mut OutFile, types: &Types) {
+    if types.into_iter().any(|ty| ty == I128 || ty == U128) {
+        writeln!(out, "#ifndef __SIZEOF_INT128__");
+        writeln!(
+            out,
+            "#error \"i128 and u128 require a C++ compiler with __int128 support\"",
+        );
+        writeln!(out, "#endif");
+    }
+}
+
+// A struct used by value in another struct's field must be complete before
+// that struct's definition, regardless of declaration order in the bridge.
+fn sort_structs<'a>(apis: &'a [Api], types: &Types<'a>) -> Vec<&'a Struct> {
+    fn visit<'a>(
+        strct: &'a Struct,
+        types: &Types<'a>,
+        sorted: &mut Vec<&'a Struct>,
+        visited: &mut HashSet<&'a Ident>,
+    ) {
+        if !visited.insert(&strct.ident) {
+            return;
+        }
+        for field in &strct.fields {
+            if let Type::Ident(ident) = &field.ty {
+                if let Some(inner) = types.structs.get(ident) {
+                    visit(inner, types, sorted, visited);
+                }
+            }
+        }
+        sorted.push(strct);
+    }
+
+    let mut sorted = Vec::new();
+    let mut visited = HashSet::new();
+    for api in apis {
+        if let Api::Struct(strct) = api {
+            visit(strct, types, &mut sorted, &mut visited);
+        }
+    }
+    sorted
+}
+
+fn write_enum(out: &mut OutFile, enm: &Enum) {
+    for line in enm.doc.to_string().lines() {
+        writeln!(out, "//{}", line);
+    }
+    write!(out, "enum class {} : ", enm.ident);
+    match &enm.repr {
+        Some(repr) => {
+            out.include.cstdint = true;
+            write_type(out, &Type::Ident(repr.clone()));
+        }
+        None => write!(out, "int"),
+    }
+    writeln!(out, " {{");
+    for variant in &enm.variants {
+        for line in variant.doc.to_string().lines() {
+            writeln!(out, "  //{}", line);
+        }
+        write!(out, "  {} = ", variant.ident);
+        if variant.discriminant == -(1 << 63) {
+            // The literal 9223372036854775808 has no signed type to negate.
+            write!(out, "{} - 1", variant.discriminant + 1);
+        } else if variant.discriminant >= 1 << 63 {
+            write!(out, "{}u", variant.discriminant);
+        } else {
+            write!(out, "{}", variant.discriminant);
+        }
+        writeln!(out, ",");
+    }
+    writeln!(out, "}};");
+}
+
 fn write_struct(out: &mut OutFile, strct: &Struct) {
     for line in strct.doc.to_string().lines() {
         writeln!(out, "//{}", line);
     }
-    writeln!(out, "struct {} final {{", strct.ident);
+    let keyword = if strct.union_token.is_some() {
+        "union"
+    } else {
+        "struct"
+    };
+    writeln!(out, "{} {} final {{", keyword, strct.ident);
+    // Value-initializing every field gives the same zeros and empty strings
+    // that the Rust derive(Default) produces, recursively through nested
+    // shared structs which must derive Default as well. Member initializers
+    // stop a struct from being an aggregate before C++14, so C++11 code gets
+    // plain fields and can still brace-initialize them.
+    if strct.derives.iter().any(|derive| derive == "Default") {
+        writeln!(out, "#if __cplusplus >= 201402L");
+        write_struct_fields(out, strct, true);
+        writeln!(out, "#else");
+        write_struct_fields(out, strct, false);
+        writeln!(out, "#endif");
+    } else {
+        write_struct_fields(out, strct, false);
+    }
+    if strct.constructor.is_some() {
+        write_struct_constructor(out, strct);
+    }
+    if strct.derives.iter().any(|derive| derive == "PartialEq") {
+        write_struct_operator_eq(out, strct);
+    }
+    writeln!(out, "}};");
+}
+
+fn write_struct_fields(out: &mut OutFile, strct: &Struct, value_init: bool) {
     for field in &strct.fields {
         write!(out, "  ");
         write_type_space(out, &field.ty);
-        writeln!(out, "{};", field.ident);
+        write!(out, "{}", field.ident);
+        if let Type::Array(array) = &field.ty {
+            write!(out, "[{}]", array.len);
+        }
+        if value_init {
+            write!(out, "{{}}");
+        }
+        writeln!(out, ";");
+    }
+}
+
+// Combines the field hashes in declaration order, the same order the Rust
+// derive(Hash) feeds them to its hasher, though the values need not agree.
+// Nested shared structs hash through their own specialization, which
+// sort_structs places ahead of this one.
+fn write_struct_hash(out: &mut OutFile, strct: &Struct, types: &Types) {
+    out.include.functional = true;
+    out.include.cstddef = true;
+    let mut path = String::new();
+    for segment in &out.namespace {
+        path += "::";
+        path += &segment.name;
+    }
+    let ident = format!("{}::{}", path, strct.ident);
+    writeln!(out, "namespace std {{");
+    writeln!(out, "template <> struct hash<{}> {{", ident);
+    writeln!(out, "  size_t operator()(const {} &self) const {{", ident,);
+    writeln!(out, "    size_t h = 0;");
+    for field in &strct.fields {
+        write!(out, "    h ^= ");
+        match &field.ty {
+            // Copies into a std::string, which may throw; hence no noexcept.
+            Type::Ident(ident) if ident == RustString => {
+                out.include.string = true;
+                write!(out, "hash<string>{{}}(string(self.{}))", field.ident);
+            }
+            // C++11 has no std::hash for enums.
+            Type::Ident(ident) if types.enums.contains_key(ident) => {
+                write!(
+                    out,
+                    "hash<size_t>{{}}(static_cast<size_t>(self.{}))",
+                    field.ident
+                );
+            }
+            _ => write!(out, "hash<decltype(self.{0})>{{}}(self.{0})", field.ident),
+        }
+        writeln!(out, " + 0x9e3779b9 + (h << 6) + (h >> 2);");
+    }
+    writeln!(out, "    return h;");
+    writeln!(out, "  }}");
+    writeln!(out, "}};");
+    writeln!(out, "}} // namespace std");
+}
+
+// Compares field by field in declaration order, the same as the Rust
+// derive(PartialEq). Nested shared structs derive it too and so bring their
+// own operator==.
+fn write_struct_operator_eq(out: &mut OutFile, strct: &Struct) {
+    writeln!(out);
+    writeln!(
+        out,
+        "  bool operator==(const {} &rhs) const noexcept {{",
+        strct.ident,
+    );
+    write!(out, "    return ");
+    for (i, field) in strct.fields.iter().enumerate() {
+        if i > 0 {
+            write!(out, " && ");
+        }
+        write!(out, "{0} == rhs.{0}", field.ident);
+    }
+    writeln!(out, ";");
+    writeln!(out, "  }}");
+    writeln!(
+        out,
+        "  bool operator!=(const {} &rhs) const noexcept {{",
+        strct.ident,
+    );
+    writeln!(out, "    return !(*this == rhs);");
+    writeln!(out, "  }}");
+}
+
+// Every field is taken by value and moved into place; none of the field types
+// that a shared struct can hold throws on move.
+fn write_struct_constructor(out: &mut OutFile, strct: &Struct) {
+    writeln!(out);
+    // rust::Box has no empty state to default construct into.
+    let has_box = strct
+        .fields
+        .iter()
+        .any(|field| matches!(field.ty, Type::RustBox(_)));
+    if !has_box {
+        writeln!(out, "  {}() = default;", strct.ident);
+    }
+    write!(out, "  {}(", strct.ident);
+    for (i, field) in strct.fields.iter().enumerate() {
+        if i > 0 {
+            write!(out, ", ");
+        }
+        write_type_space(out, &field.ty);
+        write!(out, "{}", field.ident);
+    }
+    write!(out, ") noexcept");
+    for (i, field) in strct.fields.iter().enumerate() {
+        write!(out, "{}", if i == 0 { "\n      : " } else { ", " });
+        let trivial = match &field.ty {
+            Type::Ident(ident) => match Atom::from(ident) {
+                Some(RustString) | Some(CxxString) | Some(CxxU16String) | None => false,
+                Some(_) => true,
+            },
+            _ => false,
+        };
+        if trivial {
+            write!(out, "{}({})", field.ident, field.ident);
+        } else {
+            out.include.utility = true;
+            write!(out, "{}(::std::move({}))", field.ident, field.ident);
+        }
+    }
+    writeln!(out, " {{}}");
+}
+
+// Natural alignment of each field is the layout that 32-bit and 64-bit targets
+// have to agree on; i386 for example aligns 64-bit integers to 4 bytes.
+fn write_fixed_width_asserts(out: &mut OutFile, strct: &Struct, types: &Types) {
+    let layout = layout::struct_layout(strct, types, None).unwrap();
+    let msg = format!(
+        "{} must have the same layout on 32-bit and 64-bit targets",
+        strct.ident,
+    );
+    for (field, offset) in strct.fields.iter().zip(layout.offsets) {
+        writeln!(
+            out,
+            "static_assert(offsetof({}, {}) == {}, \"{}; field `{}` is misplaced\");",
+            strct.ident, field.ident, offset, msg, field.ident,
+        );
+    }
+    writeln!(
+        out,
+        "static_assert(sizeof({}) == {}, \"{}\");",
+        strct.ident, layout.size, msg,
+    );
+    writeln!(
+        out,
+        "static_assert(alignof({}) == {}, \"{}\");",
+        strct.ident, layout.align, msg,
+    );
+}
+
+// The Rust side asserts the same predicted layout from the cxx::bridge macro,
+// so a type that maps to a differently sized C++ type fails on one side or the
+// other instead of going unnoticed.
+fn write_layout_asserts(out: &mut OutFile, strct: &Struct, types: &Types) {
+    let msg = format!(
+        "{} must have the same layout as its Rust definition",
+        strct.ident,
+    );
+    // The standard library's smart pointers need not be standard-layout, and
+    // offsetof is only defined on standard-layout types.
+    let with_offsets = !contains_std_pointer(strct, types);
+    for &pointer_width in &[8, 4] {
+        let layout = match layout::struct_layout(strct, types, Some(pointer_width)) {
+            Some(layout) => layout,
+            None => continue,
+        };
+        let target = format!("sizeof(void *) != {}", pointer_width);
+        if with_offsets {
+            for (field, offset) in strct.fields.iter().zip(layout.offsets) {
+                writeln!(
+                    out,
+                    "static_assert({} || offsetof({}, {}) == {}, \"{}; field `{}` is misplaced\");",
+                    target, strct.ident, field.ident, offset, msg, field.ident,
+                );
+            }
+        }
+        writeln!(
+            out,
+            "static_assert({} || sizeof({}) == {}, \"{}\");",
+            target, strct.ident, layout.size, msg,
+        );
+        writeln!(
+            out,
+            "static_assert({} || alignof({}) == {}, \"{}\");",
+            target, strct.ident, layout.align, msg,
+        );
+    }
+}
+
+// Spelled with sizeof and offsetof rather than the predicted layout, so that
+// the constants are whatever the C++ compiler actually laid out, which the
+// asserts above hold to the Rust definition.
+fn write_layout_constants(out: &mut OutFile, strct: &Struct, types: &Types) {
+    writeln!(out, "constexpr size_t {0}_size = sizeof({0});", strct.ident);
+    writeln!(
+        out,
+        "constexpr size_t {0}_align = alignof({0});",
+        strct.ident,
+    );
+    if contains_std_pointer(strct, types) {
+        return;
+    }
+    for field in &strct.fields {
+        writeln!(
+            out,
+            "constexpr size_t {0}_{1}_offset = offsetof({0}, {1});",
+            strct.ident, field.ident,
+        );
+    }
+}
+
+fn contains_std_pointer(strct: &Struct, types: &Types) -> bool {
+    strct.fields.iter().any(|field| match &field.ty {
+        Type::UniquePtr(_) | Type::UniqueArray(_) | Type::SharedPtr(_) => true,
+        Type::Ident(ident) => match types.structs.get(ident) {
+            Some(inner) => contains_std_pointer(inner, types),
+            None => false,
+        },
+        _ => false,
+    })
+}
+
+// The layout stays opaque to C++; the definition exists only to carry the
+// member functions, which forward to the Rust shims with `*this` as self.
+fn write_struct_with_methods(out: &mut OutFile, ident: &Ident, methods: &[&ExternFn]) {
+    writeln!(out, "struct {} final {{", ident);
+    writeln!(out, "  {}() = delete;", ident);
+    writeln!(out, "  {}(const {} &) = delete;", ident, ident);
+    for method in methods {
+        for line in method.doc.to_string().lines() {
+            writeln!(out, "  //{}", line);
+        }
+        if let Some(deprecated) = &method.deprecated {
+            write_deprecated(out, deprecated);
+        }
+        if method.pure.is_some() {
+            write_pure(out);
+        }
+        write!(out, "  ");
+        let local_name = method.cxx_ident().to_string();
+        let indirect_call = false;
+        write_rust_function_shim_decl(out, &local_name, method, indirect_call);
+        writeln!(out, ";");
     }
     writeln!(out, "}};");
 }
@@ -281,6 +833,46 @@ fn write_struct_decl(out: &mut OutFile, ident: &Ident) {
     writeln!(out, "struct {};", ident);
 }
 
+// Backs the Rust Debug impl of a #[cxx_debug] type. The string is handed to
+// Rust as an owning pointer and freed through UniquePtr<CxxString>.
+fn write_cxx_type_debug(out: &mut OutFile, ident: &Ident) {
+    out.include.string = true;
+    let link_name = mangle::cxx_type_debug(&out.namespace, ident);
+    writeln!(
+        out,
+        "::std::string *{}(const {} &self) noexcept {{",
+        link_name,
+        cxx_type_name(out, ident),
+    );
+    writeln!(out, "  return new ::std::string(self.to_string());");
+    writeln!(out, "}}");
+}
+
+// The object is default constructed; a constructor that throws terminates,
+// same as any other exception escaping into Rust.
+fn write_cxx_type_placement(out: &mut OutFile, ident: &Ident) {
+    out.include.cstddef = true;
+    out.include.new = true;
+    let prefix = mangle::cxx_type_placement(&out.namespace, ident);
+    let ident = cxx_type_name(out, ident);
+    writeln!(out, "size_t {}size() noexcept {{", prefix);
+    writeln!(out, "  return sizeof({});", ident);
+    writeln!(out, "}}");
+    writeln!(out, "size_t {}align() noexcept {{", prefix);
+    writeln!(out, "  return alignof({});", ident);
+    writeln!(out, "}}");
+    writeln!(out, "void {}new({} *ptr) noexcept {{", prefix, ident);
+    writeln!(out, "  new (ptr) {}();", ident);
+    writeln!(out, "}}");
+    writeln!(out, "void {}drop({} *ptr) noexcept {{", prefix, ident);
+    writeln!(out, "  ptr->~{}();", ident);
+    writeln!(out, "}}");
+}
+
+fn cxx_type_name(out: &OutFile, ident: &Ident) -> Ident {
+    out.cxx_names.get(ident).unwrap_or(ident).clone()
+}
+
 fn write_struct_using(out: &mut OutFile, ident: &Ident) {
     writeln!(out, "using {} = {};", ident, ident);
 }
@@ -306,14 +898,28 @@ fn write_exception_glue(out: &mut OutFile, apis: &[Api]) {
 }
 
 fn write_cxx_function_shim(out: &mut OutFile, efn: &ExternFn, types: &Types) {
+    if efn.pure.is_some() {
+        write_pure(out);
+    }
     if efn.throws {
         write!(out, "::rust::Str::Repr ");
     } else {
         write_extern_return_type_space(out, &efn.ret, types);
     }
-    write!(out, "{}cxxbridge02${}(", out.namespace, efn.ident);
+    write_calling_convention(out, &efn.abi);
+    write!(
+        out,
+        "{}(",
+        mangle::extern_fn(&out.namespace, &out.module, efn)
+    );
+    if let Some(receiver) = &efn.receiver {
+        if receiver.mutability.is_none() {
+            write!(out, "const ");
+        }
+        write!(out, "{} &self", cxx_type_name(out, &receiver.ident));
+    }
     for (i, arg) in efn.args.iter().enumerate() {
-        if i > 0 {
+        if i > 0 || efn.receiver.is_some() {
             write!(out, ", ");
         }
         if arg.ty == RustString {
@@ -322,52 +928,154 @@ fn write_cxx_function_shim(out: &mut OutFile, efn: &ExternFn, types: &Types) {
         write_extern_arg(out, arg, types);
     }
     let indirect_return = indirect_return(efn, types);
-    if indirect_return {
-        if !efn.args.is_empty() {
+    if let Some(Type::Tuple(tuple)) = &efn.ret {
+        for (i, elem) in tuple.elems.iter().enumerate() {
+            if i > 0 || efn.receiver.is_some() || !efn.args.is_empty() {
+                write!(out, ", ");
+            }
+            write_type_space(out, elem);
+            write!(out, "*return${}", i);
+        }
+    } else if indirect_return {
+        if efn.receiver.is_some() || !efn.args.is_empty() {
             write!(out, ", ");
         }
         write_indirect_return_type_space(out, efn.ret.as_ref().unwrap());
         write!(out, "*return$");
     }
+    if efn.error_type.is_some() {
+        if efn.receiver.is_some() || !efn.args.is_empty() || indirect_return {
+            write!(out, ", ");
+        }
+        write!(out, "size_t *kind$");
+    }
     writeln!(out, ") noexcept {{");
     write!(out, "  ");
-    write_return_type(out, &efn.ret);
-    write!(out, "(*{}$)(", efn.ident);
+    if efn.time_point.is_some() {
+        out.include.chrono = true;
+        write!(out, "::std::chrono::system_clock::time_point ");
+    } else {
+        write_return_type(out, &efn.ret);
+    }
+    match &efn.receiver {
+        Some(receiver) => write!(
+            out,
+            "({}::*{}$)(",
+            cxx_type_name(out, &receiver.ident),
+            efn.ident,
+        ),
+        None => write!(out, "(*{}$)(", efn.ident),
+    }
     for (i, arg) in efn.args.iter().enumerate() {
         if i > 0 {
             write!(out, ", ");
         }
-        write_type(out, &arg.ty);
+        if efn.time_point_args.contains(&arg.ident) {
+            out.include.chrono = true;
+            write!(out, "::std::chrono::system_clock::time_point");
+        } else {
+            write_type(out, &arg.ty);
+        }
+    }
+    write!(out, ")");
+    match &efn.receiver {
+        // Taking the address as a const member function pointer makes a
+        // mismatch between &self and a non-const C++ method a compile error.
+        Some(receiver) if receiver.mutability.is_none() => write!(out, " const"),
+        _ => {}
+    }
+    write!(out, " = ");
+    if let Some(receiver) = &efn.receiver {
+        write!(out, "&{}::", cxx_type_name(out, &receiver.ident));
+    }
+    match &efn.template {
+        Some(template) => {
+            write!(out, "{}<", template.ident);
+            for (i, arg) in template.args.iter().enumerate() {
+                if i > 0 {
+                    write!(out, ", ");
+                }
+                write_type(out, arg);
+            }
+            writeln!(out, ">;");
+        }
+        None => writeln!(out, "{};", efn.cxx_ident()),
+    }
+    if efn.init_once.is_some() {
+        out.include.mutex = true;
+        writeln!(out, "  static ::std::once_flag once$;");
+        write_static_destructor_assert(out, "once$");
+        if let Some(ret) = &efn.ret {
+            write!(out, "  static ");
+            write_type_space(out, ret);
+            writeln!(out, "value$;");
+            write_static_destructor_assert(out, "value$");
+        }
+        writeln!(out, "  ::std::call_once(once$, [&] {{");
+        write!(out, "  ");
     }
-    writeln!(out, ") = {};", efn.ident);
     write!(out, "  ");
     if efn.throws {
         writeln!(out, "::rust::Str::Repr throw$;");
         writeln!(out, "  ::rust::behavior::trycatch(");
         writeln!(out, "      [&] {{");
-        write!(out, "        ");
+        if efn.error_type.is_some() {
+            writeln!(out, "        try {{");
+            write!(out, "          ");
+        } else {
+            write!(out, "        ");
+        }
     }
-    if indirect_return {
+    if let Some(Type::Tuple(_)) = &efn.ret {
+        write!(out, "auto tuple$ = ");
+    } else if indirect_return {
+        out.include.new = true;
         write!(out, "new (return$) ");
         write_indirect_return_type(out, efn.ret.as_ref().unwrap());
         write!(out, "(");
     } else if efn.ret.is_some() {
-        write!(out, "return ");
+        if efn.init_once.is_some() {
+            write!(out, "value$ = ");
+        } else {
+            write!(out, "return ");
+        }
     }
     match &efn.ret {
         Some(Type::Ref(_)) => write!(out, "&"),
         Some(Type::Str(_)) if !indirect_return => write!(out, "::rust::Str::Repr("),
+        Some(ty @ Type::SliceRef(_)) if !indirect_return => {
+            write_type(out, ty);
+            write!(out, "::Repr(");
+        }
         _ => {}
     }
-    write!(out, "{}$(", efn.ident);
+    if efn.time_point.is_some() {
+        write!(
+            out,
+            "::std::chrono::duration_cast<::std::chrono::nanoseconds>(",
+        );
+    }
+    match &efn.receiver {
+        Some(_) => write!(out, "(self.*{}$)(", efn.ident),
+        None => write!(out, "{}$(", efn.ident),
+    }
     for (i, arg) in efn.args.iter().enumerate() {
         if i > 0 {
             write!(out, ", ");
         }
-        if let Type::RustBox(_) = &arg.ty {
+        if efn.time_point_args.contains(&arg.ident) {
+            // Through the clock's own duration, which may be coarser than
+            // nanoseconds; the time_point constructor only converts implicitly
+            // from a duration that loses no precision.
+            write!(
+                out,
+                "::std::chrono::system_clock::time_point(::std::chrono::duration_cast<::std::chrono::system_clock::duration>(::std::chrono::nanoseconds({})))",
+                arg.ident,
+            );
+        } else if matches!(arg.ty, Type::RustBox(_) | Type::RustArc(_)) {
             write_type(out, &arg.ty);
             write!(out, "::from_raw({})", arg.ident);
-        } else if let Type::UniquePtr(_) = &arg.ty {
+        } else if let Type::UniquePtr(_) | Type::UniqueArray(_) = &arg.ty {
             write_type(out, &arg.ty);
             write!(out, "({})", arg.ident);
         } else if arg.ty == RustString {
@@ -379,26 +1087,80 @@ fn write_cxx_function_shim(out: &mut OutFile, efn: &ExternFn, types: &Types) {
         } else if types.needs_indirect_abi(&arg.ty) {
             out.include.utility = true;
             write!(out, "::std::move(*{})", arg.ident);
+        } else if matches!(&arg.ty, Type::Optional(ty) if is_optional_value(&ty.inner)) {
+            write!(out, "{} ? ", arg.ident);
+            write_type(out, &arg.ty);
+            write!(out, "(*{}) : ::std::nullopt", arg.ident);
         } else {
             write!(out, "{}", arg.ident);
         }
     }
     write!(out, ")");
+    if efn.time_point.is_some() {
+        write!(out, ".time_since_epoch()).count()");
+    }
     match &efn.ret {
-        Some(Type::RustBox(_)) => write!(out, ".into_raw()"),
-        Some(Type::UniquePtr(_)) => write!(out, ".release()"),
-        Some(Type::Str(_)) if !indirect_return => write!(out, ")"),
+        Some(Type::RustBox(_)) | Some(Type::RustArc(_)) => write!(out, ".into_raw()"),
+        Some(Type::UniquePtr(_)) | Some(Type::UniqueArray(_)) => write!(out, ".release()"),
+        Some(Type::Optional(ty)) if is_unique_ptr(&ty.inner) => write!(out, ".release()"),
+        Some(Type::Str(_)) | Some(Type::SliceRef(_)) if !indirect_return => write!(out, ")"),
         _ => {}
     }
-    if indirect_return {
+    if indirect_return && !matches!(efn.ret, Some(Type::Tuple(_))) {
         write!(out, ")");
     }
     writeln!(out, ";");
-    if efn.throws {
-        out.include.cstring = true;
-        writeln!(out, "        throw$.ptr = nullptr;");
-        writeln!(out, "      }},");
-        writeln!(out, "      [&](const char *catch$) noexcept {{");
+    if let Some(Type::Tuple(tuple)) = &efn.ret {
+        // The out-parameters are uninitialized memory, so each element is
+        // constructed in place rather than assigned, and only once the call
+        // has returned without throwing.
+        out.include.new = true;
+        out.include.utility = true;
+        let indent = match (efn.throws, &efn.error_type) {
+            (true, Some(_)) => "          ",
+            (true, None) => "        ",
+            (false, _) => "  ",
+        };
+        for (i, elem) in tuple.elems.iter().enumerate() {
+            write!(out, "{}new (return${}) ", indent, i);
+            write_type(out, elem);
+            writeln!(out, "(::std::move(::std::get<{}>(tuple$)));", i);
+        }
+    }
+    if let Some(error_type) = &efn.error_type {
+        let enm = types.exceptions[error_type];
+        write!(out, "        }}");
+        for (i, variant) in enm.variants.iter().enumerate() {
+            if let Some(exception) = &variant.exception {
+                write!(out, " catch (const ");
+                if exception.leading_colon.is_some() {
+                    write!(out, "::");
+                }
+                for (j, segment) in exception.segments.iter().enumerate() {
+                    if j > 0 {
+                        write!(out, "::");
+                    }
+                    write!(out, "{}", segment.ident);
+                }
+                writeln!(out, " &) {{");
+                writeln!(out, "          *kind$ = {};", i);
+                writeln!(out, "          throw;");
+                write!(out, "        }}");
+            }
+        }
+        writeln!(out);
+    }
+    if efn.init_once.is_some() {
+        writeln!(out, "  }});");
+        if efn.ret.is_some() {
+            writeln!(out, "  return value$;");
+        }
+    }
+    if efn.throws {
+        out.include.cstring = true;
+        writeln!(out, "        throw$.ptr = nullptr;");
+        writeln!(out, "      }},");
+        writeln!(out, "      [&](const char *catch$) noexcept {{");
         writeln!(out, "        throw$.len = ::std::strlen(catch$);");
         writeln!(
             out,
@@ -424,19 +1186,46 @@ fn write_function_pointer_trampoline(
     types: &Types,
 ) {
     out.next_section();
-    let r_trampoline = format!("{}cxxbridge02${}${}$1", out.namespace, efn.ident, var);
+    let link_name = mangle::extern_fn(&out.namespace, &out.module, efn);
+    let r_trampoline = format!("{}${}$1", link_name, var);
     let indirect_call = true;
-    write_rust_function_decl_impl(out, &r_trampoline, f, types, indirect_call);
+    let error_type = None;
+    write_rust_function_decl_impl(
+        out,
+        &r_trampoline,
+        f,
+        types,
+        &None,
+        error_type,
+        indirect_call,
+    );
 
     out.next_section();
-    let c_trampoline = format!("{}cxxbridge02${}${}$0", out.namespace, efn.ident, var);
-    write_rust_function_shim_impl(out, &c_trampoline, f, types, &r_trampoline, indirect_call);
+    let c_trampoline = format!("{}${}$0", link_name, var);
+    write_rust_function_shim_impl(
+        out,
+        &c_trampoline,
+        f,
+        types,
+        &r_trampoline,
+        error_type,
+        indirect_call,
+    );
 }
 
 fn write_rust_function_decl(out: &mut OutFile, efn: &ExternFn, types: &Types) {
-    let link_name = format!("{}cxxbridge02${}", out.namespace, efn.ident);
+    let link_name = mangle::extern_fn(&out.namespace, &out.module, efn);
     let indirect_call = false;
-    write_rust_function_decl_impl(out, &link_name, efn, types, indirect_call);
+    let error_type = efn.error_type.as_ref();
+    write_rust_function_decl_impl(
+        out,
+        &link_name,
+        efn,
+        types,
+        &efn.abi,
+        error_type,
+        indirect_call,
+    );
 }
 
 fn write_rust_function_decl_impl(
@@ -444,15 +1233,27 @@ fn write_rust_function_decl_impl(
     link_name: &str,
     sig: &Signature,
     types: &Types,
+    abi: &Option<LitStr>,
+    error_type: Option<&Ident>,
     indirect_call: bool,
 ) {
-    if sig.throws {
+    if error_type.is_some() {
+        write!(out, "bool ");
+    } else if sig.throws {
         write!(out, "::rust::Str::Repr ");
     } else {
         write_extern_return_type_space(out, &sig.ret, types);
     }
+    write_calling_convention(out, abi);
     write!(out, "{}(", link_name);
     let mut needs_comma = false;
+    if let Some(receiver) = &sig.receiver {
+        if receiver.mutability.is_none() {
+            write!(out, "const ");
+        }
+        write!(out, "{} &self", cxx_type_name(out, &receiver.ident));
+        needs_comma = true;
+    }
     for arg in &sig.args {
         if needs_comma {
             write!(out, ", ");
@@ -468,6 +1269,13 @@ fn write_rust_function_decl_impl(
         write!(out, "*return$");
         needs_comma = true;
     }
+    if let Some(error_type) = error_type {
+        if needs_comma {
+            write!(out, ", ");
+        }
+        write!(out, "{} *error$", cxx_type_name(out, error_type));
+        needs_comma = true;
+    }
     if indirect_call {
         if needs_comma {
             write!(out, ", ");
@@ -478,13 +1286,99 @@ fn write_rust_function_decl_impl(
 }
 
 fn write_rust_function_shim(out: &mut OutFile, efn: &ExternFn, types: &Types) {
+    if let Some(receiver) = &efn.receiver {
+        // Declared within the struct body by write_struct_with_methods, so
+        // only the out-of-line definition is written here.
+        let local_name = format!(
+            "{}::{}",
+            cxx_type_name(out, &receiver.ident),
+            efn.cxx_ident(),
+        );
+        let invoke = mangle::extern_fn(&out.namespace, &out.module, efn);
+        let error_type = efn.error_type.as_ref();
+        let indirect_call = false;
+        write_rust_function_shim_impl(
+            out,
+            &local_name,
+            efn,
+            types,
+            &invoke,
+            error_type,
+            indirect_call,
+        );
+        return;
+    }
     for line in efn.doc.to_string().lines() {
         writeln!(out, "//{}", line);
     }
-    let local_name = efn.ident.to_string();
-    let invoke = format!("{}cxxbridge02${}", out.namespace, efn.ident);
+    if let Some(deprecated) = &efn.deprecated {
+        write_deprecated(out, deprecated);
+    }
+    if efn.pure.is_some() {
+        write_pure(out);
+    }
+    let local_name = efn.cxx_ident().to_string();
+    let invoke = mangle::extern_fn(&out.namespace, &out.module, efn);
+    let error_type = efn.error_type.as_ref();
     let indirect_call = false;
-    write_rust_function_shim_impl(out, &local_name, efn, types, &invoke, indirect_call);
+    write_rust_function_shim_impl(
+        out,
+        &local_name,
+        efn,
+        types,
+        &invoke,
+        error_type,
+        indirect_call,
+    );
+}
+
+// A static with a destructor would run it at exit, in an order that is
+// unspecified across translation units, and trips -Wexit-time-destructors.
+fn write_static_destructor_assert(out: &mut OutFile, var: &str) {
+    out.include.type_traits = true;
+    writeln!(
+        out,
+        "  static_assert(::std::is_trivially_destructible<decltype({})>::value, \"generated statics must not need an exit-time destructor\");",
+        var,
+    );
+}
+
+// Placed between the return type and the name, where MSVC and GCC both accept
+// it. Rust's "system" is stdcall on 32-bit Windows and the C convention
+// everywhere else, so it goes through a macro.
+fn write_calling_convention(out: &mut OutFile, abi: &Option<LitStr>) {
+    if let Some(abi) = abi {
+        match abi.value().as_str() {
+            "system" => write!(out, "CXXBRIDGE02_SYSTEM_ABI "),
+            "stdcall" => write!(out, "__stdcall "),
+            _ => {}
+        }
+    }
+}
+
+// The standard attribute is C++14; older dialects get no annotation.
+fn write_deprecated(out: &mut OutFile, deprecated: &Deprecated) {
+    writeln!(out, "#if __cplusplus >= 201402L");
+    match &deprecated.note {
+        Some(note) => {
+            let note = note
+                .value()
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            writeln!(out, "[[deprecated(\"{}\")]]", note);
+        }
+        None => writeln!(out, "[[deprecated]]"),
+    }
+    writeln!(out, "#endif");
+}
+
+// Lets the C++ optimizer merge repeated calls with the same arguments, which is
+// undefined behavior if the function has side effects, hence opt-in only.
+fn write_pure(out: &mut OutFile) {
+    writeln!(out, "#if defined(__GNUC__)");
+    writeln!(out, "[[gnu::pure]]");
+    writeln!(out, "#endif");
 }
 
 fn write_rust_function_shim_impl(
@@ -493,27 +1387,10 @@ fn write_rust_function_shim_impl(
     sig: &Signature,
     types: &Types,
     invoke: &str,
+    error_type: Option<&Ident>,
     indirect_call: bool,
 ) {
-    write_return_type(out, &sig.ret);
-    write!(out, "{}(", local_name);
-    for (i, arg) in sig.args.iter().enumerate() {
-        if i > 0 {
-            write!(out, ", ");
-        }
-        write_type_space(out, &arg.ty);
-        write!(out, "{}", arg.ident);
-    }
-    if indirect_call {
-        if !sig.args.is_empty() {
-            write!(out, ", ");
-        }
-        write!(out, "void *extern$");
-    }
-    write!(out, ")");
-    if !sig.throws {
-        write!(out, " noexcept");
-    }
+    write_rust_function_shim_decl(out, local_name, sig, indirect_call);
     if out.header {
         writeln!(out, ";");
     } else {
@@ -536,11 +1413,15 @@ fn write_rust_function_shim_impl(
         } else if let Some(ret) = &sig.ret {
             write!(out, "return ");
             match ret {
-                Type::RustBox(_) => {
+                Type::RustBox(_) | Type::RustArc(_) | Type::SliceBox(_) => {
                     write_type(out, ret);
                     write!(out, "::from_raw(");
                 }
-                Type::UniquePtr(_) => {
+                Type::UniquePtr(_) | Type::UniqueArray(_) => {
+                    write_type(out, ret);
+                    write!(out, "(");
+                }
+                Type::Optional(ty) if is_unique_ptr(&ty.inner) => {
                     write_type(out, ret);
                     write!(out, "(");
                 }
@@ -548,34 +1429,51 @@ fn write_rust_function_shim_impl(
                 _ => {}
             }
         }
-        if sig.throws {
+        if let Some(error_type) = error_type {
+            let error_type = cxx_type_name(out, error_type);
+            writeln!(out, "::rust::MaybeUninit<{}> error$;", error_type);
+            write!(out, "  bool failed$ = ");
+        } else if sig.throws {
             write!(out, "::rust::Str::Repr error$ = ");
         }
         write!(out, "{}(", invoke);
+        if sig.receiver.is_some() {
+            write!(out, "*this");
+        }
         for (i, arg) in sig.args.iter().enumerate() {
-            if i > 0 {
+            if i > 0 || sig.receiver.is_some() {
                 write!(out, ", ");
             }
             match &arg.ty {
                 Type::Str(_) => write!(out, "::rust::Str::Repr("),
+                Type::SliceRef(_) => {
+                    write_type(out, &arg.ty);
+                    write!(out, "::Repr(");
+                }
                 ty if types.needs_indirect_abi(ty) => write!(out, "&"),
                 _ => {}
             }
             write!(out, "{}", arg.ident);
             match &arg.ty {
-                Type::RustBox(_) => write!(out, ".into_raw()"),
-                Type::UniquePtr(_) => write!(out, ".release()"),
-                Type::Str(_) => write!(out, ")"),
+                Type::RustBox(_) | Type::RustArc(_) => write!(out, ".into_raw()"),
+                Type::UniquePtr(_) | Type::UniqueArray(_) => write!(out, ".release()"),
+                Type::Str(_) | Type::SliceRef(_) => write!(out, ")"),
                 ty if ty != RustString && types.needs_indirect_abi(ty) => write!(out, "$.value"),
                 _ => {}
             }
         }
         if indirect_return {
-            if !sig.args.is_empty() {
+            if sig.receiver.is_some() || !sig.args.is_empty() {
                 write!(out, ", ");
             }
             write!(out, "&return$.value");
         }
+        if error_type.is_some() {
+            if sig.receiver.is_some() || !sig.args.is_empty() || indirect_return {
+                write!(out, ", ");
+            }
+            write!(out, "&error$.value");
+        }
         if indirect_call {
             if !sig.args.is_empty() || indirect_return {
                 write!(out, ", ");
@@ -583,13 +1481,30 @@ fn write_rust_function_shim_impl(
             write!(out, "extern$");
         }
         write!(out, ")");
-        if let Some(ret) = &sig.ret {
-            if let Type::RustBox(_) | Type::UniquePtr(_) = ret {
-                write!(out, ")");
+        match &sig.ret {
+            Some(Type::RustBox(_))
+            | Some(Type::RustArc(_))
+            | Some(Type::UniquePtr(_))
+            | Some(Type::UniqueArray(_))
+            | Some(Type::SliceBox(_))
+                if !indirect_return =>
+            {
+                write!(out, ")")
             }
+            Some(Type::Optional(ty)) if is_unique_ptr(&ty.inner) => write!(out, ")"),
+            _ => {}
         }
         writeln!(out, ";");
-        if sig.throws {
+        if let Some(error_type) = error_type {
+            out.include.utility = true;
+            writeln!(out, "  if (failed$) {{");
+            writeln!(
+                out,
+                "    throw ::rust::TypedError<{}>(::std::move(error$.value));",
+                cxx_type_name(out, error_type),
+            );
+            writeln!(out, "  }}");
+        } else if sig.throws {
             writeln!(out, "  if (error$.ptr) {{");
             writeln!(out, "    throw ::rust::Error(error$);");
             writeln!(out, "  }}");
@@ -602,6 +1517,38 @@ fn write_rust_function_shim_impl(
     }
 }
 
+fn write_rust_function_shim_decl(
+    out: &mut OutFile,
+    local_name: &str,
+    sig: &Signature,
+    indirect_call: bool,
+) {
+    write_return_type(out, &sig.ret);
+    write!(out, "{}(", local_name);
+    for (i, arg) in sig.args.iter().enumerate() {
+        if i > 0 {
+            write!(out, ", ");
+        }
+        write_type_space(out, &arg.ty);
+        write!(out, "{}", arg.ident);
+    }
+    if indirect_call {
+        if !sig.args.is_empty() {
+            write!(out, ", ");
+        }
+        write!(out, "void *extern$");
+    }
+    write!(out, ")");
+    if let Some(receiver) = &sig.receiver {
+        if receiver.mutability.is_none() {
+            write!(out, " const");
+        }
+    }
+    if !sig.throws {
+        write!(out, " noexcept");
+    }
+}
+
 fn write_return_type(out: &mut OutFile, ty: &Option<Type>) {
     match ty {
         None => write!(out, "void "),
@@ -617,7 +1564,12 @@ fn indirect_return(sig: &Signature, types: &Types) -> bool {
 
 fn write_indirect_return_type(out: &mut OutFile, ty: &Type) {
     match ty {
-        Type::RustBox(ty) | Type::UniquePtr(ty) => {
+        Type::RustBox(ty) | Type::UniquePtr(ty) | Type::UniqueArray(ty) => {
+            write_type_space(out, &ty.inner);
+            write!(out, "*");
+        }
+        Type::RustArc(ty) => {
+            write!(out, "const ");
             write_type_space(out, &ty.inner);
             write!(out, "*");
         }
@@ -629,6 +1581,11 @@ fn write_indirect_return_type(out: &mut OutFile, ty: &Type) {
             write!(out, " *");
         }
         Type::Str(_) => write!(out, "::rust::Str::Repr"),
+        Type::SliceRef(_) => {
+            write_type(out, ty);
+            write!(out, "::Repr");
+        }
+        Type::Optional(ty) => write_indirect_return_type(out, &ty.inner),
         _ => write_type(out, ty),
     }
 }
@@ -636,18 +1593,42 @@ fn write_indirect_return_type(out: &mut OutFile, ty: &Type) {
 fn write_indirect_return_type_space(out: &mut OutFile, ty: &Type) {
     write_indirect_return_type(out, ty);
     match ty {
-        Type::RustBox(_) | Type::UniquePtr(_) | Type::Ref(_) => {}
-        Type::Str(_) => write!(out, " "),
+        Type::RustBox(_)
+        | Type::RustArc(_)
+        | Type::UniquePtr(_)
+        | Type::UniqueArray(_)
+        | Type::Optional(_)
+        | Type::Ref(_) => {}
+        Type::Str(_) | Type::SliceRef(_) => write!(out, " "),
         _ => write_space_after_type(out, ty),
     }
 }
 
 fn write_extern_return_type_space(out: &mut OutFile, ty: &Option<Type>, types: &Types) {
     match ty {
-        Some(Type::RustBox(ty)) | Some(Type::UniquePtr(ty)) => {
+        Some(Type::RustBox(ty)) | Some(Type::UniquePtr(ty)) | Some(Type::UniqueArray(ty)) => {
+            write_type_space(out, &ty.inner);
+            write!(out, "*");
+        }
+        Some(Type::RustArc(ty)) => {
+            write!(out, "const ");
             write_type_space(out, &ty.inner);
             write!(out, "*");
         }
+        Some(Type::Optional(ty)) => match &ty.inner {
+            Type::UniquePtr(ptr) => {
+                write_type_space(out, &ptr.inner);
+                write!(out, "*");
+            }
+            Type::Ref(r) => {
+                if r.mutability.is_none() {
+                    write!(out, "const ");
+                }
+                write_type(out, &r.inner);
+                write!(out, " *");
+            }
+            _ => unreachable!(),
+        },
         Some(Type::Ref(ty)) => {
             if ty.mutability.is_none() {
                 write!(out, "const ");
@@ -656,6 +1637,10 @@ fn write_extern_return_type_space(out: &mut OutFile, ty: &Option<Type>, types: &
             write!(out, " *");
         }
         Some(Type::Str(_)) => write!(out, "::rust::Str::Repr "),
+        Some(ty @ Type::SliceRef(_)) | Some(ty @ Type::SliceBox(_)) => {
+            write_type(out, ty);
+            write!(out, "::Repr ");
+        }
         Some(ty) if types.needs_indirect_abi(ty) => write!(out, "void "),
         _ => write_return_type(out, ty),
     }
@@ -663,11 +1648,25 @@ fn write_extern_return_type_space(out: &mut OutFile, ty: &Option<Type>, types: &
 
 fn write_extern_arg(out: &mut OutFile, arg: &Var, types: &Types) {
     match &arg.ty {
-        Type::RustBox(ty) | Type::UniquePtr(ty) => {
+        Type::RustBox(ty) | Type::UniquePtr(ty) | Type::UniqueArray(ty) => {
+            write_type_space(out, &ty.inner);
+            write!(out, "*");
+        }
+        Type::RustArc(ty) => {
+            write!(out, "const ");
             write_type_space(out, &ty.inner);
             write!(out, "*");
         }
         Type::Str(_) => write!(out, "::rust::Str::Repr "),
+        Type::SliceRef(_) => {
+            write_type(out, &arg.ty);
+            write!(out, "::Repr ");
+        }
+        Type::Optional(ty) if is_optional_value(&ty.inner) => {
+            write!(out, "const ");
+            write_type_space(out, &ty.inner);
+            write!(out, "*");
+        }
         _ => write_type_space(out, &arg.ty),
     }
     if types.needs_indirect_abi(&arg.ty) {
@@ -680,32 +1679,100 @@ fn write_type(out: &mut OutFile, ty: &Type) {
     match ty {
         Type::Ident(ident) => match Atom::from(ident) {
             Some(Bool) => write!(out, "bool"),
+            Some(Char) => write!(out, "char32_t"),
             Some(U8) => write!(out, "uint8_t"),
             Some(U16) => write!(out, "uint16_t"),
             Some(U32) => write!(out, "uint32_t"),
             Some(U64) => write!(out, "uint64_t"),
-            Some(Usize) => write!(out, "size_t"),
+            Some(U128) => write!(out, "unsigned __int128"),
+            Some(Usize) => match &out.cxx_usize {
+                Some(cxx_usize) => write!(out, "{}", cxx_usize),
+                None => write!(out, "size_t"),
+            },
             Some(I8) => write!(out, "int8_t"),
             Some(I16) => write!(out, "int16_t"),
             Some(I32) => write!(out, "int32_t"),
             Some(I64) => write!(out, "int64_t"),
-            Some(Isize) => write!(out, "ssize_t"),
+            Some(I128) => write!(out, "__int128"),
+            Some(Isize) => match &out.cxx_isize {
+                Some(cxx_isize) => write!(out, "{}", cxx_isize),
+                None => write!(out, "ssize_t"),
+            },
             Some(F32) => write!(out, "float"),
             Some(F64) => write!(out, "double"),
             Some(CxxString) => write!(out, "::std::string"),
+            Some(CxxU16String) => write!(out, "::std::u16string"),
             Some(RustString) => write!(out, "::rust::String"),
-            None => write!(out, "{}", ident),
+            None => write!(out, "{}", cxx_type_name(out, ident)),
         },
         Type::RustBox(ty) => {
             write!(out, "::rust::Box<");
             write_type(out, &ty.inner);
             write!(out, ">");
         }
+        Type::RustArc(ty) => {
+            write!(out, "::rust::Arc<");
+            write_type(out, &ty.inner);
+            write!(out, ">");
+        }
+        Type::RustVec(ty) => {
+            write!(out, "::rust::Vec<");
+            write_type(out, &ty.inner);
+            write!(out, ">");
+        }
+        // A null unique_ptr or null pointer represents None.
+        Type::Optional(ty) => match &ty.inner {
+            inner if is_optional_value(inner) => {
+                write!(out, "::std::optional<");
+                write_type(out, inner);
+                write!(out, ">");
+            }
+            Type::Ref(r) => {
+                if r.mutability.is_none() {
+                    write!(out, "const ");
+                }
+                write_type(out, &r.inner);
+                write!(out, " *");
+            }
+            inner => write_type(out, inner),
+        },
         Type::UniquePtr(ptr) => {
             write!(out, "::std::unique_ptr<");
             write_type(out, &ptr.inner);
             write!(out, ">");
         }
+        Type::UniqueArray(ptr) => {
+            write!(out, "::std::unique_ptr<");
+            write_type(out, &ptr.inner);
+            write!(out, "[]>");
+        }
+        Type::SharedPtr(ptr) => {
+            write!(out, "::std::shared_ptr<");
+            write_type(out, &ptr.inner);
+            write!(out, ">");
+        }
+        Type::CxxVector(ty) => {
+            write!(out, "::std::vector<");
+            write_type(out, &ty.inner);
+            write!(out, ">");
+        }
+        Type::CxxSet(ty) => {
+            write!(out, "::std::set<");
+            write_type(out, &ty.inner);
+            write!(out, ">");
+        }
+        Type::CxxMap(ty) => {
+            write!(out, "::std::map<");
+            write_type(out, &ty.first);
+            write!(out, ", ");
+            write_type(out, &ty.second);
+            write!(out, ">");
+        }
+        Type::Borrowed(ty) => {
+            write!(out, "const ");
+            write_type(out, &ty.inner);
+            write!(out, " *");
+        }
         Type::Ref(r) => {
             if r.mutability.is_none() {
                 write!(out, "const ");
@@ -716,6 +1783,37 @@ fn write_type(out: &mut OutFile, ty: &Type) {
         Type::Str(_) => {
             write!(out, "::rust::Str");
         }
+        Type::SliceRef(slice) => {
+            write!(out, "::rust::Slice<");
+            if slice.mutability.is_none() {
+                write!(out, "const ");
+            }
+            write_type(out, &slice.inner);
+            write!(out, ">");
+        }
+        Type::Slice(slice) => write_type(out, &slice.inner),
+        // The length follows the declarator, as written by write_struct.
+        Type::Array(array) => write_type(out, &array.inner),
+        Type::CxxArray(array) => {
+            write!(out, "::std::array<");
+            write_type(out, &array.inner);
+            write!(out, ", {}>", array.len);
+        }
+        Type::SliceBox(ty) => {
+            write!(out, "::rust::Box<");
+            write_type(out, &ty.inner);
+            write!(out, "[]>");
+        }
+        Type::Tuple(tuple) => {
+            write!(out, "::std::tuple<");
+            for (i, elem) in tuple.elems.iter().enumerate() {
+                if i > 0 {
+                    write!(out, ", ");
+                }
+                write_type(out, elem);
+            }
+            write!(out, ">");
+        }
         Type::Fn(f) => {
             write!(out, "::rust::{}<", if f.throws { "TryFn" } else { "Fn" });
             match &f.ret {
@@ -735,6 +1833,15 @@ fn write_type(out: &mut OutFile, ty: &Type) {
     }
 }
 
+fn is_unique_ptr(ty: &Type) -> bool {
+    matches!(ty, Type::UniquePtr(_))
+}
+
+// Option of a primitive, which is std::optional in C++ rather than a pointer.
+fn is_optional_value(ty: &Type) -> bool {
+    matches!(ty, Type::Ident(_))
+}
+
 fn write_type_space(out: &mut OutFile, ty: &Type) {
     write_type(out, ty);
     write_space_after_type(out, ty);
@@ -742,10 +1849,25 @@ fn write_type_space(out: &mut OutFile, ty: &Type) {
 
 fn write_space_after_type(out: &mut OutFile, ty: &Type) {
     match ty {
-        Type::Ident(_) | Type::RustBox(_) | Type::UniquePtr(_) | Type::Str(_) | Type::Fn(_) => {
-            write!(out, " ")
-        }
-        Type::Ref(_) => {}
+        Type::Ident(_)
+        | Type::RustBox(_)
+        | Type::RustArc(_)
+        | Type::RustVec(_)
+        | Type::UniquePtr(_)
+        | Type::UniqueArray(_)
+        | Type::SharedPtr(_)
+        | Type::CxxVector(_)
+        | Type::CxxMap(_)
+        | Type::CxxSet(_)
+        | Type::Str(_)
+        | Type::SliceRef(_)
+        | Type::SliceBox(_)
+        | Type::Tuple(_)
+        | Type::Array(_)
+        | Type::CxxArray(_)
+        | Type::Fn(_) => write!(out, " "),
+        Type::Optional(ty) => write_space_after_type(out, &ty.inner),
+        Type::Ref(_) | Type::Borrowed(_) | Type::Slice(_) => {}
         Type::Void(_) => unreachable!(),
     }
 }
@@ -762,11 +1884,53 @@ fn write_generic_instantiations(out: &mut OutFile, types: &Types) {
                 out.next_section();
                 write_rust_box_extern(out, inner);
             }
+        } else if let Type::SliceBox(ty) = ty {
+            if let Type::Slice(slice) = &ty.inner {
+                if let Type::Ident(inner) = &slice.inner {
+                    if Atom::from(inner).is_none() {
+                        out.next_section();
+                        write_rust_box_slice_extern(out, inner);
+                    }
+                }
+            }
+        } else if let Type::RustArc(ty) = ty {
+            if let Type::Ident(inner) = &ty.inner {
+                out.next_section();
+                write_rust_arc_extern(out, inner);
+            }
+        } else if let Type::RustVec(ty) = ty {
+            if let Type::Ident(inner) = &ty.inner {
+                if Atom::from(inner).is_none() {
+                    out.next_section();
+                    write_rust_vec_extern(out, inner);
+                }
+            }
         } else if let Type::UniquePtr(ptr) = ty {
             if let Type::Ident(inner) = &ptr.inner {
                 if allow_unique_ptr(inner) {
                     out.next_section();
-                    write_unique_ptr(out, inner);
+                    write_unique_ptr(out, inner, types);
+                }
+            }
+        } else if let Type::SharedPtr(ptr) = ty {
+            if let Type::Ident(inner) = &ptr.inner {
+                if allow_unique_ptr(inner) {
+                    out.next_section();
+                    write_shared_ptr(out, inner, types);
+                }
+            }
+        } else if let Type::CxxVector(ptr) = ty {
+            if let Type::Ident(inner) = &ptr.inner {
+                if allow_unique_ptr(inner) {
+                    out.next_section();
+                    write_cxx_vector(out, inner);
+                }
+            }
+        } else if let Type::CxxMap(map) = ty {
+            if let (Type::Ident(key), Type::Ident(value)) = (&map.first, &map.second) {
+                if Atom::from(value).is_none() {
+                    out.next_section();
+                    write_cxx_map(out, key, value);
                 }
             }
         }
@@ -780,20 +1944,46 @@ fn write_generic_instantiations(out: &mut OutFile, types: &Types) {
             if let Type::Ident(inner) = &ty.inner {
                 write_rust_box_impl(out, inner);
             }
+        } else if let Type::SliceBox(ty) = ty {
+            if let Type::Slice(slice) = &ty.inner {
+                if let Type::Ident(inner) = &slice.inner {
+                    if Atom::from(inner).is_none() {
+                        write_rust_box_slice_impl(out, inner);
+                    }
+                }
+            }
+        } else if let Type::RustArc(ty) = ty {
+            if let Type::Ident(inner) = &ty.inner {
+                write_rust_arc_impl(out, inner);
+            }
+        } else if let Type::RustVec(ty) = ty {
+            if let Type::Ident(inner) = &ty.inner {
+                if Atom::from(inner).is_none() {
+                    write_rust_vec_impl(out, inner);
+                }
+            }
         }
     }
     out.end_block("namespace cxxbridge02");
     out.end_block("namespace rust");
 }
 
-fn write_rust_box_extern(out: &mut OutFile, ident: &Ident) {
-    let mut inner = String::new();
-    for name in &out.namespace {
-        inner += name;
-        inner += "::";
+// The C++ spelling of a bridge type inside a template argument, alongside the
+// symbol fragment identifying the instantiation. The latter is derived from the
+// Rust name so it matches what the cxx::bridge macro emits.
+fn instance_names(out: &OutFile, ident: &Ident) -> (String, String) {
+    let mut path = String::new();
+    for segment in &out.namespace {
+        path += &segment.name;
+        path += "::";
     }
-    inner += &ident.to_string();
-    let instance = inner.replace("::", "$");
+    let instance = format!("{}{}", path, ident).replace("::", "$");
+    let inner = format!("{}{}", path, cxx_type_name(out, ident));
+    (inner, instance)
+}
+
+fn write_rust_box_extern(out: &mut OutFile, ident: &Ident) {
+    let (inner, instance) = instance_names(out, ident);
 
     writeln!(out, "#ifndef CXXBRIDGE02_RUST_BOX_{}", instance);
     writeln!(out, "#define CXXBRIDGE02_RUST_BOX_{}", instance);
@@ -811,13 +2001,7 @@ fn write_rust_box_extern(out: &mut OutFile, ident: &Ident) {
 }
 
 fn write_rust_box_impl(out: &mut OutFile, ident: &Ident) {
-    let mut inner = String::new();
-    for name in &out.namespace {
-        inner += name;
-        inner += "::";
-    }
-    inner += &ident.to_string();
-    let instance = inner.replace("::", "$");
+    let (inner, instance) = instance_names(out, ident);
 
     writeln!(out, "template <>");
     writeln!(out, "void Box<{}>::uninit() noexcept {{", inner);
@@ -830,16 +2014,189 @@ fn write_rust_box_impl(out: &mut OutFile, ident: &Ident) {
     writeln!(out, "}}");
 }
 
-fn write_unique_ptr(out: &mut OutFile, ident: &Ident) {
-    out.include.utility = true;
+fn write_rust_box_slice_extern(out: &mut OutFile, ident: &Ident) {
+    let (inner, instance) = instance_names(out, ident);
 
-    let mut inner = String::new();
-    for name in &out.namespace {
-        inner += name;
-        inner += "::";
-    }
-    inner += &ident.to_string();
-    let instance = inner.replace("::", "$");
+    writeln!(out, "#ifndef CXXBRIDGE02_RUST_BOX_SLICE_{}", instance);
+    writeln!(out, "#define CXXBRIDGE02_RUST_BOX_SLICE_{}", instance);
+    writeln!(
+        out,
+        "void cxxbridge02$box_slice${}$drop(::rust::Box<{}[]>::Repr repr) noexcept;",
+        instance, inner,
+    );
+    writeln!(out, "#endif // CXXBRIDGE02_RUST_BOX_SLICE_{}", instance);
+}
+
+fn write_rust_box_slice_impl(out: &mut OutFile, ident: &Ident) {
+    let (inner, instance) = instance_names(out, ident);
+
+    writeln!(out, "template <>");
+    writeln!(out, "void Box<{}[]>::drop() noexcept {{", inner);
+    writeln!(
+        out,
+        "  return cxxbridge02$box_slice${}$drop(this->repr);",
+        instance,
+    );
+    writeln!(out, "}}");
+}
+
+fn write_rust_vec_extern(out: &mut OutFile, ident: &Ident) {
+    let (inner, instance) = instance_names(out, ident);
+
+    writeln!(out, "#ifndef CXXBRIDGE02_RUST_VEC_{}", instance);
+    writeln!(out, "#define CXXBRIDGE02_RUST_VEC_{}", instance);
+    writeln!(
+        out,
+        "void cxxbridge02$rust_vec${}$new(::rust::Vec<{}> *ptr) noexcept;",
+        instance, inner,
+    );
+    writeln!(
+        out,
+        "void cxxbridge02$rust_vec${}$drop(::rust::Vec<{}> *ptr) noexcept;",
+        instance, inner,
+    );
+    writeln!(
+        out,
+        "size_t cxxbridge02$rust_vec${}$len(const ::rust::Vec<{}> *ptr) noexcept;",
+        instance, inner,
+    );
+    writeln!(
+        out,
+        "size_t cxxbridge02$rust_vec${}$capacity(const ::rust::Vec<{}> *ptr) noexcept;",
+        instance, inner,
+    );
+    writeln!(
+        out,
+        "const {} *cxxbridge02$rust_vec${}$data(const ::rust::Vec<{0}> *ptr) noexcept;",
+        inner, instance,
+    );
+    writeln!(
+        out,
+        "void cxxbridge02$rust_vec${}$reserve_total(::rust::Vec<{}> *ptr, size_t cap) noexcept;",
+        instance, inner,
+    );
+    writeln!(
+        out,
+        "void cxxbridge02$rust_vec${}$set_len(::rust::Vec<{}> *ptr, size_t len) noexcept;",
+        instance, inner,
+    );
+    writeln!(out, "#endif // CXXBRIDGE02_RUST_VEC_{}", instance);
+}
+
+fn write_rust_vec_impl(out: &mut OutFile, ident: &Ident) {
+    let (inner, instance) = instance_names(out, ident);
+
+    writeln!(out, "template <>");
+    writeln!(out, "Vec<{}>::Vec() noexcept {{", inner);
+    writeln!(out, "  cxxbridge02$rust_vec${}$new(this);", instance);
+    writeln!(out, "}}");
+
+    writeln!(out, "template <>");
+    writeln!(out, "void Vec<{}>::drop() noexcept {{", inner);
+    writeln!(
+        out,
+        "  return cxxbridge02$rust_vec${}$drop(this);",
+        instance
+    );
+    writeln!(out, "}}");
+
+    writeln!(out, "template <>");
+    writeln!(out, "size_t Vec<{}>::size() const noexcept {{", inner);
+    writeln!(out, "  return cxxbridge02$rust_vec${}$len(this);", instance);
+    writeln!(out, "}}");
+
+    writeln!(out, "template <>");
+    writeln!(out, "size_t Vec<{}>::capacity() const noexcept {{", inner);
+    writeln!(
+        out,
+        "  return cxxbridge02$rust_vec${}$capacity(this);",
+        instance,
+    );
+    writeln!(out, "}}");
+
+    writeln!(out, "template <>");
+    writeln!(out, "const {} *Vec<{0}>::data() const noexcept {{", inner);
+    writeln!(
+        out,
+        "  return cxxbridge02$rust_vec${}$data(this);",
+        instance
+    );
+    writeln!(out, "}}");
+
+    writeln!(out, "template <>");
+    writeln!(
+        out,
+        "void Vec<{}>::reserve(size_t new_cap) noexcept {{",
+        inner
+    );
+    writeln!(
+        out,
+        "  return cxxbridge02$rust_vec${}$reserve_total(this, new_cap);",
+        instance,
+    );
+    writeln!(out, "}}");
+
+    writeln!(out, "template <>");
+    writeln!(out, "void Vec<{}>::set_len(size_t len) noexcept {{", inner);
+    writeln!(
+        out,
+        "  return cxxbridge02$rust_vec${}$set_len(this, len);",
+        instance,
+    );
+    writeln!(out, "}}");
+}
+
+fn write_rust_arc_extern(out: &mut OutFile, ident: &Ident) {
+    let (inner, instance) = instance_names(out, ident);
+
+    writeln!(out, "#ifndef CXXBRIDGE02_RUST_ARC_{}", instance);
+    writeln!(out, "#define CXXBRIDGE02_RUST_ARC_{}", instance);
+    writeln!(
+        out,
+        "void cxxbridge02$arc${}$clone(const {} *ptr) noexcept;",
+        instance, inner,
+    );
+    writeln!(
+        out,
+        "void cxxbridge02$arc${}$drop(const {} *ptr) noexcept;",
+        instance, inner,
+    );
+    writeln!(out, "#endif // CXXBRIDGE02_RUST_ARC_{}", instance);
+}
+
+fn write_rust_arc_impl(out: &mut OutFile, ident: &Ident) {
+    let (inner, instance) = instance_names(out, ident);
+
+    writeln!(out, "template <>");
+    writeln!(out, "void Arc<{}>::clone() noexcept {{", inner);
+    writeln!(
+        out,
+        "  return cxxbridge02$arc${}$clone(this->ptr);",
+        instance
+    );
+    writeln!(out, "}}");
+
+    writeln!(out, "template <>");
+    writeln!(out, "void Arc<{}>::drop() noexcept {{", inner);
+    writeln!(
+        out,
+        "  return cxxbridge02$arc${}$drop(this->ptr);",
+        instance
+    );
+    writeln!(out, "}}");
+}
+
+fn write_unique_ptr(out: &mut OutFile, ident: &Ident, types: &Types) {
+    let (inner, instance) = instance_names(out, ident);
+
+    let can_construct = types.structs.contains_key(ident);
+    write_unique_ptr_common(out, &inner, &instance, can_construct);
+}
+
+// Shared by unique_ptr<T> and unique_ptr<std::vector<T>>. Only a shared struct
+// is ever constructed from a Rust value, so nothing else gets a $new.
+fn write_unique_ptr_common(out: &mut OutFile, inner: &str, instance: &str, can_construct: bool) {
+    out.include.utility = true;
 
     writeln!(out, "#ifndef CXXBRIDGE02_UNIQUE_PTR_{}", instance);
     writeln!(out, "#define CXXBRIDGE02_UNIQUE_PTR_{}", instance);
@@ -860,17 +2217,20 @@ fn write_unique_ptr(out: &mut OutFile, ident: &Ident) {
     );
     writeln!(out, "  new (ptr) ::std::unique_ptr<{}>();", inner);
     writeln!(out, "}}");
-    writeln!(
-        out,
-        "void cxxbridge02$unique_ptr${}$new(::std::unique_ptr<{}> *ptr, {} *value) noexcept {{",
-        instance, inner, inner,
-    );
-    writeln!(
-        out,
-        "  new (ptr) ::std::unique_ptr<{}>(new {}(::std::move(*value)));",
-        inner, inner,
-    );
-    writeln!(out, "}}");
+    if can_construct {
+        write_move_constructible_assert(out, inner, "UniquePtr::new");
+        writeln!(
+            out,
+            "void cxxbridge02$unique_ptr${}$new(::std::unique_ptr<{}> *ptr, {} *value) noexcept {{",
+            instance, inner, inner,
+        );
+        writeln!(
+            out,
+            "  new (ptr) ::std::unique_ptr<{}>(new {}(::std::move(*value)));",
+            inner, inner,
+        );
+        writeln!(out, "}}");
+    }
     writeln!(
         out,
         "void cxxbridge02$unique_ptr${}$raw(::std::unique_ptr<{}> *ptr, {} *raw) noexcept {{",
@@ -901,3 +2261,159 @@ fn write_unique_ptr(out: &mut OutFile, ident: &Ident) {
     writeln!(out, "}}");
     writeln!(out, "#endif // CXXBRIDGE02_UNIQUE_PTR_{}", instance);
 }
+
+fn write_shared_ptr(out: &mut OutFile, ident: &Ident, types: &Types) {
+    let (inner, instance) = instance_names(out, ident);
+
+    out.include.utility = true;
+
+    writeln!(out, "#ifndef CXXBRIDGE02_SHARED_PTR_{}", instance);
+    writeln!(out, "#define CXXBRIDGE02_SHARED_PTR_{}", instance);
+    writeln!(
+        out,
+        "static_assert(sizeof(::std::shared_ptr<{}>) == 2 * sizeof(void *), \"\");",
+        inner,
+    );
+    writeln!(
+        out,
+        "static_assert(alignof(::std::shared_ptr<{}>) == alignof(void *), \"\");",
+        inner,
+    );
+    writeln!(
+        out,
+        "void cxxbridge02$shared_ptr${}$null(::std::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  new (ptr) ::std::shared_ptr<{}>();", inner);
+    writeln!(out, "}}");
+    if types.structs.contains_key(ident) {
+        write_move_constructible_assert(out, &inner, "SharedPtr::new");
+        writeln!(
+            out,
+            "void cxxbridge02$shared_ptr${}$new({} *value, ::std::shared_ptr<{}> *ptr) noexcept {{",
+            instance, inner, inner,
+        );
+        writeln!(
+            out,
+            "  new (ptr) ::std::shared_ptr<{}>(new {}(::std::move(*value)));",
+            inner, inner,
+        );
+        writeln!(out, "}}");
+    }
+    writeln!(
+        out,
+        "void cxxbridge02$shared_ptr${}$clone(const ::std::shared_ptr<{}>& self, ::std::shared_ptr<{}> *ptr) noexcept {{",
+        instance, inner, inner,
+    );
+    writeln!(out, "  new (ptr) ::std::shared_ptr<{}>(self);", inner);
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "const {} *cxxbridge02$shared_ptr${}$get(const ::std::shared_ptr<{}>& self) noexcept {{",
+        inner, instance, inner,
+    );
+    writeln!(out, "  return self.get();");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "void cxxbridge02$shared_ptr${}$drop(::std::shared_ptr<{}> *self) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  self->~shared_ptr();");
+    writeln!(out, "}}");
+    writeln!(out, "#endif // CXXBRIDGE02_SHARED_PTR_{}", instance);
+}
+
+// Names the requirement up front instead of leaving it to an error from deep
+// inside the new-expression.
+fn write_move_constructible_assert(out: &mut OutFile, inner: &str, operation: &str) {
+    out.include.type_traits = true;
+    writeln!(
+        out,
+        "static_assert(::std::is_move_constructible<{}>::value, \"{} requires {} to be move constructible\");",
+        inner, operation, inner,
+    );
+}
+
+fn write_cxx_vector(out: &mut OutFile, ident: &Ident) {
+    let (inner, instance) = instance_names(out, ident);
+
+    writeln!(out, "#ifndef CXXBRIDGE02_VECTOR_{}", instance);
+    writeln!(out, "#define CXXBRIDGE02_VECTOR_{}", instance);
+    writeln!(
+        out,
+        "size_t cxxbridge02$std$vector${}$size(const ::std::vector<{}> &s) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  return s.size();");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "const {} *cxxbridge02$std$vector${}$get_unchecked(const ::std::vector<{}> &s, size_t pos) noexcept {{",
+        inner, instance, inner,
+    );
+    writeln!(out, "  return &s[pos];");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "void cxxbridge02$std$vector${}$pop_back(::std::vector<{}> &s) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  s.pop_back();");
+    writeln!(out, "}}");
+    writeln!(
+        out,
+        "void cxxbridge02$std$vector${}$clear(::std::vector<{}> &s) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  s.clear();");
+    writeln!(out, "}}");
+    write_move_constructible_assert(out, &inner, "Vector::reserve");
+    writeln!(
+        out,
+        "void cxxbridge02$std$vector${}$reserve(::std::vector<{}> &s, size_t new_cap) noexcept {{",
+        instance, inner,
+    );
+    writeln!(out, "  s.reserve(new_cap);");
+    writeln!(out, "}}");
+    writeln!(out, "#endif // CXXBRIDGE02_VECTOR_{}", instance);
+
+    out.next_section();
+    let vector = format!("::std::vector<{}>", inner);
+    let vector_instance = format!("std$vector${}", instance);
+    write_unique_ptr_common(out, &vector, &vector_instance, false);
+}
+
+fn write_cxx_map(out: &mut OutFile, key: &Ident, value: &Ident) {
+    let (inner, _) = instance_names(out, value);
+    let prefix = mangle::std_map(&out.namespace, key, value);
+    let instance = prefix["cxxbridge02$std$map$".len()..].trim_end_matches('$');
+    let guard = format!("CXXBRIDGE02_MAP_{}", instance);
+    let key = Type::Ident(key.clone());
+
+    writeln!(out, "#ifndef {}", guard);
+    writeln!(out, "#define {}", guard);
+    write!(out, "size_t {}size(const ::std::map<", prefix);
+    write_type(out, &key);
+    writeln!(out, ", {}> &m) noexcept {{", inner);
+    writeln!(out, "  return m.size();");
+    writeln!(out, "}}");
+    write!(out, "const {} *{}get(const ::std::map<", inner, prefix);
+    write_type(out, &key);
+    write!(out, ", {}> &m, const ", inner);
+    write_type(out, &key);
+    writeln!(out, " &key) noexcept {{");
+    writeln!(out, "  auto it = m.find(key);");
+    writeln!(out, "  return it == m.end() ? nullptr : &it->second;");
+    writeln!(out, "}}");
+    write!(out, "void {}iterate(const ::std::map<", prefix);
+    write_type(out, &key);
+    write!(out, ", {}> &m, void (*visit)(void *, const ", inner);
+    write_type(out, &key);
+    writeln!(out, " *, const {} *), void *ctx) noexcept {{", inner);
+    writeln!(out, "  for (const auto &entry : m) {{");
+    writeln!(out, "    visit(ctx, &entry.first, &entry.second);");
+    writeln!(out, "  }}");
+    writeln!(out, "}}");
+    writeln!(out, "#endif // {}", guard);
+}
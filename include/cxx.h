@@ -5,6 +5,9 @@
 #include <exception>
 #include <iosfwd>
 #include <string>
+#if __cplusplus >= 201703L
+#include <string_view>
+#endif
 #include <type_traits>
 #include <utility>
 
@@ -35,13 +38,75 @@ public:
   size_t size() const noexcept;
   size_t length() const noexcept;
 
+  void swap(String &other) noexcept { std::swap(this->repr, other.repr); }
+  friend void swap(String &lhs, String &rhs) noexcept { lhs.swap(rhs); }
+
+  // Byte-wise comparisons, without converting either side.
+  friend bool operator==(const String &lhs, const String &rhs) noexcept {
+    return eq(lhs, rhs.data(), rhs.size());
+  }
+  friend bool operator==(const String &lhs, const std::string &rhs) noexcept {
+    return eq(lhs, rhs.data(), rhs.size());
+  }
+  friend bool operator==(const std::string &lhs, const String &rhs) noexcept {
+    return eq(rhs, lhs.data(), lhs.size());
+  }
+  friend bool operator==(const String &lhs, const char *rhs) noexcept {
+    return eq(lhs, rhs, std::char_traits<char>::length(rhs));
+  }
+  friend bool operator==(const char *lhs, const String &rhs) noexcept {
+    return eq(rhs, lhs, std::char_traits<char>::length(lhs));
+  }
+#if __cplusplus >= 201703L
+  friend bool operator==(const String &lhs, std::string_view rhs) noexcept {
+    return eq(lhs, rhs.data(), rhs.size());
+  }
+  friend bool operator==(std::string_view lhs, const String &rhs) noexcept {
+    return eq(rhs, lhs.data(), lhs.size());
+  }
+#endif
+
+  friend bool operator!=(const String &lhs, const String &rhs) noexcept {
+    return !(lhs == rhs);
+  }
+  friend bool operator!=(const String &lhs, const std::string &rhs) noexcept {
+    return !(lhs == rhs);
+  }
+  friend bool operator!=(const std::string &lhs, const String &rhs) noexcept {
+    return !(lhs == rhs);
+  }
+  friend bool operator!=(const String &lhs, const char *rhs) noexcept {
+    return !(lhs == rhs);
+  }
+  friend bool operator!=(const char *lhs, const String &rhs) noexcept {
+    return !(lhs == rhs);
+  }
+#if __cplusplus >= 201703L
+  friend bool operator!=(const String &lhs, std::string_view rhs) noexcept {
+    return !(lhs == rhs);
+  }
+  friend bool operator!=(std::string_view lhs, const String &rhs) noexcept {
+    return !(lhs == rhs);
+  }
+#endif
+
   // Internal API only intended for the cxxbridge code generator.
   String(unsafe_bitcopy_t, const String &) noexcept;
 
 private:
+  static bool eq(const String &lhs, const char *data, size_t size) noexcept {
+    return lhs.size() == size &&
+           std::char_traits<char>::compare(lhs.data(), data, size) == 0;
+  }
+
   // Size and alignment statically verified by rust_string.rs.
   std::array<uintptr_t, 3> repr;
 };
+
+// Shared structs holding a String are moved across the bridge by the
+// generated shims, which are noexcept.
+static_assert(std::is_nothrow_move_constructible<String>::value, "");
+static_assert(std::is_nothrow_move_assignable<String>::value, "");
 #endif // CXXBRIDGE02_RUST_STRING
 
 #ifndef CXXBRIDGE02_RUST_STR
@@ -53,11 +118,21 @@ public:
 
   Str(const std::string &);
   Str(const char *);
+  Str(const char *, size_t);
   Str(std::string &&) = delete;
+#if __cplusplus >= 201703L
+  Str(std::string_view s) : Str(s.data(), s.size()) {}
+#endif
 
   Str &operator=(Str) noexcept;
 
   explicit operator std::string() const;
+#if __cplusplus >= 201703L
+  // Byte view of the same data; no UTF-8 decoding takes place.
+  operator std::string_view() const noexcept {
+    return std::string_view(this->data(), this->size());
+  }
+#endif
 
   // Note: no null terminator.
   const char *data() const noexcept;
@@ -80,6 +155,160 @@ private:
 };
 #endif // CXXBRIDGE02_RUST_STR
 
+#ifndef CXXBRIDGE02_RUST_SLICE
+#define CXXBRIDGE02_RUST_SLICE
+// A Slice<T> received from Rust refers to a unique borrow &mut [T] and a
+// Slice<const T> to a shared borrow &[T], either of which lasts only for the
+// duration of the call. Only elements data()[0] through data()[size() - 1]
+// may be accessed, and the pointer must not be retained past the end of the
+// call.
+//
+// The same rules apply in the other direction. A Slice<T> handed to Rust
+// must not overlap any other slice or reference that is in use while Rust
+// holds it, and the elements must stay alive and in place until the call
+// returns. A null pointer is only accepted together with a size of 0, such
+// as from an empty std::vector, and is replaced by a dangling non-null one.
+// Throws std::out_of_range, or aborts if built without exceptions.
+[[noreturn]] void slice_out_of_range();
+
+template <typename T> class Slice final {
+public:
+  Slice() noexcept : repr(Repr{reinterpret_cast<T *>(alignof(T)), 0}) {}
+  Slice(const Slice<T> &) noexcept = default;
+
+  Slice(T *s, size_t count) noexcept
+      : repr(Repr{s == nullptr ? reinterpret_cast<T *>(alignof(T)) : s,
+                  count}) {}
+
+  Slice &operator=(Slice<T> other) noexcept {
+    this->repr = other.repr;
+    return *this;
+  }
+
+  T *data() const noexcept { return this->repr.ptr; }
+  size_t size() const noexcept { return this->repr.len; }
+  size_t length() const noexcept { return this->repr.len; }
+
+  // A view of `len` elements starting at `start`, into the same buffer.
+  Slice<T> subslice(size_t start, size_t len) const {
+    if (start > this->repr.len || len > this->repr.len - start) {
+      slice_out_of_range();
+    }
+    return Slice<T>(this->repr.ptr + start, len);
+  }
+
+  // Repr is PRIVATE; must not be used other than by our generated code.
+  //
+  // Used for &[T] and &mut [T] slices of primitives, enums and shared structs
+  // without owned fields. Not necessarily ABI compatible with &[T] or &mut [T].
+  // Codegen will translate to cxx::rust_slice::RustSlice which matches this
+  // layout.
+  struct Repr {
+    T *ptr;
+    size_t len;
+  };
+  Slice(Repr repr_) noexcept : repr(repr_) {}
+  explicit operator Repr() noexcept { return this->repr; }
+
+private:
+  Repr repr;
+};
+#endif // CXXBRIDGE02_RUST_SLICE
+
+#ifndef CXXBRIDGE02_RUST_VEC
+#define CXXBRIDGE02_RUST_VEC
+// All allocation goes through Rust's global allocator. A Vec held by value in
+// C++, whether received from Rust or constructed here, such as an element
+// about to be pushed into a Vec<Vec<uint8_t>>, is freed by its destructor
+// unless it was moved into Rust's ownership first.
+// Only Vec<uint8_t>, Vec<Vec<uint8_t>> and Vec<String> are implemented so far.
+template <typename T> class Vec final {
+public:
+  using value_type = T;
+
+  Vec() noexcept;
+  Vec(const Vec &) = delete;
+  Vec(Vec &&other) noexcept : repr(other.repr) { new (&other) Vec(); }
+  ~Vec() noexcept { this->drop(); }
+  Vec &operator=(const Vec &) = delete;
+
+  size_t size() const noexcept;
+  size_t capacity() const noexcept;
+  bool empty() const noexcept { return this->size() == 0; }
+  const T *data() const noexcept;
+  T *data() noexcept {
+    return const_cast<T *>(static_cast<const Vec *>(this)->data());
+  }
+
+  const T &operator[](size_t n) const noexcept { return this->data()[n]; }
+  T &operator[](size_t n) noexcept { return this->data()[n]; }
+
+  // Ensures a total capacity of at least new_cap elements.
+  void reserve(size_t new_cap) noexcept;
+  void push_back(const T &value) noexcept {
+    // Copied first, since value may live in the buffer that reserve frees.
+    T copy(value);
+    this->push_back(std::move(copy));
+  }
+  void push_back(T &&value) noexcept {
+    auto len = this->size();
+    this->reserve(len + 1);
+    ::new (this->data() + len) T(std::move(value));
+    this->set_len(len + 1);
+  }
+  void resize(size_t count) noexcept {
+    auto len = this->size();
+    if (count < len) {
+      this->truncate(count);
+      return;
+    }
+    this->reserve(count);
+    for (auto i = len; i < count; i++) {
+      ::new (this->data() + i) T();
+    }
+    this->set_len(count);
+  }
+  void clear() noexcept { this->truncate(0); }
+
+private:
+  void set_len(size_t len) noexcept;
+  void drop() noexcept;
+  void truncate(size_t len) noexcept {
+    auto old_len = this->size();
+    auto data = this->data();
+    this->set_len(len);
+    for (auto i = len; i < old_len; i++) {
+      data[i].~T();
+    }
+  }
+
+  // Size and alignment statically verified by rust_vec.rs.
+  std::array<uintptr_t, 3> repr;
+};
+
+template <> size_t Vec<uint8_t>::size() const noexcept;
+template <> size_t Vec<uint8_t>::capacity() const noexcept;
+template <> const uint8_t *Vec<uint8_t>::data() const noexcept;
+template <> void Vec<uint8_t>::reserve(size_t new_cap) noexcept;
+template <> void Vec<uint8_t>::set_len(size_t len) noexcept;
+template <> Vec<uint8_t>::Vec() noexcept;
+template <> void Vec<uint8_t>::drop() noexcept;
+template <> size_t Vec<Vec<uint8_t>>::size() const noexcept;
+template <> size_t Vec<Vec<uint8_t>>::capacity() const noexcept;
+template <> const Vec<uint8_t> *Vec<Vec<uint8_t>>::data() const noexcept;
+template <> void Vec<Vec<uint8_t>>::reserve(size_t new_cap) noexcept;
+template <> void Vec<Vec<uint8_t>>::set_len(size_t len) noexcept;
+template <> Vec<Vec<uint8_t>>::Vec() noexcept;
+template <> void Vec<Vec<uint8_t>>::drop() noexcept;
+template <> size_t Vec<String>::size() const noexcept;
+template <> size_t Vec<String>::capacity() const noexcept;
+template <> const String *Vec<String>::data() const noexcept;
+template <> void Vec<String>::reserve(size_t new_cap) noexcept;
+template <> void Vec<String>::set_len(size_t len) noexcept;
+template <> Vec<String>::Vec() noexcept;
+template <> void Vec<String>::drop() noexcept;
+#endif // CXXBRIDGE02_RUST_VEC
+
 #ifndef CXXBRIDGE02_RUST_BOX
 #define CXXBRIDGE02_RUST_BOX
 template <typename T> class Box final {
@@ -125,6 +354,10 @@ public:
   T *operator->() noexcept { return this->ptr; }
   T &operator*() noexcept { return *this->ptr; }
 
+  // Rust never hands out a null Box; it is only empty after being moved from
+  // or released by into_raw, like a std::unique_ptr.
+  explicit operator bool() const noexcept { return this->ptr != nullptr; }
+
   // Important: requires that `raw` came from an into_raw call. Do not pass a
   // pointer from `new` or any other source.
   static Box from_raw(T *raw) noexcept {
@@ -147,6 +380,132 @@ private:
 };
 #endif // CXXBRIDGE02_RUST_BOX
 
+#ifndef CXXBRIDGE02_RUST_BOX_SLICE
+#define CXXBRIDGE02_RUST_BOX_SLICE
+// A Box<[T]> received from Rust. The buffer is owned, and is handed back to
+// Rust by the destructor to be freed with the layout it was allocated with.
+template <typename T> class Box<T[]> final {
+public:
+  using value_type = T;
+
+  Box(Box &&other) noexcept : repr(other.repr) { other.repr.ptr = nullptr; }
+  Box &operator=(Box &&other) noexcept {
+    if (this->repr.ptr) {
+      this->drop();
+    }
+    this->repr = other.repr;
+    other.repr.ptr = nullptr;
+    return *this;
+  }
+  ~Box() noexcept {
+    if (this->repr.ptr) {
+      this->drop();
+    }
+  }
+
+  const T *data() const noexcept { return this->repr.ptr; }
+  T *data() noexcept { return this->repr.ptr; }
+  size_t size() const noexcept { return this->repr.len; }
+  const T &operator[](size_t n) const noexcept { return this->repr.ptr[n]; }
+  T &operator[](size_t n) noexcept { return this->repr.ptr[n]; }
+  explicit operator bool() const noexcept { return this->repr.ptr != nullptr; }
+
+  // Repr is PRIVATE; must not be used other than by our generated code.
+  //
+  // Codegen will translate to cxx::rust_slice::RustSlice which matches this
+  // layout.
+  struct Repr {
+    T *ptr;
+    size_t len;
+  };
+  static Box from_raw(Repr repr) noexcept {
+    Box box;
+    box.repr = repr;
+    return box;
+  }
+  Repr into_raw() noexcept {
+    Repr repr = this->repr;
+    this->repr.ptr = nullptr;
+    return repr;
+  }
+
+private:
+  Box() noexcept {}
+  void drop() noexcept;
+  Repr repr;
+};
+
+template <> void Box<uint8_t[]>::drop() noexcept;
+template <> void Box<uint16_t[]>::drop() noexcept;
+template <> void Box<uint32_t[]>::drop() noexcept;
+template <> void Box<uint64_t[]>::drop() noexcept;
+template <> void Box<int8_t[]>::drop() noexcept;
+template <> void Box<int16_t[]>::drop() noexcept;
+template <> void Box<int32_t[]>::drop() noexcept;
+template <> void Box<int64_t[]>::drop() noexcept;
+template <> void Box<float[]>::drop() noexcept;
+template <> void Box<double[]>::drop() noexcept;
+#endif // CXXBRIDGE02_RUST_BOX_SLICE
+
+#ifndef CXXBRIDGE02_RUST_ARC
+#define CXXBRIDGE02_RUST_ARC
+template <typename T> class Arc final {
+public:
+  using value_type = T;
+
+  Arc(const Arc &other) noexcept : ptr(other.ptr) { this->clone(); }
+  Arc(Arc &&other) noexcept : ptr(other.ptr) { other.ptr = nullptr; }
+  Arc &operator=(const Arc &other) noexcept {
+    if (this->ptr != other.ptr) {
+      if (this->ptr) {
+        this->drop();
+      }
+      this->ptr = other.ptr;
+      this->clone();
+    }
+    return *this;
+  }
+  Arc &operator=(Arc &&other) noexcept {
+    if (this->ptr) {
+      this->drop();
+    }
+    this->ptr = other.ptr;
+    other.ptr = nullptr;
+    return *this;
+  }
+  ~Arc() noexcept {
+    if (this->ptr) {
+      this->drop();
+    }
+  }
+
+  // Shared ownership only ever hands out shared access, as in Rust.
+  const T *operator->() const noexcept { return this->ptr; }
+  const T &operator*() const noexcept { return *this->ptr; }
+  explicit operator bool() const noexcept { return this->ptr != nullptr; }
+
+  // Important: requires that `raw` came from an into_raw call. Do not pass a
+  // pointer from `new` or any other source.
+  static Arc from_raw(const T *raw) noexcept {
+    Arc arc;
+    arc.ptr = raw;
+    return arc;
+  }
+
+  const T *into_raw() noexcept {
+    const T *raw = this->ptr;
+    this->ptr = nullptr;
+    return raw;
+  }
+
+private:
+  Arc() noexcept {}
+  void clone() noexcept;
+  void drop() noexcept;
+  const T *ptr;
+};
+#endif // CXXBRIDGE02_RUST_ARC
+
 #ifndef CXXBRIDGE02_RUST_FN
 #define CXXBRIDGE02_RUST_FN
 template <typename Signature, bool Throws = false> class Fn;
@@ -179,6 +538,21 @@ private:
 };
 #endif // CXXBRIDGE02_RUST_ERROR
 
+#ifndef CXXBRIDGE02_RUST_TYPED_ERROR
+#define CXXBRIDGE02_RUST_TYPED_ERROR
+template <typename E> class TypedError final : public std::exception {
+public:
+  TypedError(E &&err) : err(std::move(err)) {}
+  const char *what() const noexcept override { return "rust::TypedError"; }
+
+  const E &error() const noexcept { return this->err; }
+  E &error() noexcept { return this->err; }
+
+private:
+  E err;
+};
+#endif // CXXBRIDGE02_RUST_TYPED_ERROR
+
 std::ostream &operator<<(std::ostream &, const String &);
 std::ostream &operator<<(std::ostream &, const Str &);
 
@@ -186,7 +560,9 @@ std::ostream &operator<<(std::ostream &, const Str &);
 using string = String;
 using str = Str;
 template <class T> using box = Box<T>;
+template <class T> using arc = Arc<T>;
 using error = Error;
+template <class E> using typed_error = TypedError<E>;
 template <typename Signature, bool Throws = false>
 using fn = Fn<Signature, Throws>;
 template <typename Signature> using try_fn = TryFn<Signature>;
@@ -201,7 +577,7 @@ constexpr unsafe_bitcopy_t unsafe_bitcopy{};
 
 template <typename Ret, typename... Args, bool Throws>
 Ret Fn<Ret(Args...), Throws>::operator()(Args... args) noexcept(!Throws) {
-  return (*this->trampoline)(std::move(args)..., this->fn);
+  return (*this->trampoline)(std::forward<Args>(args)..., this->fn);
 }
 
 } // namespace cxxbridge02
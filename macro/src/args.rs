@@ -0,0 +1,50 @@
+use crate::namespace::Namespace;
+use crate::syntax::ident;
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{Ident, Token};
+
+mod kw {
+    syn::custom_keyword!(namespace);
+    syn::custom_keyword!(module);
+}
+
+pub struct Args {
+    pub namespace: Namespace,
+    // The `module = path::to::ffi` argument, with its segments joined by `$`
+    // as they appear in link names.
+    pub module: Option<String>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut namespace = Namespace::none();
+        let mut module = None;
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::namespace) {
+                input.parse::<kw::namespace>()?;
+                input.parse::<Token![=]>()?;
+                namespace = input.parse()?;
+            } else if lookahead.peek(kw::module) {
+                input.parse::<kw::module>()?;
+                input.parse::<Token![=]>()?;
+                let mut segments = Vec::new();
+                loop {
+                    let ident: Ident = input.parse()?;
+                    ident::check(&ident)?;
+                    segments.push(ident.to_string());
+                    if input.parse::<Option<Token![::]>>()?.is_none() {
+                        break;
+                    }
+                }
+                module = Some(segments.join("$"));
+            } else {
+                return Err(lookahead.error());
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(Args { namespace, module })
+    }
+}
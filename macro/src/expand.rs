@@ -1,12 +1,18 @@
+use crate::args::Args;
 use crate::namespace::Namespace;
 use crate::syntax::atom::Atom::{self, *};
-use crate::syntax::{self, check, Api, ExternFn, ExternType, Signature, Struct, Type, Types};
-use proc_macro2::{Ident, Span, TokenStream};
+use crate::syntax::{
+    self, check, layout, mangle, Api, Enum, ExceptionEnum, ExternFn, ExternType, Signature, Struct,
+    Type, Types,
+};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned};
-use syn::{parse_quote, Error, ItemMod, Result, Token};
+use syn::{parse_quote, Error, ItemMod, LitStr, Path, Result, Token};
 
-pub fn bridge(namespace: &Namespace, ffi: ItemMod) -> Result<TokenStream> {
+pub fn bridge(args: Args, ffi: ItemMod) -> Result<TokenStream> {
+    let ref namespace = args.namespace;
     let ident = &ffi.ident;
+    let ref module = args.module.unwrap_or_else(|| ident.to_string());
     let content = ffi.content.ok_or(Error::new(
         Span::call_site(),
         "#[cxx::bridge] module must have inline contents",
@@ -36,13 +42,20 @@ pub fn bridge(namespace: &Namespace, ffi: ItemMod) -> Result<TokenStream> {
     for api in &apis {
         match api {
             Api::Include(_) | Api::RustType(_) => {}
-            Api::Struct(strct) => expanded.extend(expand_struct(strct)),
-            Api::CxxType(ety) => expanded.extend(expand_cxx_type(ety)),
+            Api::Struct(strct) => {
+                expanded.extend(expand_struct(strct));
+                if strct.fixed_width.is_none() && strct.union_token.is_none() {
+                    hidden.extend(expand_struct_layout_asserts(strct, types));
+                }
+            }
+            Api::Enum(enm) => expanded.extend(expand_enum(enm)),
+            Api::ExceptionEnum(enm) => expanded.extend(expand_exception_enum(enm)),
+            Api::CxxType(ety) => expanded.extend(expand_cxx_type(namespace, ety)),
             Api::CxxFunction(efn) => {
-                expanded.extend(expand_cxx_function_shim(namespace, efn, types));
+                expanded.extend(expand_cxx_function_shim(namespace, module, efn, types));
             }
             Api::RustFunction(efn) => {
-                hidden.extend(expand_rust_function_shim(namespace, efn, types))
+                hidden.extend(expand_rust_function_shim(namespace, module, efn, types))
             }
         }
     }
@@ -54,10 +67,46 @@ pub fn bridge(namespace: &Namespace, ffi: ItemMod) -> Result<TokenStream> {
                     hidden.extend(expand_rust_box(namespace, ident));
                 }
             }
+        } else if let Type::SliceBox(ty) = ty {
+            if let Type::Slice(slice) = &ty.inner {
+                if let Type::Ident(ident) = &slice.inner {
+                    if Atom::from(ident).is_none() {
+                        hidden.extend(expand_rust_box_slice(namespace, ident));
+                    }
+                }
+            }
+        } else if let Type::RustArc(ty) = ty {
+            if let Type::Ident(ident) = &ty.inner {
+                hidden.extend(expand_rust_arc(namespace, ident));
+            }
+        } else if let Type::RustVec(ty) = ty {
+            if let Type::Ident(ident) = &ty.inner {
+                if Atom::from(ident).is_none() {
+                    hidden.extend(expand_rust_vec(namespace, ident));
+                }
+            }
         } else if let Type::UniquePtr(ptr) = ty {
             if let Type::Ident(ident) = &ptr.inner {
                 if Atom::from(ident).is_none() {
-                    expanded.extend(expand_unique_ptr(namespace, ident));
+                    expanded.extend(expand_unique_ptr(namespace, ident, types));
+                }
+            }
+        } else if let Type::SharedPtr(ptr) = ty {
+            if let Type::Ident(ident) = &ptr.inner {
+                if Atom::from(ident).is_none() {
+                    expanded.extend(expand_shared_ptr(namespace, ident, types));
+                }
+            }
+        } else if let Type::CxxVector(ptr) = ty {
+            if let Type::Ident(ident) = &ptr.inner {
+                if Atom::from(ident).is_none() {
+                    expanded.extend(expand_cxx_vector(namespace, ident, types));
+                }
+            }
+        } else if let Type::CxxMap(map) = ty {
+            if let (Type::Ident(key), Type::Ident(value)) = (&map.first, &map.second) {
+                if Atom::from(value).is_none() {
+                    expanded.extend(expand_cxx_map(namespace, key, value));
                 }
             }
         }
@@ -73,10 +122,11 @@ pub fn bridge(namespace: &Namespace, ffi: ItemMod) -> Result<TokenStream> {
         });
     }
 
-    let attrs = ffi
-        .attrs
-        .into_iter()
-        .filter(|attr| attr.path.is_ident("doc"));
+    // Lint levels are kept so that lints firing on the generated code can be
+    // silenced from the bridge module itself.
+    let attrs = ffi.attrs.into_iter().filter(|attr| {
+        attr.path.is_ident("doc") || attr.path.is_ident("allow") || attr.path.is_ident("cfg_attr")
+    });
     let vis = &ffi.vis;
 
     Ok(quote! {
@@ -93,6 +143,11 @@ fn expand_struct(strct: &Struct) -> TokenStream {
     let ident = &strct.ident;
     let doc = &strct.doc;
     let derives = &strct.derives;
+    let non_exhaustive = strct.non_exhaustive.iter();
+    let keyword = match &strct.union_token {
+        Some(union_token) => quote!(#union_token),
+        None => quote!(struct),
+    };
     let fields = strct.fields.iter().map(|field| {
         // This span on the pub makes "private type in public interface" errors
         // appear in the right place.
@@ -102,27 +157,240 @@ fn expand_struct(strct: &Struct) -> TokenStream {
     quote! {
         #doc
         #[derive(#(#derives),*)]
+        #(#[#non_exhaustive])*
         #[repr(C)]
-        pub struct #ident {
+        pub #keyword #ident {
             #(#fields,)*
         }
     }
 }
 
-fn expand_cxx_type(ety: &ExternType) -> TokenStream {
+// The generated C++ asserts the same predicted layout on its own definition of
+// the struct.
+fn expand_struct_layout_asserts(strct: &Struct, types: &Types) -> TokenStream {
+    let ident = &strct.ident;
+    let mut asserts = TokenStream::new();
+    for &pointer_width in &[8, 4] {
+        let layout = match layout::struct_layout(strct, types, Some(pointer_width)) {
+            Some(layout) => layout,
+            None => continue,
+        };
+        let target = (pointer_width * 8).to_string();
+        let size = layout.size;
+        let align = layout.align;
+        asserts.extend(quote! {
+            #[cfg(target_pointer_width = #target)]
+            const _: [(); #size] = [(); ::std::mem::size_of::<#ident>()];
+            #[cfg(target_pointer_width = #target)]
+            const _: [(); #align] = [(); ::std::mem::align_of::<#ident>()];
+        });
+    }
+    asserts
+}
+
+fn expand_enum(enm: &Enum) -> TokenStream {
+    let ident = &enm.ident;
+    let doc = &enm.doc;
+    let repr = match &enm.repr {
+        Some(repr) => quote!(#repr),
+        None => quote!(C),
+    };
+    // The integer type that C++ holds the enum as; `int` unless a repr is
+    // given.
+    let int = match &enm.repr {
+        Some(repr) => quote!(#repr),
+        None => quote!(::std::os::raw::c_int),
+    };
+    let variants = enm.variants.iter().map(|variant| {
+        let doc = &variant.doc;
+        let ident = &variant.ident;
+        // Spelled out so that C++ is guaranteed to see the same values.
+        let discriminant = Literal::i128_unsuffixed(variant.discriminant);
+        quote! {
+            #doc
+            #ident = #discriminant
+        }
+    });
+    let arms = enm.variants.iter().map(|variant| {
+        let variant = &variant.ident;
+        quote! {
+            discriminant if discriminant == #ident::#variant as #int => {
+                ::std::result::Result::Ok(#ident::#variant)
+            }
+        }
+    });
+    quote! {
+        #doc
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        #[repr(#repr)]
+        pub enum #ident {
+            #(#variants,)*
+        }
+
+        // Checked rather than transmuted, since an integer from C++ need not
+        // be one of the declared discriminants. The rejected value is handed
+        // back as the error.
+        impl ::std::convert::TryFrom<#int> for #ident {
+            type Error = #int;
+            fn try_from(discriminant: #int) -> ::std::result::Result<Self, #int> {
+                match discriminant {
+                    #(#arms)*
+                    _ => ::std::result::Result::Err(discriminant),
+                }
+            }
+        }
+    }
+}
+
+fn expand_exception_enum(enm: &ExceptionEnum) -> TokenStream {
+    let ident = &enm.ident;
+    let doc = &enm.doc;
+    let variants = enm.variants.iter().map(|variant| {
+        let doc = &variant.doc;
+        let ident = &variant.ident;
+        quote! {
+            #doc
+            #ident(::cxx::Exception)
+        }
+    });
+    let what = enm.variants.iter().map(|variant| {
+        let variant = &variant.ident;
+        quote!(#ident::#variant(e) => e.what())
+    });
+    let mapped = enm
+        .variants
+        .iter()
+        .enumerate()
+        .filter(|(_, variant)| variant.exception.is_some())
+        .map(|(i, variant)| {
+            let variant = &variant.ident;
+            quote!(#i => #ident::#variant(e))
+        });
+    let fallback = &enm.variants[fallback_index(enm)].ident;
+    quote! {
+        #doc
+        #[derive(Debug)]
+        pub enum #ident {
+            #(#variants,)*
+        }
+        impl #ident {
+            pub fn what(&self) -> &str {
+                match self {
+                    #(#what,)*
+                }
+            }
+            #[doc(hidden)]
+            pub fn __from_exception(kind: usize, e: ::cxx::Exception) -> Self {
+                match kind {
+                    #(#mapped,)*
+                    _ => #ident::#fallback(e),
+                }
+            }
+        }
+        impl ::std::fmt::Display for #ident {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                formatter.write_str(self.what())
+            }
+        }
+        impl ::std::error::Error for #ident {}
+    }
+}
+
+// Index of the one variant without #[cxx_exception], which typecheck has
+// already guaranteed exists.
+fn fallback_index(enm: &ExceptionEnum) -> usize {
+    enm.variants
+        .iter()
+        .position(|variant| variant.exception.is_none())
+        .unwrap()
+}
+
+fn expand_cxx_type(namespace: &Namespace, ety: &ExternType) -> TokenStream {
     let ident = &ety.ident;
     let doc = &ety.doc;
-    quote! {
+    let mut expanded = quote! {
         #doc
         #[repr(C)]
         pub struct #ident {
             _private: ::cxx::private::Opaque,
         }
+    };
+    if ety.debug.is_some() {
+        expanded.extend(expand_cxx_type_debug(namespace, ident));
+    }
+    if ety.placement.is_some() {
+        expanded.extend(expand_cxx_type_placement(namespace, ident));
+    }
+    expanded
+}
+
+fn expand_cxx_type_debug(namespace: &Namespace, ident: &Ident) -> TokenStream {
+    let link_name = mangle::cxx_type_debug(namespace, ident);
+    quote! {
+        impl ::std::fmt::Debug for #ident {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                extern "C" {
+                    #[link_name = #link_name]
+                    fn __debug(this: &#ident) -> *mut ::cxx::CxxString;
+                }
+                let repr = unsafe { ::cxx::UniquePtr::from_raw(__debug(self)) };
+                formatter.write_str(&repr.to_string_lossy())
+            }
+        }
+    }
+}
+
+fn expand_cxx_type_placement(namespace: &Namespace, ident: &Ident) -> TokenStream {
+    let prefix = mangle::cxx_type_placement(namespace, ident);
+    let link_size = format!("{}size", prefix);
+    let link_align = format!("{}align", prefix);
+    let link_new = format!("{}new", prefix);
+    let link_drop = format!("{}drop", prefix);
+    quote! {
+        unsafe impl ::cxx::Placement for #ident {
+            fn layout() -> ::std::alloc::Layout {
+                extern "C" {
+                    #[link_name = #link_size]
+                    fn __size() -> usize;
+                    #[link_name = #link_align]
+                    fn __align() -> usize;
+                }
+                unsafe { ::std::alloc::Layout::from_size_align_unchecked(__size(), __align()) }
+            }
+            unsafe fn construct_at(ptr: *mut Self) {
+                extern "C" {
+                    #[link_name = #link_new]
+                    fn __new(ptr: *mut #ident);
+                }
+                __new(ptr);
+            }
+            unsafe fn destroy_at(ptr: *mut Self) {
+                extern "C" {
+                    #[link_name = #link_drop]
+                    fn __drop(ptr: *mut #ident);
+                }
+                __drop(ptr);
+            }
+        }
     }
 }
 
-fn expand_cxx_function_decl(namespace: &Namespace, efn: &ExternFn, types: &Types) -> TokenStream {
+fn expand_cxx_function_decl(
+    namespace: &Namespace,
+    module: &str,
+    efn: &ExternFn,
+    types: &Types,
+) -> TokenStream {
     let ident = &efn.ident;
+    let receiver = efn.receiver.iter().map(|receiver| {
+        let ident = &receiver.ident;
+        let mutability = &receiver.mutability;
+        if receiver.pinned {
+            quote!(_: ::std::pin::Pin<&mut #ident>)
+        } else {
+            quote!(_: &#mutability #ident)
+        }
+    });
     let args = efn.args.iter().map(|arg| {
         let ident = &arg.ident;
         let ty = expand_extern_type(&arg.ty);
@@ -142,52 +410,97 @@ fn expand_cxx_function_decl(namespace: &Namespace, efn: &ExternFn, types: &Types
         expand_extern_return_type(&efn.ret, types)
     };
     let mut outparam = None;
-    if indirect_return(efn, types) {
+    if let Some(Type::Tuple(tuple)) = &efn.ret {
+        let elems = tuple.elems.iter().enumerate().map(|(i, elem)| {
+            let var = format_ident!("__return{}", i);
+            quote!(#var: *mut #elem,)
+        });
+        outparam = Some(quote!(#(#elems)*));
+    } else if indirect_return(efn, types) {
         let ret = expand_extern_type(efn.ret.as_ref().unwrap());
-        outparam = Some(quote!(__return: *mut #ret));
+        outparam = Some(quote!(__return: *mut #ret,));
     }
-    let link_name = format!("{}cxxbridge02${}", namespace, ident);
+    let kind = efn.error_type.as_ref().map(|_| quote!(__kind: *mut usize));
+    let link_name = mangle::extern_fn(namespace, module, efn);
     let local_name = format_ident!("__{}", ident);
     quote! {
         #[link_name = #link_name]
-        fn #local_name(#(#args,)* #outparam) #ret;
+        fn #local_name(#(#receiver,)* #(#args,)* #outparam #kind) #ret;
     }
 }
 
-fn expand_cxx_function_shim(namespace: &Namespace, efn: &ExternFn, types: &Types) -> TokenStream {
+fn expand_cxx_function_shim(
+    namespace: &Namespace,
+    module: &str,
+    efn: &ExternFn,
+    types: &Types,
+) -> TokenStream {
     let ident = &efn.ident;
     let doc = &efn.doc;
-    let decl = expand_cxx_function_decl(namespace, efn, types);
+    let deprecated = &efn.deprecated;
+    let decl = expand_cxx_function_decl(namespace, module, efn, types);
+    let abi = expand_abi(&efn.abi);
+    let receiver = efn.receiver.iter().map(|receiver| {
+        let mutability = &receiver.mutability;
+        if receiver.pinned {
+            quote!(self: ::std::pin::Pin<&mut Self>)
+        } else {
+            quote!(&#mutability self)
+        }
+    });
     let args = &efn.args;
     let ret = if efn.throws {
         let ok = match &efn.ret {
             Some(ret) => quote!(#ret),
             None => quote!(()),
         };
-        quote!(-> ::std::result::Result<#ok, ::cxx::Exception>)
+        let err = match &efn.error_type {
+            Some(error_type) => quote!(#error_type),
+            None => quote!(::cxx::Exception),
+        };
+        quote!(-> ::std::result::Result<#ok, #err>)
     } else {
         expand_return_type(&efn.ret)
     };
     let indirect_return = indirect_return(efn, types);
-    let vars = efn.args.iter().map(|arg| {
+    let receiver_var = efn.receiver.iter().map(|_| quote!(self));
+    let vars = receiver_var.chain(efn.args.iter().map(|arg| {
         let var = &arg.ident;
         match &arg.ty {
             Type::Ident(ident) if ident == RustString => {
                 quote!(#var.as_mut_ptr() as *const ::cxx::private::RustString)
             }
+            Type::Ident(ident) if ident == Char => quote!(#var as u32),
             Type::RustBox(_) => quote!(::std::boxed::Box::into_raw(#var)),
+            Type::RustArc(_) => quote!(::std::sync::Arc::into_raw(#var)),
             Type::UniquePtr(_) => quote!(::cxx::UniquePtr::into_raw(#var)),
+            Type::UniqueArray(_) => quote!(::cxx::UniqueArray::into_raw(#var)),
+            Type::RustVec(_) => quote!(#var.as_mut_ptr() as *mut ::cxx::private::RustVec<_>),
+            Type::Optional(ty) => match &ty.inner {
+                Type::Ident(_) => {
+                    quote!(#var.as_ref().map_or(::std::ptr::null(), |v| v as *const _))
+                }
+                _ => quote!(#var),
+            },
             Type::Ref(ty) => match &ty.inner {
                 Type::Ident(ident) if ident == RustString => {
                     quote!(::cxx::private::RustString::from_ref(#var))
                 }
+                Type::RustVec(_) => match ty.mutability {
+                    None => quote!(::cxx::private::RustVec::from_ref(#var)),
+                    Some(_) => quote!(::cxx::private::RustVec::from_mut(#var)),
+                },
                 _ => quote!(#var),
             },
             Type::Str(_) => quote!(::cxx::private::RustStr::from(#var)),
+            Type::SliceRef(ty) => match ty.mutability {
+                None => quote!(::cxx::private::RustSlice::from_ref(#var)),
+                Some(_) => quote!(::cxx::private::RustSlice::from_mut(#var)),
+            },
             ty if types.needs_indirect_abi(ty) => quote!(#var.as_mut_ptr()),
             _ => quote!(#var),
         }
-    });
+    }));
     let trampolines = efn
         .args
         .iter()
@@ -195,7 +508,7 @@ fn expand_cxx_function_shim(namespace: &Namespace, efn: &ExternFn, types: &Types
             if let Type::Fn(f) = &arg.ty {
                 let var = &arg.ident;
                 Some(expand_function_pointer_trampoline(
-                    namespace, efn, var, f, types,
+                    namespace, module, efn, var, f, types,
                 ))
             } else {
                 None
@@ -215,15 +528,49 @@ fn expand_cxx_function_shim(namespace: &Namespace, efn: &ExternFn, types: &Types
             }
         })
         .collect::<TokenStream>();
+    let mut kind = None;
+    let mut exception = quote!(.exception());
+    if let Some(error_type) = &efn.error_type {
+        let enm = types.exceptions[error_type];
+        let fallback = fallback_index(enm);
+        setup.extend(quote! {
+            let mut __kind: usize = #fallback;
+        });
+        kind = Some(quote!(&mut __kind));
+        exception = quote!(.exception().map_err(|e| #error_type::__from_exception(__kind, e)));
+    }
     let local_name = format_ident!("__{}", ident);
-    let call = if indirect_return {
+    let call = if let Some(Type::Tuple(tuple)) = &efn.ret {
+        // C++ writes each element through its own out-parameter, since the
+        // layout of a Rust tuple is unspecified.
+        let mut outvars = Vec::new();
+        for (i, elem) in tuple.elems.iter().enumerate() {
+            let var = format_ident!("__return{}", i);
+            setup.extend(quote! {
+                let mut #var = ::std::mem::MaybeUninit::<#elem>::uninit();
+            });
+            outvars.push(var);
+        }
+        let unpacked = quote!((#(#outvars.assume_init(),)*));
+        if efn.throws {
+            setup.extend(quote! {
+                #local_name(#(#vars,)* #(#outvars.as_mut_ptr(),)* #kind) #exception?;
+            });
+            quote!(::std::result::Result::Ok(#unpacked))
+        } else {
+            setup.extend(quote! {
+                #local_name(#(#vars,)* #(#outvars.as_mut_ptr()),*);
+            });
+            unpacked
+        }
+    } else if indirect_return {
         let ret = expand_extern_type(efn.ret.as_ref().unwrap());
         setup.extend(quote! {
             let mut __return = ::std::mem::MaybeUninit::<#ret>::uninit();
         });
         if efn.throws {
             setup.extend(quote! {
-                #local_name(#(#vars,)* __return.as_mut_ptr()).exception()?;
+                #local_name(#(#vars,)* __return.as_mut_ptr(), #kind) #exception?;
             });
             quote!(::std::result::Result::Ok(__return.assume_init()))
         } else {
@@ -234,7 +581,7 @@ fn expand_cxx_function_shim(namespace: &Namespace, efn: &ExternFn, types: &Types
         }
     } else if efn.throws {
         quote! {
-            #local_name(#(#vars),*).exception()
+            #local_name(#(#vars,)* #kind) #exception
         }
     } else {
         quote! {
@@ -246,35 +593,87 @@ fn expand_cxx_function_shim(namespace: &Namespace, efn: &ExternFn, types: &Types
             Type::Ident(ident) if ident == RustString => {
                 Some(quote!(#call.map(|r| r.into_string())))
             }
+            Type::Ident(ident) if ident == Char => Some(match &efn.error_type {
+                Some(error_type) => {
+                    let fallback = fallback_index(types.exceptions[error_type]);
+                    quote! {
+                        #call.and_then(|r| {
+                            ::cxx::private::char_from_u32(r)
+                                .map_err(|e| #error_type::__from_exception(#fallback, e))
+                        })
+                    }
+                }
+                None => quote!(#call.and_then(::cxx::private::char_from_u32)),
+            }),
             Type::RustBox(_) => Some(quote!(#call.map(|r| ::std::boxed::Box::from_raw(r)))),
+            Type::RustArc(_) => Some(quote!(#call.map(|r| ::std::sync::Arc::from_raw(r)))),
             Type::UniquePtr(_) => Some(quote!(#call.map(|r| ::cxx::UniquePtr::from_raw(r)))),
+            Type::UniqueArray(_) => Some(quote!(#call.map(|r| ::cxx::UniqueArray::from_raw(r)))),
+            Type::RustVec(_) => Some(quote!(#call.map(|r| r.into_vec()))),
+            Type::Optional(ty) => match &ty.inner {
+                Type::UniquePtr(_) => Some(quote! {
+                    #call.map(|r| {
+                        let r = ::cxx::UniquePtr::from_raw(r);
+                        if r.is_null() { None } else { Some(r) }
+                    })
+                }),
+                _ => None,
+            },
             Type::Ref(ty) => match &ty.inner {
                 Type::Ident(ident) if ident == RustString => {
                     Some(quote!(#call.map(|r| r.as_string())))
                 }
+                Type::RustVec(_) => match ty.mutability {
+                    None => Some(quote!(#call.map(|r| r.as_vec()))),
+                    Some(_) => Some(quote!(#call.map(|r| r.as_mut_vec()))),
+                },
                 _ => None,
             },
             Type::Str(_) => Some(quote!(#call.map(|r| r.as_str()))),
+            Type::SliceRef(ty) => match ty.mutability {
+                None => Some(quote!(#call.map(|r| r.as_slice()))),
+                Some(_) => Some(quote!(#call.map(|r| r.as_mut_slice()))),
+            },
             _ => None,
         })
     } else {
         efn.ret.as_ref().and_then(|ret| match ret {
             Type::Ident(ident) if ident == RustString => Some(quote!(#call.into_string())),
+            Type::Ident(ident) if ident == Char => Some(quote!(::cxx::private::expect_char(#call))),
             Type::RustBox(_) => Some(quote!(::std::boxed::Box::from_raw(#call))),
+            Type::RustArc(_) => Some(quote!(::std::sync::Arc::from_raw(#call))),
             Type::UniquePtr(_) => Some(quote!(::cxx::UniquePtr::from_raw(#call))),
+            Type::UniqueArray(_) => Some(quote!(::cxx::UniqueArray::from_raw(#call))),
+            Type::RustVec(_) => Some(quote!(#call.into_vec())),
+            Type::Optional(ty) => match &ty.inner {
+                Type::UniquePtr(_) => Some(quote! {{
+                    let r = ::cxx::UniquePtr::from_raw(#call);
+                    if r.is_null() { None } else { Some(r) }
+                }}),
+                _ => None,
+            },
             Type::Ref(ty) => match &ty.inner {
                 Type::Ident(ident) if ident == RustString => Some(quote!(#call.as_string())),
+                Type::RustVec(_) => match ty.mutability {
+                    None => Some(quote!(#call.as_vec())),
+                    Some(_) => Some(quote!(#call.as_mut_vec())),
+                },
                 _ => None,
             },
             Type::Str(_) => Some(quote!(#call.as_str())),
+            Type::SliceRef(ty) => match ty.mutability {
+                None => Some(quote!(#call.as_slice())),
+                Some(_) => Some(quote!(#call.as_mut_slice())),
+            },
             _ => None,
         })
     }
     .unwrap_or(call);
-    quote! {
+    let function_shim = quote! {
         #doc
-        pub fn #ident(#(#args),*) #ret {
-            extern "C" {
+        #deprecated
+        pub fn #ident(#(#receiver,)* #(#args),*) #ret {
+            extern #abi {
                 #decl
             }
             #trampolines
@@ -283,18 +682,27 @@ fn expand_cxx_function_shim(namespace: &Namespace, efn: &ExternFn, types: &Types
                 #expr
             }
         }
+    };
+    match &efn.receiver {
+        None => function_shim,
+        Some(receiver) => {
+            let receiver_type = &receiver.ident;
+            quote!(impl #receiver_type { #function_shim })
+        }
     }
 }
 
 fn expand_function_pointer_trampoline(
     namespace: &Namespace,
+    module: &str,
     efn: &ExternFn,
     var: &Ident,
     sig: &Signature,
     types: &Types,
 ) -> TokenStream {
-    let c_trampoline = format!("{}cxxbridge02${}${}$0", namespace, efn.ident, var);
-    let r_trampoline = format!("{}cxxbridge02${}${}$1", namespace, efn.ident, var);
+    let link_name = mangle::extern_fn(namespace, module, efn);
+    let c_trampoline = format!("{}${}$0", link_name, var);
+    let r_trampoline = format!("{}${}$1", link_name, var);
     let local_name = parse_quote!(__);
     let catch_unwind_label = format!("::{}::{}", efn.ident, var);
     let shim = expand_rust_function_shim_impl(
@@ -304,6 +712,10 @@ fn expand_function_pointer_trampoline(
         local_name,
         catch_unwind_label,
         None,
+        None,
+        None,
+        quote!("C"),
+        false,
     );
 
     quote! {
@@ -328,20 +740,42 @@ fn expand_rust_type(ety: &ExternType) -> TokenStream {
     }
 }
 
-fn expand_rust_function_shim(namespace: &Namespace, efn: &ExternFn, types: &Types) -> TokenStream {
+fn expand_rust_function_shim(
+    namespace: &Namespace,
+    module: &str,
+    efn: &ExternFn,
+    types: &Types,
+) -> TokenStream {
     let ident = &efn.ident;
-    let link_name = format!("{}cxxbridge02${}", namespace, ident);
-    let local_name = format_ident!("__{}", ident);
-    let catch_unwind_label = format!("::{}", ident);
+    let link_name = mangle::extern_fn(namespace, module, efn);
+    let (local_name, catch_unwind_label) = match &efn.receiver {
+        Some(receiver) => (
+            format_ident!("__{}__{}", receiver.ident, ident),
+            format!("::{}::{}", receiver.ident, ident),
+        ),
+        None => (format_ident!("__{}", ident), format!("::{}", ident)),
+    };
     let invoke = Some(ident);
-    expand_rust_function_shim_impl(
+    let shim = expand_rust_function_shim_impl(
         efn,
         types,
         &link_name,
         local_name,
         catch_unwind_label,
         invoke,
-    )
+        efn.error_convert.as_ref(),
+        efn.error_type.as_ref(),
+        expand_abi(&efn.abi),
+        efn.non_reentrant.is_some(),
+    );
+    let link_section = efn
+        .link_section
+        .iter()
+        .map(|section| quote!(#[link_section = #section]));
+    quote! {
+        #(#link_section)*
+        #shim
+    }
 }
 
 fn expand_rust_function_shim_impl(
@@ -351,7 +785,16 @@ fn expand_rust_function_shim_impl(
     local_name: Ident,
     catch_unwind_label: String,
     invoke: Option<&Ident>,
+    error_convert: Option<&Path>,
+    error_type: Option<&Ident>,
+    abi: TokenStream,
+    non_reentrant: bool,
 ) -> TokenStream {
+    let receiver = sig.receiver.iter().map(|receiver| {
+        let ident = &receiver.ident;
+        let mutability = &receiver.mutability;
+        quote!(__self: &#mutability #ident)
+    });
     let args = sig.args.iter().map(|arg| {
         let ident = &arg.ident;
         let ty = expand_extern_type(&arg.ty);
@@ -362,6 +805,43 @@ fn expand_rust_function_shim_impl(
         }
     });
 
+    let mut outparam = None;
+    let indirect_return = indirect_return(sig, types);
+    if indirect_return {
+        let ret = expand_extern_type(sig.ret.as_ref().unwrap());
+        outparam = Some(quote!(__return: *mut #ret,));
+    }
+    let errparam = error_type.map(|error_type| quote!(__error: *mut #error_type,));
+    let out = match sig.ret {
+        Some(_) => quote!(__return),
+        None => quote!(&mut ()),
+    };
+
+    // An invalid char32_t from C++ is reported back as an error when the
+    // function is able to return one as a string, and panics otherwise.
+    let char_args = sig
+        .args
+        .iter()
+        .filter(|arg| arg.ty == Char)
+        .map(|arg| {
+            let ident = &arg.ident;
+            if sig.throws && error_type.is_none() {
+                quote! {
+                    let #ident = match ::cxx::private::char_from_u32(#ident) {
+                        ::std::result::Result::Ok(ch) => ch,
+                        ::std::result::Result::Err(err) => {
+                            return ::cxx::private::r#try(#out, ::std::result::Result::Err(err));
+                        }
+                    };
+                }
+            } else {
+                quote! {
+                    let #ident = ::cxx::private::expect_char(#ident);
+                }
+            }
+        })
+        .collect::<TokenStream>();
+
     let vars = sig.args.iter().map(|arg| {
         let ident = &arg.ident;
         match &arg.ty {
@@ -369,22 +849,39 @@ fn expand_rust_function_shim_impl(
                 quote!(::std::mem::take((*#ident).as_mut_string()))
             }
             Type::RustBox(_) => quote!(::std::boxed::Box::from_raw(#ident)),
+            Type::RustArc(_) => quote!(::std::sync::Arc::from_raw(#ident)),
             Type::UniquePtr(_) => quote!(::cxx::UniquePtr::from_raw(#ident)),
+            Type::UniqueArray(_) => quote!(::cxx::UniqueArray::from_raw(#ident)),
+            Type::RustVec(_) => quote!(::std::ptr::read(#ident).into_vec()),
             Type::Ref(ty) => match &ty.inner {
                 Type::Ident(i) if i == RustString => quote!(#ident.as_string()),
+                Type::RustVec(_) => match ty.mutability {
+                    None => quote!(#ident.as_vec()),
+                    Some(_) => quote!(#ident.as_mut_vec()),
+                },
                 _ => quote!(#ident),
             },
             Type::Str(_) => quote!(#ident.as_str()),
+            Type::SliceRef(ty) => match ty.mutability {
+                None => quote!(#ident.as_slice()),
+                Some(_) => quote!(#ident.as_mut_slice()),
+            },
             ty if types.needs_indirect_abi(ty) => quote!(::std::ptr::read(#ident)),
             _ => quote!(#ident),
         }
     });
 
     let mut call = match invoke {
-        Some(ident) => quote!(super::#ident),
+        Some(ident) => match &sig.receiver {
+            Some(_) => quote!(__self.#ident),
+            None => quote!(super::#ident),
+        },
         None => quote!(__extern),
     };
     call.extend(quote! { (#(#vars),*) });
+    if let Some(error_convert) = error_convert {
+        call = quote!(#call.map_err(#error_convert));
+    }
 
     let mut expr = sig
         .ret
@@ -393,38 +890,89 @@ fn expand_rust_function_shim_impl(
             Type::Ident(ident) if ident == RustString => {
                 Some(quote!(::cxx::private::RustString::from(#call)))
             }
+            Type::Ident(ident) if ident == Char => {
+                if sig.throws {
+                    Some(quote!(#call.map(|r| r as u32)))
+                } else {
+                    Some(quote!(#call as u32))
+                }
+            }
             Type::RustBox(_) => Some(quote!(::std::boxed::Box::into_raw(#call))),
+            Type::RustArc(_) => Some(quote!(::std::sync::Arc::into_raw(#call))),
             Type::UniquePtr(_) => Some(quote!(::cxx::UniquePtr::into_raw(#call))),
+            Type::UniqueArray(_) => Some(quote!(::cxx::UniqueArray::into_raw(#call))),
+            Type::SliceBox(_) => {
+                if sig.throws {
+                    Some(quote!(#call.map(::cxx::private::RustSlice::from_box)))
+                } else {
+                    Some(quote!(::cxx::private::RustSlice::from_box(#call)))
+                }
+            }
+            Type::RustVec(_) => {
+                if sig.throws {
+                    Some(quote!(#call.map(::cxx::private::RustVec::from)))
+                } else {
+                    Some(quote!(::cxx::private::RustVec::from(#call)))
+                }
+            }
+            Type::Optional(ty) => match &ty.inner {
+                Type::UniquePtr(_) => Some(quote! {
+                    match #call {
+                        Some(r) => ::cxx::UniquePtr::into_raw(r),
+                        None => ::std::ptr::null_mut(),
+                    }
+                }),
+                _ => None,
+            },
             Type::Ref(ty) => match &ty.inner {
                 Type::Ident(ident) if ident == RustString => {
                     Some(quote!(::cxx::private::RustString::from_ref(#call)))
                 }
+                Type::RustVec(_) => match ty.mutability {
+                    None => Some(quote!(::cxx::private::RustVec::from_ref(#call))),
+                    Some(_) => Some(quote!(::cxx::private::RustVec::from_mut(#call))),
+                },
                 _ => None,
             },
             Type::Str(_) => Some(quote!(::cxx::private::RustStr::from(#call))),
+            Type::SliceRef(ty) => match ty.mutability {
+                None => Some(quote!(::cxx::private::RustSlice::from_ref(#call))),
+                Some(_) => Some(quote!(::cxx::private::RustSlice::from_mut(#call))),
+            },
             _ => None,
         })
         .unwrap_or(call);
 
-    let mut outparam = None;
-    let indirect_return = indirect_return(sig, types);
-    if indirect_return {
-        let ret = expand_extern_type(sig.ret.as_ref().unwrap());
-        outparam = Some(quote!(__return: *mut #ret,));
-    }
-    if sig.throws {
-        let out = match sig.ret {
-            Some(_) => quote!(__return),
-            None => quote!(&mut ()),
-        };
+    if error_type.is_some() {
+        expr = quote!(::cxx::private::try_typed(#out, __error, #expr));
+    } else if sig.throws {
         expr = quote!(::cxx::private::r#try(#out, #expr));
     } else if indirect_return {
         expr = quote!(::std::ptr::write(__return, #expr));
     }
 
+    if !char_args.is_empty() {
+        expr = quote!({ #char_args #expr });
+    }
+    if non_reentrant {
+        // Only checked in debug builds, where the cost of a thread-local
+        // access per call is acceptable.
+        expr = quote!({
+            #[cfg(debug_assertions)]
+            let __guard = {
+                ::std::thread_local! {
+                    static __ENTERED: ::std::cell::Cell<bool> = ::std::cell::Cell::new(false);
+                }
+                ::cxx::private::ReentrancyGuard::enter(&__ENTERED, __fn)
+            };
+            #expr
+        });
+    }
     expr = quote!(::cxx::private::catch_unwind(__fn, move || #expr));
 
-    let ret = if sig.throws {
+    let ret = if error_type.is_some() {
+        quote!(-> bool)
+    } else if sig.throws {
         quote!(-> ::cxx::private::Result)
     } else {
         expand_extern_return_type(&sig.ret, types)
@@ -435,10 +983,17 @@ fn expand_rust_function_shim_impl(
         Some(_) => None,
     };
 
+    // The Rust fn pointer is only ever handed back to Rust by the trampoline,
+    // never called through by C++.
+    let allow_fn_pointer = pointer
+        .as_ref()
+        .map(|_| quote!(#[allow(improper_ctypes_definitions)]));
+
     quote! {
         #[doc(hidden)]
+        #allow_fn_pointer
         #[export_name = #link_name]
-        unsafe extern "C" fn #local_name(#(#args,)* #outparam #pointer) #ret {
+        unsafe extern #abi fn #local_name(#(#receiver,)* #(#args,)* #outparam #errparam #pointer) #ret {
             let __fn = concat!(module_path!(), #catch_unwind_label);
             #expr
         }
@@ -474,7 +1029,112 @@ fn expand_rust_box(namespace: &Namespace, ident: &Ident) -> TokenStream {
     }
 }
 
-fn expand_unique_ptr(namespace: &Namespace, ident: &Ident) -> TokenStream {
+// Frees a Box<[T]> handed to C++, which only the Rust side knows the layout of.
+// The runtime exports the same for slices of primitives.
+fn expand_rust_box_slice(namespace: &Namespace, ident: &Ident) -> TokenStream {
+    let link_drop = format!("cxxbridge02$box_slice${}{}$drop", namespace, ident);
+    let local_drop = format_ident!("{}__box_slice_drop", ident);
+
+    let span = ident.span();
+    quote_spanned! {span=>
+        #[doc(hidden)]
+        #[export_name = #link_drop]
+        unsafe extern "C" fn #local_drop(repr: ::cxx::private::RustSlice) {
+            ::std::mem::drop(repr.into_box::<#ident>());
+        }
+    }
+}
+
+// The same operations that the runtime exports for Vec<u8>, for a Vec of a
+// shared struct declared in this bridge.
+fn expand_rust_vec(namespace: &Namespace, ident: &Ident) -> TokenStream {
+    let link_prefix = format!("cxxbridge02$rust_vec${}{}$", namespace, ident);
+    let link_new = format!("{}new", link_prefix);
+    let link_drop = format!("{}drop", link_prefix);
+    let link_len = format!("{}len", link_prefix);
+    let link_capacity = format!("{}capacity", link_prefix);
+    let link_data = format!("{}data", link_prefix);
+    let link_reserve_total = format!("{}reserve_total", link_prefix);
+    let link_set_len = format!("{}set_len", link_prefix);
+
+    let local_prefix = format_ident!("{}__vec_", ident);
+    let local_new = format_ident!("{}new", local_prefix);
+    let local_drop = format_ident!("{}drop", local_prefix);
+    let local_len = format_ident!("{}len", local_prefix);
+    let local_capacity = format_ident!("{}capacity", local_prefix);
+    let local_data = format_ident!("{}data", local_prefix);
+    let local_reserve_total = format_ident!("{}reserve_total", local_prefix);
+    let local_set_len = format_ident!("{}set_len", local_prefix);
+
+    let span = ident.span();
+    quote_spanned! {span=>
+        #[doc(hidden)]
+        #[export_name = #link_new]
+        unsafe extern "C" fn #local_new(this: *mut ::std::vec::Vec<#ident>) {
+            ::std::ptr::write(this, ::std::vec::Vec::new());
+        }
+        #[doc(hidden)]
+        #[export_name = #link_drop]
+        unsafe extern "C" fn #local_drop(this: *mut ::std::vec::Vec<#ident>) {
+            ::std::ptr::drop_in_place(this);
+        }
+        #[doc(hidden)]
+        #[export_name = #link_len]
+        unsafe extern "C" fn #local_len(this: &::std::vec::Vec<#ident>) -> usize {
+            this.len()
+        }
+        #[doc(hidden)]
+        #[export_name = #link_capacity]
+        unsafe extern "C" fn #local_capacity(this: &::std::vec::Vec<#ident>) -> usize {
+            this.capacity()
+        }
+        #[doc(hidden)]
+        #[export_name = #link_data]
+        unsafe extern "C" fn #local_data(this: &::std::vec::Vec<#ident>) -> *const #ident {
+            this.as_ptr()
+        }
+        #[doc(hidden)]
+        #[export_name = #link_reserve_total]
+        unsafe extern "C" fn #local_reserve_total(this: &mut ::std::vec::Vec<#ident>, cap: usize) {
+            if cap > this.capacity() {
+                this.reserve(cap - this.len());
+            }
+        }
+        #[doc(hidden)]
+        #[export_name = #link_set_len]
+        unsafe extern "C" fn #local_set_len(this: &mut ::std::vec::Vec<#ident>, len: usize) {
+            this.set_len(len);
+        }
+    }
+}
+
+fn expand_rust_arc(namespace: &Namespace, ident: &Ident) -> TokenStream {
+    let link_prefix = format!("cxxbridge02$arc${}{}$", namespace, ident);
+    let link_clone = format!("{}clone", link_prefix);
+    let link_drop = format!("{}drop", link_prefix);
+
+    let local_prefix = format_ident!("{}__arc_", ident);
+    let local_clone = format_ident!("{}clone", local_prefix);
+    let local_drop = format_ident!("{}drop", local_prefix);
+
+    let span = ident.span();
+    quote_spanned! {span=>
+        #[doc(hidden)]
+        #[export_name = #link_clone]
+        unsafe extern "C" fn #local_clone(this: *const #ident) {
+            // Borrows the reference held by C++ just long enough to add one.
+            let arc = ::std::mem::ManuallyDrop::new(::std::sync::Arc::from_raw(this));
+            ::std::mem::forget(::std::sync::Arc::clone(&arc));
+        }
+        #[doc(hidden)]
+        #[export_name = #link_drop]
+        unsafe extern "C" fn #local_drop(this: *const #ident) {
+            ::std::mem::drop(::std::sync::Arc::from_raw(this));
+        }
+    }
+}
+
+fn expand_unique_ptr(namespace: &Namespace, ident: &Ident, types: &Types) -> TokenStream {
     let prefix = format!("cxxbridge02$unique_ptr${}{}$", namespace, ident);
     let link_null = format!("{}null", prefix);
     let link_new = format!("{}new", prefix);
@@ -483,6 +1143,29 @@ fn expand_unique_ptr(namespace: &Namespace, ident: &Ident) -> TokenStream {
     let link_release = format!("{}release", prefix);
     let link_drop = format!("{}drop", prefix);
 
+    // Only a shared struct can be held by value on the Rust side, so only
+    // those get a $new; C++ is not asked to move-construct anything else.
+    let new = if types.structs.contains_key(ident) {
+        quote! {
+            fn __new(mut value: Self) -> *mut ::std::ffi::c_void {
+                extern "C" {
+                    #[link_name = #link_new]
+                    fn __new(this: *mut *mut ::std::ffi::c_void, value: *mut #ident);
+                }
+                let mut repr = ::std::ptr::null_mut::<::std::ffi::c_void>();
+                unsafe { __new(&mut repr, &mut value) }
+                repr
+            }
+        }
+    } else {
+        quote! {
+            fn __new(value: Self) -> *mut ::std::ffi::c_void {
+                let _ = value;
+                unreachable!()
+            }
+        }
+    };
+
     quote! {
         unsafe impl ::cxx::private::UniquePtrTarget for #ident {
             fn __null() -> *mut ::std::ffi::c_void {
@@ -494,15 +1177,8 @@ fn expand_unique_ptr(namespace: &Namespace, ident: &Ident) -> TokenStream {
                 unsafe { __null(&mut repr) }
                 repr
             }
-            fn __new(mut value: Self) -> *mut ::std::ffi::c_void {
-                extern "C" {
-                    #[link_name = #link_new]
-                    fn __new(this: *mut *mut ::std::ffi::c_void, value: *mut #ident);
-                }
-                let mut repr = ::std::ptr::null_mut::<::std::ffi::c_void>();
-                unsafe { __new(&mut repr, &mut value) }
-                repr
-            }
+            #new
+
             unsafe fn __raw(raw: *mut Self) -> *mut ::std::ffi::c_void {
                 extern "C" {
                     #[link_name = #link_raw]
@@ -537,6 +1213,223 @@ fn expand_unique_ptr(namespace: &Namespace, ident: &Ident) -> TokenStream {
     }
 }
 
+fn expand_shared_ptr(namespace: &Namespace, ident: &Ident, types: &Types) -> TokenStream {
+    let prefix = format!("cxxbridge02$shared_ptr${}{}$", namespace, ident);
+    let link_null = format!("{}null", prefix);
+    let link_new = format!("{}new", prefix);
+    let link_clone = format!("{}clone", prefix);
+    let link_get = format!("{}get", prefix);
+    let link_drop = format!("{}drop", prefix);
+
+    let new = if types.structs.contains_key(ident) {
+        quote! {
+            unsafe fn __new(value: Self, new: *mut ::std::ffi::c_void) {
+                extern "C" {
+                    #[link_name = #link_new]
+                    fn __new(value: *mut #ident, new: *mut ::std::ffi::c_void);
+                }
+                // C++ moves out of the value, so it must not be dropped here.
+                let mut value = ::std::mem::MaybeUninit::new(value);
+                __new(value.as_mut_ptr(), new);
+            }
+        }
+    } else {
+        quote! {
+            unsafe fn __new(value: Self, new: *mut ::std::ffi::c_void) {
+                let _ = (value, new);
+                unreachable!()
+            }
+        }
+    };
+
+    quote! {
+        unsafe impl ::cxx::private::SharedPtrTarget for #ident {
+            unsafe fn __null(new: *mut ::std::ffi::c_void) {
+                extern "C" {
+                    #[link_name = #link_null]
+                    fn __null(new: *mut ::std::ffi::c_void);
+                }
+                __null(new);
+            }
+            #new
+
+            unsafe fn __clone(this: *const ::std::ffi::c_void, new: *mut ::std::ffi::c_void) {
+                extern "C" {
+                    #[link_name = #link_clone]
+                    fn __clone(this: *const ::std::ffi::c_void, new: *mut ::std::ffi::c_void);
+                }
+                __clone(this, new);
+            }
+            unsafe fn __get(this: *const ::std::ffi::c_void) -> *const Self {
+                extern "C" {
+                    #[link_name = #link_get]
+                    fn __get(this: *const ::std::ffi::c_void) -> *const #ident;
+                }
+                __get(this)
+            }
+            unsafe fn __drop(this: *mut ::std::ffi::c_void) {
+                extern "C" {
+                    #[link_name = #link_drop]
+                    fn __drop(this: *mut ::std::ffi::c_void);
+                }
+                __drop(this);
+            }
+        }
+    }
+}
+
+fn expand_cxx_map(namespace: &Namespace, key: &Ident, value: &Ident) -> TokenStream {
+    let prefix = mangle::std_map(namespace, key, value);
+    let link_size = format!("{}size", prefix);
+    let link_get = format!("{}get", prefix);
+    let link_iterate = format!("{}iterate", prefix);
+    let key = Type::Ident(key.clone());
+
+    quote! {
+        unsafe impl ::cxx::private::MapValue<#key> for #value {
+            fn __map_size(m: &::cxx::CxxMap<#key, Self>) -> usize {
+                extern "C" {
+                    #[link_name = #link_size]
+                    fn __map_size(_: &::cxx::CxxMap<#key, #value>) -> usize;
+                }
+                unsafe { __map_size(m) }
+            }
+            fn __map_get(m: &::cxx::CxxMap<#key, Self>, key: &#key) -> *const Self {
+                extern "C" {
+                    #[link_name = #link_get]
+                    fn __map_get(_: &::cxx::CxxMap<#key, #value>, key: &#key) -> *const #value;
+                }
+                unsafe { __map_get(m, key) }
+            }
+            unsafe fn __map_iterate(
+                m: &::cxx::CxxMap<#key, Self>,
+                visit: unsafe extern "C" fn(*mut ::std::ffi::c_void, *const #key, *const Self),
+                ctx: *mut ::std::ffi::c_void,
+            ) {
+                extern "C" {
+                    #[link_name = #link_iterate]
+                    fn __map_iterate(
+                        _: &::cxx::CxxMap<#key, #value>,
+                        visit: unsafe extern "C" fn(*mut ::std::ffi::c_void, *const #key, *const #value),
+                        ctx: *mut ::std::ffi::c_void,
+                    );
+                }
+                __map_iterate(m, visit, ctx)
+            }
+        }
+    }
+}
+
+fn expand_cxx_vector(namespace: &Namespace, ident: &Ident, types: &Types) -> TokenStream {
+    let prefix = format!("cxxbridge02$std$vector${}{}$", namespace, ident);
+    let link_size = format!("{}size", prefix);
+    let link_get_unchecked = format!("{}get_unchecked", prefix);
+    let link_pop_back = format!("{}pop_back", prefix);
+    let link_clear = format!("{}clear", prefix);
+    let link_reserve = format!("{}reserve", prefix);
+    let unique_ptr_prefix = format!("cxxbridge02$unique_ptr$std$vector${}{}$", namespace, ident);
+    let link_unique_ptr_null = format!("{}null", unique_ptr_prefix);
+    let link_unique_ptr_raw = format!("{}raw", unique_ptr_prefix);
+    let link_unique_ptr_get = format!("{}get", unique_ptr_prefix);
+    let link_unique_ptr_release = format!("{}release", unique_ptr_prefix);
+    let link_unique_ptr_drop = format!("{}drop", unique_ptr_prefix);
+    // Only a shared struct has the same layout on both sides; an opaque C++
+    // type is zero-sized in Rust and cannot be viewed as a slice.
+    let slice_element = if types.structs.contains_key(ident) {
+        Some(quote!(unsafe impl ::cxx::private::VectorSliceElement for #ident {}))
+    } else {
+        None
+    };
+
+    quote! {
+        unsafe impl ::cxx::private::VectorElement for #ident {
+            fn __vector_size(v: &::cxx::Vector<Self>) -> usize {
+                extern "C" {
+                    #[link_name = #link_size]
+                    fn __vector_size(_: &::cxx::Vector<#ident>) -> usize;
+                }
+                unsafe { __vector_size(v) }
+            }
+            unsafe fn __get_unchecked(v: &::cxx::Vector<Self>, pos: usize) -> *const Self {
+                extern "C" {
+                    #[link_name = #link_get_unchecked]
+                    fn __get_unchecked(_: &::cxx::Vector<#ident>, _: usize) -> *const #ident;
+                }
+                __get_unchecked(v, pos)
+            }
+            unsafe fn __pop_back(v: &mut ::cxx::Vector<Self>) {
+                extern "C" {
+                    #[link_name = #link_pop_back]
+                    fn __pop_back(_: &mut ::cxx::Vector<#ident>);
+                }
+                __pop_back(v)
+            }
+            fn __clear(v: &mut ::cxx::Vector<Self>) {
+                extern "C" {
+                    #[link_name = #link_clear]
+                    fn __clear(_: &mut ::cxx::Vector<#ident>);
+                }
+                unsafe { __clear(v) }
+            }
+            fn __reserve(v: &mut ::cxx::Vector<Self>, new_cap: usize) {
+                extern "C" {
+                    #[link_name = #link_reserve]
+                    fn __reserve(_: &mut ::cxx::Vector<#ident>, new_cap: usize);
+                }
+                unsafe { __reserve(v, new_cap) }
+            }
+            fn __unique_ptr_null() -> *mut ::std::ffi::c_void {
+                extern "C" {
+                    #[link_name = #link_unique_ptr_null]
+                    fn __unique_ptr_null(this: *mut *mut ::std::ffi::c_void);
+                }
+                let mut repr = ::std::ptr::null_mut::<::std::ffi::c_void>();
+                unsafe { __unique_ptr_null(&mut repr) }
+                repr
+            }
+            unsafe fn __unique_ptr_raw(raw: *mut ::cxx::Vector<Self>) -> *mut ::std::ffi::c_void {
+                extern "C" {
+                    #[link_name = #link_unique_ptr_raw]
+                    fn __unique_ptr_raw(this: *mut *mut ::std::ffi::c_void, raw: *mut ::cxx::Vector<#ident>);
+                }
+                let mut repr = ::std::ptr::null_mut::<::std::ffi::c_void>();
+                __unique_ptr_raw(&mut repr, raw);
+                repr
+            }
+            unsafe fn __unique_ptr_get(repr: *mut ::std::ffi::c_void) -> *const ::cxx::Vector<Self> {
+                extern "C" {
+                    #[link_name = #link_unique_ptr_get]
+                    fn __unique_ptr_get(this: *const *mut ::std::ffi::c_void) -> *const ::cxx::Vector<#ident>;
+                }
+                __unique_ptr_get(&repr)
+            }
+            unsafe fn __unique_ptr_release(mut repr: *mut ::std::ffi::c_void) -> *mut ::cxx::Vector<Self> {
+                extern "C" {
+                    #[link_name = #link_unique_ptr_release]
+                    fn __unique_ptr_release(this: *mut *mut ::std::ffi::c_void) -> *mut ::cxx::Vector<#ident>;
+                }
+                __unique_ptr_release(&mut repr)
+            }
+            unsafe fn __unique_ptr_drop(mut repr: *mut ::std::ffi::c_void) {
+                extern "C" {
+                    #[link_name = #link_unique_ptr_drop]
+                    fn __unique_ptr_drop(this: *mut *mut ::std::ffi::c_void);
+                }
+                __unique_ptr_drop(&mut repr);
+            }
+        }
+
+        #slice_element
+    }
+}
+
+fn expand_abi(abi: &Option<LitStr>) -> TokenStream {
+    match abi {
+        Some(abi) => quote!(#abi),
+        None => quote!("C"),
+    }
+}
+
 fn expand_return_type(ret: &Option<Type>) -> TokenStream {
     match ret {
         Some(ret) => quote!(-> #ret),
@@ -553,15 +1446,39 @@ fn indirect_return(sig: &Signature, types: &Types) -> bool {
 fn expand_extern_type(ty: &Type) -> TokenStream {
     match ty {
         Type::Ident(ident) if ident == RustString => quote!(::cxx::private::RustString),
-        Type::RustBox(ty) | Type::UniquePtr(ty) => {
+        Type::Ident(ident) if ident == Char => quote!(u32),
+        Type::RustBox(ty) | Type::UniquePtr(ty) | Type::UniqueArray(ty) => {
             let inner = &ty.inner;
             quote!(*mut #inner)
         }
+        Type::RustArc(ty) => {
+            let inner = &ty.inner;
+            quote!(*const #inner)
+        }
+        // Null maps to None, so the representation is that of the UniquePtr.
+        // Option<&T> is already guaranteed to be represented as a nullable
+        // pointer and crosses unchanged.
+        Type::Optional(optional) => match &optional.inner {
+            Type::UniquePtr(_) => expand_extern_type(&optional.inner),
+            // Option of a primitive is passed as a pointer to the value.
+            Type::Ident(inner) => quote!(*const #inner),
+            _ => quote!(#ty),
+        },
         Type::Ref(ty) => match &ty.inner {
             Type::Ident(ident) if ident == RustString => quote!(&::cxx::private::RustString),
+            Type::RustVec(vec) => {
+                let mutability = &ty.mutability;
+                let inner = &vec.inner;
+                quote!(&#mutability ::cxx::private::RustVec<#inner>)
+            }
             _ => quote!(#ty),
         },
+        Type::RustVec(vec) => {
+            let inner = &vec.inner;
+            quote!(::cxx::private::RustVec<#inner>)
+        }
         Type::Str(_) => quote!(::cxx::private::RustStr),
+        Type::SliceRef(_) | Type::SliceBox(_) => quote!(::cxx::private::RustSlice),
         _ => quote!(#ty),
     }
 }
@@ -4,16 +4,19 @@
     clippy::new_without_default,
     clippy::or_fun_call,
     clippy::toplevel_ref_arg,
+    clippy::too_many_arguments,
+    clippy::unnecessary_map_or,
     clippy::useless_let_if_seq
 )]
 
 extern crate proc_macro;
 
+mod args;
 mod expand;
 mod namespace;
 mod syntax;
 
-use crate::namespace::Namespace;
+use crate::args::Args;
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, ItemMod};
 
@@ -34,14 +37,42 @@ use syn::{parse_macro_input, ItemMod};
 ///
 /// The types and functions from the `extern "Rust"` side of the bridge will be
 /// placed into that same namespace in the generated C++ code.
+///
+/// The namespace may also be written as a string, which is split on `::` into
+/// the same nested namespaces.
+///
+/// ```
+/// #[cxx::bridge(namespace = "mycompany::rust")]
+/// # mod ffi {}
+/// ```
+///
+/// Any segment of the namespace may be preceded by `inline` to declare it as a
+/// C++ inline namespace, as used by libraries that version their symbols. This
+/// works in the string form too, as in `namespace = "mycompany::inline v2"`.
+///
+/// ```
+/// #[cxx::bridge(namespace = mycompany::inline v2)]
+/// # mod ffi {}
+/// ```
+///
+/// The link names of the generated shims include the namespace and the name of
+/// the bridge module, but not the path of the module enclosing it, which the
+/// macro cannot see. Two bridges in one crate with the same namespace and the
+/// same module name, such as two `mod ffi` in different files, therefore need
+/// to be told apart by passing each its module path.
+///
+/// ```
+/// #[cxx::bridge(namespace = mycompany::rust, module = engine::physics::ffi)]
+/// # mod ffi {}
+/// ```
 #[proc_macro_attribute]
 pub fn bridge(args: TokenStream, input: TokenStream) -> TokenStream {
     let _ = syntax::error::ERRORS;
 
-    let namespace = parse_macro_input!(args as Namespace);
+    let args = parse_macro_input!(args as Args);
     let ffi = parse_macro_input!(input as ItemMod);
 
-    expand::bridge(&namespace, ffi)
+    expand::bridge(args, ffi)
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
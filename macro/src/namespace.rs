@@ -2,28 +2,45 @@ use crate::syntax::ident;
 use quote::IdentFragment;
 use std::fmt::{self, Display};
 use syn::parse::{Parse, ParseStream, Result};
-use syn::{Path, Token};
+use syn::{Ident, LitStr, Token};
 
 mod kw {
-    syn::custom_keyword!(namespace);
+    syn::custom_keyword!(inline);
 }
 
 pub struct Namespace {
     segments: Vec<String>,
 }
 
+impl Namespace {
+    pub fn none() -> Self {
+        Namespace {
+            segments: Vec::new(),
+        }
+    }
+}
+
+// The value of the `namespace = ...` bridge argument.
 impl Parse for Namespace {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut segments = Vec::new();
-        if !input.is_empty() {
-            input.parse::<kw::namespace>()?;
-            input.parse::<Token![=]>()?;
-            let path = input.call(Path::parse_mod_style)?;
-            for segment in path.segments {
-                ident::check(&segment.ident)?;
-                segments.push(segment.ident.to_string());
+        if input.peek(LitStr) {
+            let lit: LitStr = input.parse()?;
+            for (_inline, ident) in ident::split_namespace(&lit)? {
+                segments.push(ident.to_string());
+            }
+            return Ok(Namespace { segments });
+        }
+        loop {
+            // Inline namespaces only affect the emitted C++ namespace blocks,
+            // not the link names, so the marker is discarded here.
+            input.parse::<Option<kw::inline>>()?;
+            let ident: Ident = input.parse()?;
+            ident::check(&ident)?;
+            segments.push(ident.to_string());
+            if input.parse::<Option<Token![::]>>()?.is_none() {
+                break;
             }
-            input.parse::<Option<Token![,]>>()?;
         }
         Ok(Namespace { segments })
     }
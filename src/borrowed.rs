@@ -0,0 +1,98 @@
+use std::any::type_name;
+use std::fmt::{self, Debug, Display};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ptr;
+
+/// Non-owning handle to an object owned by C++, corresponding to a
+/// `const T *` on the C++ side.
+///
+/// Unlike `UniquePtr<T>`, dropping a Borrowed never runs the C++ destructor.
+/// Unlike `&T`, it is a plain value that may be stored and copied around for
+/// as long as the lifetime `'a` it was handed out with, and it may be null.
+#[repr(transparent)]
+pub struct Borrowed<'a, T> {
+    ptr: *const T,
+    lifetime: PhantomData<&'a T>,
+}
+
+impl<'a, T> Borrowed<'a, T> {
+    /// Makes a new Borrowed handle referring to nothing.
+    pub fn null() -> Self {
+        Borrowed {
+            ptr: ptr::null(),
+            lifetime: PhantomData,
+        }
+    }
+
+    /// Checks whether the handle refers to nothing.
+    pub fn is_null(&self) -> bool {
+        self.ptr.is_null()
+    }
+
+    /// Returns a reference to the object this handle refers to if any,
+    /// otherwise None.
+    pub fn as_ref(&self) -> Option<&'a T> {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Returns the raw pointer held by this handle.
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+}
+
+impl<'a, T> From<&'a T> for Borrowed<'a, T> {
+    fn from(reference: &'a T) -> Self {
+        Borrowed {
+            ptr: reference,
+            lifetime: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Copy for Borrowed<'a, T> {}
+
+impl<'a, T> Clone for Borrowed<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+unsafe impl<'a, T> Send for Borrowed<'a, T> where T: Sync {}
+unsafe impl<'a, T> Sync for Borrowed<'a, T> where T: Sync {}
+
+impl<'a, T> Deref for Borrowed<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self.as_ref() {
+            Some(target) => target,
+            None => panic!("called deref on a null Borrowed<{}>", type_name::<T>()),
+        }
+    }
+}
+
+impl<'a, T> Debug for Borrowed<'a, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_ref() {
+            None => formatter.write_str("nullptr"),
+            Some(value) => Debug::fmt(value, formatter),
+        }
+    }
+}
+
+impl<'a, T> Display for Borrowed<'a, T>
+where
+    T: Display,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_ref() {
+            None => formatter.write_str("nullptr"),
+            Some(value) => Display::fmt(value, formatter),
+        }
+    }
+}
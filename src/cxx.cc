@@ -1,8 +1,12 @@
 #include "../include/cxx.h"
+#include <cstdlib>
 #include <cstring>
 #include <iostream>
+#include <map>
 #include <memory>
+#include <set>
 #include <stdexcept>
+#include <vector>
 
 extern "C" {
 const char *cxxbridge02$cxx_string$data(const std::string &s) noexcept {
@@ -13,6 +17,37 @@ size_t cxxbridge02$cxx_string$length(const std::string &s) noexcept {
   return s.length();
 }
 
+std::string *cxxbridge02$cxx_string$from_bytes(const char *ptr,
+                                              size_t len) noexcept {
+  return new std::string(ptr, len);
+}
+
+void cxxbridge02$cxx_string$push(std::string &s, const char *ptr,
+                                 size_t len) noexcept {
+  s.append(ptr, len);
+}
+
+void cxxbridge02$cxx_string$clear(std::string &s) noexcept { s.clear(); }
+
+void cxxbridge02$cxx_string$reserve_total(std::string &s,
+                                          size_t cap) noexcept {
+  s.reserve(cap);
+}
+
+const char16_t *
+cxxbridge02$cxx_u16string$data(const std::u16string &s) noexcept {
+  return s.data();
+}
+
+size_t cxxbridge02$cxx_u16string$length(const std::u16string &s) noexcept {
+  return s.length();
+}
+
+std::u16string *cxxbridge02$cxx_u16string$from_units(const char16_t *ptr,
+                                                     size_t len) noexcept {
+  return new std::u16string(ptr, len);
+}
+
 // rust::String
 void cxxbridge02$string$new(rust::String *self) noexcept;
 void cxxbridge02$string$clone(rust::String *self,
@@ -25,11 +60,67 @@ size_t cxxbridge02$string$len(const rust::String *self) noexcept;
 
 // rust::Str
 bool cxxbridge02$str$valid(const char *ptr, size_t len) noexcept;
+
+// rust::Vec
+size_t cxxbridge02$rust_vec$u8$len(const rust::Vec<uint8_t> *self) noexcept;
+size_t
+cxxbridge02$rust_vec$u8$capacity(const rust::Vec<uint8_t> *self) noexcept;
+const uint8_t *
+cxxbridge02$rust_vec$u8$data(const rust::Vec<uint8_t> *self) noexcept;
+void cxxbridge02$rust_vec$u8$reserve_total(rust::Vec<uint8_t> *self,
+                                           size_t cap) noexcept;
+void cxxbridge02$rust_vec$u8$set_len(rust::Vec<uint8_t> *self,
+                                     size_t len) noexcept;
+void cxxbridge02$rust_vec$u8$new(rust::Vec<uint8_t> *self) noexcept;
+void cxxbridge02$rust_vec$u8$drop(rust::Vec<uint8_t> *self) noexcept;
+size_t cxxbridge02$rust_vec$rust_vec$u8$len(
+    const rust::Vec<rust::Vec<uint8_t>> *self) noexcept;
+size_t cxxbridge02$rust_vec$rust_vec$u8$capacity(
+    const rust::Vec<rust::Vec<uint8_t>> *self) noexcept;
+const rust::Vec<uint8_t> *cxxbridge02$rust_vec$rust_vec$u8$data(
+    const rust::Vec<rust::Vec<uint8_t>> *self) noexcept;
+void cxxbridge02$rust_vec$rust_vec$u8$reserve_total(
+    rust::Vec<rust::Vec<uint8_t>> *self, size_t cap) noexcept;
+void cxxbridge02$rust_vec$rust_vec$u8$set_len(
+    rust::Vec<rust::Vec<uint8_t>> *self, size_t len) noexcept;
+void cxxbridge02$rust_vec$rust_vec$u8$new(
+    rust::Vec<rust::Vec<uint8_t>> *self) noexcept;
+void cxxbridge02$rust_vec$rust_vec$u8$drop(
+    rust::Vec<rust::Vec<uint8_t>> *self) noexcept;
+size_t
+cxxbridge02$rust_vec$string$len(const rust::Vec<rust::String> *self) noexcept;
+size_t cxxbridge02$rust_vec$string$capacity(
+    const rust::Vec<rust::String> *self) noexcept;
+const rust::String *
+cxxbridge02$rust_vec$string$data(const rust::Vec<rust::String> *self) noexcept;
+void cxxbridge02$rust_vec$string$reserve_total(rust::Vec<rust::String> *self,
+                                               size_t cap) noexcept;
+void cxxbridge02$rust_vec$string$set_len(rust::Vec<rust::String> *self,
+                                         size_t len) noexcept;
+void cxxbridge02$rust_vec$string$new(rust::Vec<rust::String> *self) noexcept;
+void cxxbridge02$rust_vec$string$drop(rust::Vec<rust::String> *self) noexcept;
 } // extern "C"
 
 namespace rust {
 inline namespace cxxbridge02 {
 
+namespace {
+// Allows this file to be built with -fno-exceptions, in which case invalid
+// input aborts rather than throwing.
+template <typename Exception> [[noreturn]] void panic(const char *msg) {
+#if defined(__cpp_exceptions) || defined(_CPPUNWIND)
+  throw Exception(msg);
+#else
+  std::cerr << msg << std::endl;
+  std::abort();
+#endif
+}
+} // namespace
+
+void slice_out_of_range() {
+  panic<std::out_of_range>("rust::Slice index out of range");
+}
+
 String::String() noexcept { cxxbridge02$string$new(this); }
 
 String::String(const String &other) noexcept {
@@ -47,14 +138,14 @@ String::String(const std::string &s) {
   auto ptr = s.data();
   auto len = s.length();
   if (!cxxbridge02$string$from(this, ptr, len)) {
-    throw std::invalid_argument("data for rust::String is not utf-8");
+    panic<std::invalid_argument>("data for rust::String is not utf-8");
   }
 }
 
 String::String(const char *s) {
   auto len = std::strlen(s);
   if (!cxxbridge02$string$from(this, s, len)) {
-    throw std::invalid_argument("data for rust::String is not utf-8");
+    panic<std::invalid_argument>("data for rust::String is not utf-8");
   }
 }
 
@@ -101,13 +192,19 @@ Str::Str(const Str &) noexcept = default;
 
 Str::Str(const std::string &s) : repr(Repr{s.data(), s.length()}) {
   if (!cxxbridge02$str$valid(this->repr.ptr, this->repr.len)) {
-    throw std::invalid_argument("data for rust::Str is not utf-8");
+    panic<std::invalid_argument>("data for rust::Str is not utf-8");
   }
 }
 
 Str::Str(const char *s) : repr(Repr{s, std::strlen(s)}) {
   if (!cxxbridge02$str$valid(this->repr.ptr, this->repr.len)) {
-    throw std::invalid_argument("data for rust::Str is not utf-8");
+    panic<std::invalid_argument>("data for rust::Str is not utf-8");
+  }
+}
+
+Str::Str(const char *s, size_t len) : repr(Repr{s, len}) {
+  if (!cxxbridge02$str$valid(this->repr.ptr, this->repr.len)) {
+    panic<std::invalid_argument>("data for rust::Str is not utf-8");
   }
 }
 
@@ -135,6 +232,90 @@ std::ostream &operator<<(std::ostream &os, const Str &s) {
   return os;
 }
 
+template <> size_t Vec<uint8_t>::size() const noexcept {
+  return cxxbridge02$rust_vec$u8$len(this);
+}
+
+template <> size_t Vec<uint8_t>::capacity() const noexcept {
+  return cxxbridge02$rust_vec$u8$capacity(this);
+}
+
+template <> const uint8_t *Vec<uint8_t>::data() const noexcept {
+  return cxxbridge02$rust_vec$u8$data(this);
+}
+
+template <> void Vec<uint8_t>::reserve(size_t new_cap) noexcept {
+  cxxbridge02$rust_vec$u8$reserve_total(this, new_cap);
+}
+
+template <> void Vec<uint8_t>::set_len(size_t len) noexcept {
+  cxxbridge02$rust_vec$u8$set_len(this, len);
+}
+
+template <> Vec<uint8_t>::Vec() noexcept {
+  cxxbridge02$rust_vec$u8$new(this);
+}
+
+template <> void Vec<uint8_t>::drop() noexcept {
+  cxxbridge02$rust_vec$u8$drop(this);
+}
+
+template <> size_t Vec<Vec<uint8_t>>::size() const noexcept {
+  return cxxbridge02$rust_vec$rust_vec$u8$len(this);
+}
+
+template <> size_t Vec<Vec<uint8_t>>::capacity() const noexcept {
+  return cxxbridge02$rust_vec$rust_vec$u8$capacity(this);
+}
+
+template <> const Vec<uint8_t> *Vec<Vec<uint8_t>>::data() const noexcept {
+  return cxxbridge02$rust_vec$rust_vec$u8$data(this);
+}
+
+template <> void Vec<Vec<uint8_t>>::reserve(size_t new_cap) noexcept {
+  cxxbridge02$rust_vec$rust_vec$u8$reserve_total(this, new_cap);
+}
+
+template <> void Vec<Vec<uint8_t>>::set_len(size_t len) noexcept {
+  cxxbridge02$rust_vec$rust_vec$u8$set_len(this, len);
+}
+
+template <> Vec<Vec<uint8_t>>::Vec() noexcept {
+  cxxbridge02$rust_vec$rust_vec$u8$new(this);
+}
+
+template <> void Vec<Vec<uint8_t>>::drop() noexcept {
+  cxxbridge02$rust_vec$rust_vec$u8$drop(this);
+}
+
+template <> size_t Vec<String>::size() const noexcept {
+  return cxxbridge02$rust_vec$string$len(this);
+}
+
+template <> size_t Vec<String>::capacity() const noexcept {
+  return cxxbridge02$rust_vec$string$capacity(this);
+}
+
+template <> const String *Vec<String>::data() const noexcept {
+  return cxxbridge02$rust_vec$string$data(this);
+}
+
+template <> void Vec<String>::reserve(size_t new_cap) noexcept {
+  cxxbridge02$rust_vec$string$reserve_total(this, new_cap);
+}
+
+template <> void Vec<String>::set_len(size_t len) noexcept {
+  cxxbridge02$rust_vec$string$set_len(this, len);
+}
+
+template <> Vec<String>::Vec() noexcept {
+  cxxbridge02$rust_vec$string$new(this);
+}
+
+template <> void Vec<String>::drop() noexcept {
+  cxxbridge02$rust_vec$string$drop(this);
+}
+
 extern "C" {
 const char *cxxbridge02$error(const char *ptr, size_t len) {
   char *copy = new char[len];
@@ -189,4 +370,273 @@ void cxxbridge02$unique_ptr$std$string$drop(
     std::unique_ptr<std::string> *ptr) noexcept {
   ptr->~unique_ptr();
 }
+
+void cxxbridge02$unique_ptr$std$u16string$null(
+    std::unique_ptr<std::u16string> *ptr) noexcept {
+  new (ptr) std::unique_ptr<std::u16string>();
+}
+void cxxbridge02$unique_ptr$std$u16string$new(
+    std::unique_ptr<std::u16string> *ptr, std::u16string *value) noexcept {
+  new (ptr)
+      std::unique_ptr<std::u16string>(new std::u16string(std::move(*value)));
+}
+void cxxbridge02$unique_ptr$std$u16string$raw(
+    std::unique_ptr<std::u16string> *ptr, std::u16string *raw) noexcept {
+  new (ptr) std::unique_ptr<std::u16string>(raw);
+}
+const std::u16string *cxxbridge02$unique_ptr$std$u16string$get(
+    const std::unique_ptr<std::u16string> &ptr) noexcept {
+  return ptr.get();
+}
+std::u16string *cxxbridge02$unique_ptr$std$u16string$release(
+    std::unique_ptr<std::u16string> &ptr) noexcept {
+  return ptr.release();
+}
+void cxxbridge02$unique_ptr$std$u16string$drop(
+    std::unique_ptr<std::u16string> *ptr) noexcept {
+  ptr->~unique_ptr();
+}
+} // extern "C"
+
+#define STD_VECTOR_OPS(RUST_TYPE, CXX_TYPE)                                    \
+  size_t cxxbridge02$std$vector$##RUST_TYPE##$size(                            \
+      const std::vector<CXX_TYPE> &s) noexcept {                               \
+    return s.size();                                                           \
+  }                                                                            \
+  const CXX_TYPE *cxxbridge02$std$vector$##RUST_TYPE##$get_unchecked(          \
+      const std::vector<CXX_TYPE> &s, size_t pos) noexcept {                   \
+    return &s[pos];                                                            \
+  }                                                                            \
+  void cxxbridge02$std$vector$##RUST_TYPE##$pop_back(                          \
+      std::vector<CXX_TYPE> &s) noexcept {                                     \
+    s.pop_back();                                                              \
+  }                                                                            \
+  void cxxbridge02$std$vector$##RUST_TYPE##$clear(                             \
+      std::vector<CXX_TYPE> &s) noexcept {                                     \
+    s.clear();                                                                 \
+  }                                                                            \
+  void cxxbridge02$std$vector$##RUST_TYPE##$reserve(                           \
+      std::vector<CXX_TYPE> &s, size_t new_cap) noexcept {                     \
+    s.reserve(new_cap);                                                        \
+  }                                                                            \
+  void cxxbridge02$unique_ptr$std$vector$##RUST_TYPE##$null(                   \
+      std::unique_ptr<std::vector<CXX_TYPE>> *ptr) noexcept {                  \
+    new (ptr) std::unique_ptr<std::vector<CXX_TYPE>>();                        \
+  }                                                                            \
+  void cxxbridge02$unique_ptr$std$vector$##RUST_TYPE##$raw(                    \
+      std::unique_ptr<std::vector<CXX_TYPE>> *ptr,                             \
+      std::vector<CXX_TYPE> *raw) noexcept {                                   \
+    new (ptr) std::unique_ptr<std::vector<CXX_TYPE>>(raw);                     \
+  }                                                                            \
+  const std::vector<CXX_TYPE>                                                  \
+      *cxxbridge02$unique_ptr$std$vector$##RUST_TYPE##$get(                    \
+          const std::unique_ptr<std::vector<CXX_TYPE>> &ptr) noexcept {        \
+    return ptr.get();                                                          \
+  }                                                                            \
+  std::vector<CXX_TYPE>                                                        \
+      *cxxbridge02$unique_ptr$std$vector$##RUST_TYPE##$release(                \
+          std::unique_ptr<std::vector<CXX_TYPE>> &ptr) noexcept {              \
+    return ptr.release();                                                      \
+  }                                                                            \
+  void cxxbridge02$unique_ptr$std$vector$##RUST_TYPE##$drop(                   \
+      std::unique_ptr<std::vector<CXX_TYPE>> *ptr) noexcept {                  \
+    ptr->~unique_ptr();                                                        \
+  }
+
+#define BOX_SLICE_OPS(RUST_TYPE, CXX_TYPE)                                      \
+  extern "C" {                                                                 \
+  void cxxbridge02$box_slice$##RUST_TYPE##$drop(                               \
+      rust::Box<CXX_TYPE[]>::Repr repr) noexcept;                              \
+  }                                                                            \
+  namespace rust {                                                             \
+  inline namespace cxxbridge02 {                                               \
+  template <> void Box<CXX_TYPE[]>::drop() noexcept {                          \
+    cxxbridge02$box_slice$##RUST_TYPE##$drop(this->repr);                      \
+  }                                                                            \
+  }                                                                            \
+  }
+
+BOX_SLICE_OPS(u8, uint8_t)
+BOX_SLICE_OPS(u16, uint16_t)
+BOX_SLICE_OPS(u32, uint32_t)
+BOX_SLICE_OPS(u64, uint64_t)
+BOX_SLICE_OPS(i8, int8_t)
+BOX_SLICE_OPS(i16, int16_t)
+BOX_SLICE_OPS(i32, int32_t)
+BOX_SLICE_OPS(i64, int64_t)
+BOX_SLICE_OPS(f32, float)
+BOX_SLICE_OPS(f64, double)
+
+extern "C" {
+STD_VECTOR_OPS(u8, uint8_t)
+STD_VECTOR_OPS(u16, uint16_t)
+STD_VECTOR_OPS(u32, uint32_t)
+STD_VECTOR_OPS(u64, uint64_t)
+STD_VECTOR_OPS(usize, size_t)
+STD_VECTOR_OPS(i8, int8_t)
+STD_VECTOR_OPS(i16, int16_t)
+STD_VECTOR_OPS(i32, int32_t)
+STD_VECTOR_OPS(i64, int64_t)
+STD_VECTOR_OPS(f32, float)
+STD_VECTOR_OPS(f64, double)
+} // extern "C"
+
+// std::vector<bool> is bit-packed; its operator[] yields a proxy rather than a
+// reference, so elements cross the bridge by value.
+extern "C" {
+size_t cxxbridge02$std$vector$bool$size(const std::vector<bool> &s) noexcept {
+  return s.size();
+}
+bool cxxbridge02$std$vector$bool$get_unchecked(const std::vector<bool> &s,
+                                               size_t pos) noexcept {
+  return s[pos];
+}
+void cxxbridge02$std$vector$bool$push_back(std::vector<bool> &s,
+                                           bool value) noexcept {
+  s.push_back(value);
+}
+void cxxbridge02$std$vector$bool$pop_back(std::vector<bool> &s) noexcept {
+  s.pop_back();
+}
+void cxxbridge02$std$vector$bool$clear(std::vector<bool> &s) noexcept {
+  s.clear();
+}
+void cxxbridge02$std$vector$bool$reserve(std::vector<bool> &s,
+                                         size_t new_cap) noexcept {
+  s.reserve(new_cap);
+}
+void cxxbridge02$unique_ptr$std$vector$bool$null(
+    std::unique_ptr<std::vector<bool>> *ptr) noexcept {
+  new (ptr) std::unique_ptr<std::vector<bool>>();
+}
+void cxxbridge02$unique_ptr$std$vector$bool$raw(
+    std::unique_ptr<std::vector<bool>> *ptr, std::vector<bool> *raw) noexcept {
+  new (ptr) std::unique_ptr<std::vector<bool>>(raw);
+}
+const std::vector<bool> *cxxbridge02$unique_ptr$std$vector$bool$get(
+    const std::unique_ptr<std::vector<bool>> &ptr) noexcept {
+  return ptr.get();
+}
+std::vector<bool> *cxxbridge02$unique_ptr$std$vector$bool$release(
+    std::unique_ptr<std::vector<bool>> &ptr) noexcept {
+  return ptr.release();
+}
+void cxxbridge02$unique_ptr$std$vector$bool$drop(
+    std::unique_ptr<std::vector<bool>> *ptr) noexcept {
+  ptr->~unique_ptr();
+}
+} // extern "C"
+
+#define STD_MAP_OPS(RUST_KEY, CXX_KEY, RUST_VALUE, CXX_VALUE)                  \
+  size_t cxxbridge02$std$map$##RUST_KEY##$##RUST_VALUE##$size(                 \
+      const std::map<CXX_KEY, CXX_VALUE> &m) noexcept {                        \
+    return m.size();                                                           \
+  }                                                                            \
+  const CXX_VALUE *cxxbridge02$std$map$##RUST_KEY##$##RUST_VALUE##$get(        \
+      const std::map<CXX_KEY, CXX_VALUE> &m, const CXX_KEY &key) noexcept {    \
+    auto it = m.find(key);                                                     \
+    return it == m.end() ? nullptr : &it->second;                              \
+  }                                                                            \
+  void cxxbridge02$std$map$##RUST_KEY##$##RUST_VALUE##$iterate(                \
+      const std::map<CXX_KEY, CXX_VALUE> &m,                                   \
+      void (*visit)(void *, const CXX_KEY *, const CXX_VALUE *),               \
+      void *ctx) noexcept {                                                    \
+    for (const auto &entry : m) {                                              \
+      visit(ctx, &entry.first, &entry.second);                                 \
+    }                                                                          \
+  }
+
+#define STD_MAP_VALUE_OPS(RUST_KEY, CXX_KEY)                                   \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, u8, uint8_t)                                  \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, u16, uint16_t)                                \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, u32, uint32_t)                                \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, u64, uint64_t)                                \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, usize, size_t)                                \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, i8, int8_t)                                   \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, i16, int16_t)                                 \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, i32, int32_t)                                 \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, i64, int64_t)                                 \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, f32, float)                                   \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, f64, double)                                  \
+  STD_MAP_OPS(RUST_KEY, CXX_KEY, string, std::string)
+
+extern "C" {
+STD_MAP_VALUE_OPS(u8, uint8_t)
+STD_MAP_VALUE_OPS(u16, uint16_t)
+STD_MAP_VALUE_OPS(u32, uint32_t)
+STD_MAP_VALUE_OPS(u64, uint64_t)
+STD_MAP_VALUE_OPS(usize, size_t)
+STD_MAP_VALUE_OPS(i8, int8_t)
+STD_MAP_VALUE_OPS(i16, int16_t)
+STD_MAP_VALUE_OPS(i32, int32_t)
+STD_MAP_VALUE_OPS(i64, int64_t)
+STD_MAP_VALUE_OPS(string, std::string)
+} // extern "C"
+
+#define STD_SET_OPS(RUST_TYPE, CXX_TYPE)                                       \
+  size_t cxxbridge02$std$set$##RUST_TYPE##$size(                               \
+      const std::set<CXX_TYPE> &s) noexcept {                                  \
+    return s.size();                                                           \
+  }                                                                            \
+  bool cxxbridge02$std$set$##RUST_TYPE##$contains(                             \
+      const std::set<CXX_TYPE> &s, const CXX_TYPE &value) noexcept {           \
+    return s.count(value) != 0;                                                \
+  }                                                                            \
+  void cxxbridge02$std$set$##RUST_TYPE##$iterate(                              \
+      const std::set<CXX_TYPE> &s, void (*visit)(void *, const CXX_TYPE *),    \
+      void *ctx) noexcept {                                                    \
+    for (const auto &value : s) {                                              \
+      visit(ctx, &value);                                                      \
+    }                                                                          \
+  }
+
+extern "C" {
+STD_SET_OPS(u8, uint8_t)
+STD_SET_OPS(u16, uint16_t)
+STD_SET_OPS(u32, uint32_t)
+STD_SET_OPS(u64, uint64_t)
+STD_SET_OPS(usize, size_t)
+STD_SET_OPS(i8, int8_t)
+STD_SET_OPS(i16, int16_t)
+STD_SET_OPS(i32, int32_t)
+STD_SET_OPS(i64, int64_t)
+STD_SET_OPS(f32, float)
+STD_SET_OPS(f64, double)
+STD_SET_OPS(string, std::string)
+} // extern "C"
+
+#define UNIQUE_PTR_ARRAY_OPS(RUST_TYPE, CXX_TYPE)                              \
+  void cxxbridge02$unique_ptr$array$##RUST_TYPE##$null(                        \
+      std::unique_ptr<CXX_TYPE[]> *ptr) noexcept {                             \
+    new (ptr) std::unique_ptr<CXX_TYPE[]>();                                   \
+  }                                                                            \
+  void cxxbridge02$unique_ptr$array$##RUST_TYPE##$raw(                         \
+      std::unique_ptr<CXX_TYPE[]> *ptr, CXX_TYPE *raw) noexcept {              \
+    new (ptr) std::unique_ptr<CXX_TYPE[]>(raw);                                \
+  }                                                                            \
+  const CXX_TYPE *cxxbridge02$unique_ptr$array$##RUST_TYPE##$get(              \
+      const std::unique_ptr<CXX_TYPE[]> &ptr) noexcept {                       \
+    return ptr.get();                                                          \
+  }                                                                            \
+  CXX_TYPE *cxxbridge02$unique_ptr$array$##RUST_TYPE##$release(                \
+      std::unique_ptr<CXX_TYPE[]> &ptr) noexcept {                             \
+    return ptr.release();                                                      \
+  }                                                                            \
+  void cxxbridge02$unique_ptr$array$##RUST_TYPE##$drop(                        \
+      std::unique_ptr<CXX_TYPE[]> *ptr) noexcept {                             \
+    ptr->~unique_ptr();                                                        \
+  }
+
+extern "C" {
+UNIQUE_PTR_ARRAY_OPS(u8, uint8_t)
+UNIQUE_PTR_ARRAY_OPS(u16, uint16_t)
+UNIQUE_PTR_ARRAY_OPS(u32, uint32_t)
+UNIQUE_PTR_ARRAY_OPS(u64, uint64_t)
+UNIQUE_PTR_ARRAY_OPS(usize, size_t)
+UNIQUE_PTR_ARRAY_OPS(i8, int8_t)
+UNIQUE_PTR_ARRAY_OPS(i16, int16_t)
+UNIQUE_PTR_ARRAY_OPS(i32, int32_t)
+UNIQUE_PTR_ARRAY_OPS(i64, int64_t)
+UNIQUE_PTR_ARRAY_OPS(f32, float)
+UNIQUE_PTR_ARRAY_OPS(f64, double)
 } // extern "C"
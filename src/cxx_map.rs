@@ -0,0 +1,215 @@
+use crate::cxx_string::CxxString;
+use std::ffi::c_void;
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::vec;
+
+/// Binding to C++ `std::map<K, V>`.
+///
+/// # Invariants
+///
+/// As with [`Vector`][crate::Vector], in Rust code we can never obtain a
+/// `CxxMap` by value. Instead in Rust code we will only ever look at a CxxMap
+/// through a reference, as in `&CxxMap<K, V>`.
+#[repr(C)]
+pub struct CxxMap<K, V> {
+    _private: [*const c_void; 0],
+    _key: PhantomData<K>,
+    _value: PhantomData<V>,
+}
+
+impl<K, V> CxxMap<K, V>
+where
+    V: MapValue<K>,
+{
+    /// Returns the number of entries in the map.
+    ///
+    /// Matches the behavior of C++ [std::map\<K, V\>::size][size].
+    ///
+    /// [size]: https://en.cppreference.com/w/cpp/container/map/size
+    pub fn len(&self) -> usize {
+        V::__map_size(self)
+    }
+
+    /// Returns true if the map contains no entries.
+    ///
+    /// Matches the behavior of C++ [std::map\<K, V\>::empty][empty].
+    ///
+    /// [empty]: https://en.cppreference.com/w/cpp/container/map/empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the value stored under the given key, or
+    /// `None` if the map has no such entry.
+    ///
+    /// Matches the behavior of C++ [std::map\<K, V\>::find][find].
+    ///
+    /// [find]: https://en.cppreference.com/w/cpp/container/map/find
+    pub fn get(&self, key: &K) -> Option<&V> {
+        unsafe { V::__map_get(self, key).as_ref() }
+    }
+
+    /// Returns true if the map has an entry for the given key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns an iterator over the entries of the map, in the order of
+    /// their keys as sorted by the std::map.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        // The C++ side walks the map in its own order and reports every
+        // entry, so the order seen here is exactly std::map's.
+        let mut entries = Vec::with_capacity(self.len());
+        let ctx = &mut entries as *mut Vec<(&K, &V)> as *mut c_void;
+        unsafe { V::__map_iterate(self, visit::<K, V>, ctx) }
+        Iter {
+            entries: entries.into_iter(),
+        }
+    }
+}
+
+unsafe extern "C" fn visit<K, V>(ctx: *mut c_void, key: *const K, value: *const V) {
+    let entries = &mut *(ctx as *mut Vec<(&K, &V)>);
+    entries.push((&*key, &*value));
+}
+
+/// Iterator over entries of a `CxxMap` by shared reference.
+pub struct Iter<'a, K, V> {
+    entries: vec::IntoIter<(&'a K, &'a V)>,
+}
+
+impl<'a, K, V> IntoIterator for &'a CxxMap<K, V>
+where
+    V: MapValue<K>,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
+
+impl<K, V> Debug for CxxMap<K, V>
+where
+    K: Debug,
+    V: MapValue<K> + Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_map().entries(self).finish()
+    }
+}
+
+// Methods are private; not intended to be implemented outside of cxxbridge
+// codebase.
+#[doc(hidden)]
+pub unsafe trait MapValue<K>: Sized {
+    #[doc(hidden)]
+    fn __map_size(m: &CxxMap<K, Self>) -> usize;
+    #[doc(hidden)]
+    fn __map_get(m: &CxxMap<K, Self>, key: &K) -> *const Self;
+    #[doc(hidden)]
+    unsafe fn __map_iterate(
+        m: &CxxMap<K, Self>,
+        visit: unsafe extern "C" fn(*mut c_void, *const K, *const Self),
+        ctx: *mut c_void,
+    );
+}
+
+macro_rules! impl_map_value_for_primitive {
+    ($key:ty, $key_name:literal, $ty:ty, $name:literal) => {
+        unsafe impl MapValue<$key> for $ty {
+            fn __map_size(m: &CxxMap<$key, $ty>) -> usize {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$std$map$", $key_name, "$", $name, "$size")]
+                        fn __map_size(_: &CxxMap<$key, $ty>) -> usize;
+                    }
+                }
+                unsafe { __map_size(m) }
+            }
+            fn __map_get(m: &CxxMap<$key, $ty>, key: &$key) -> *const $ty {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$std$map$", $key_name, "$", $name, "$get")]
+                        fn __map_get(_: &CxxMap<$key, $ty>, key: &$key) -> *const $ty;
+                    }
+                }
+                unsafe { __map_get(m, key) }
+            }
+            unsafe fn __map_iterate(
+                m: &CxxMap<$key, $ty>,
+                visit: unsafe extern "C" fn(*mut c_void, *const $key, *const $ty),
+                ctx: *mut c_void,
+            ) {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$std$map$", $key_name, "$", $name, "$iterate")]
+                        fn __map_iterate(
+                            _: &CxxMap<$key, $ty>,
+                            visit: unsafe extern "C" fn(*mut c_void, *const $key, *const $ty),
+                            ctx: *mut c_void,
+                        );
+                    }
+                }
+                __map_iterate(m, visit, ctx)
+            }
+        }
+    };
+}
+
+macro_rules! impl_map_values_for_key {
+    ($key:ty, $key_name:literal) => {
+        impl_map_value_for_primitive!($key, $key_name, u8, "u8");
+        impl_map_value_for_primitive!($key, $key_name, u16, "u16");
+        impl_map_value_for_primitive!($key, $key_name, u32, "u32");
+        impl_map_value_for_primitive!($key, $key_name, u64, "u64");
+        impl_map_value_for_primitive!($key, $key_name, usize, "usize");
+        impl_map_value_for_primitive!($key, $key_name, i8, "i8");
+        impl_map_value_for_primitive!($key, $key_name, i16, "i16");
+        impl_map_value_for_primitive!($key, $key_name, i32, "i32");
+        impl_map_value_for_primitive!($key, $key_name, i64, "i64");
+        impl_map_value_for_primitive!($key, $key_name, f32, "f32");
+        impl_map_value_for_primitive!($key, $key_name, f64, "f64");
+        impl_map_value_for_primitive!($key, $key_name, CxxString, "string");
+    };
+}
+
+impl_map_values_for_key!(u8, "u8");
+impl_map_values_for_key!(u16, "u16");
+impl_map_values_for_key!(u32, "u32");
+impl_map_values_for_key!(u64, "u64");
+impl_map_values_for_key!(usize, "usize");
+impl_map_values_for_key!(i8, "i8");
+impl_map_values_for_key!(i16, "i16");
+impl_map_values_for_key!(i32, "i32");
+impl_map_values_for_key!(i64, "i64");
+impl_map_values_for_key!(CxxString, "string");
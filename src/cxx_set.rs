@@ -0,0 +1,190 @@
+use crate::cxx_string::CxxString;
+use std::ffi::c_void;
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::vec;
+
+/// Binding to C++ `std::set<T>`.
+///
+/// # Invariants
+///
+/// As with [`Vector`][crate::Vector], in Rust code we can never obtain a
+/// `CxxSet` by value. Instead in Rust code we will only ever look at a CxxSet
+/// through a reference, as in `&CxxSet<T>`.
+#[repr(C)]
+pub struct CxxSet<T> {
+    _private: [*const c_void; 0],
+    _element: PhantomData<T>,
+}
+
+impl<T> CxxSet<T>
+where
+    T: SetElement,
+{
+    /// Returns the number of elements in the set.
+    ///
+    /// Matches the behavior of C++ [std::set\<T\>::size][size].
+    ///
+    /// [size]: https://en.cppreference.com/w/cpp/container/set/size
+    pub fn len(&self) -> usize {
+        T::__set_size(self)
+    }
+
+    /// Returns true if the set contains no elements.
+    ///
+    /// Matches the behavior of C++ [std::set\<T\>::empty][empty].
+    ///
+    /// [empty]: https://en.cppreference.com/w/cpp/container/set/empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the set has an element equal to the given one.
+    ///
+    /// Matches the behavior of C++ [std::set\<T\>::count][count].
+    ///
+    /// [count]: https://en.cppreference.com/w/cpp/container/set/count
+    pub fn contains(&self, value: &T) -> bool {
+        T::__set_contains(self, value)
+    }
+
+    /// Returns an iterator over the elements of the set, in the order in which
+    /// the std::set keeps them.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut elements = Vec::with_capacity(self.len());
+        let ctx = &mut elements as *mut Vec<&T> as *mut c_void;
+        unsafe { T::__set_iterate(self, visit::<T>, ctx) }
+        Iter {
+            elements: elements.into_iter(),
+        }
+    }
+}
+
+unsafe extern "C" fn visit<T>(ctx: *mut c_void, value: *const T) {
+    let elements = &mut *(ctx as *mut Vec<&T>);
+    elements.push(&*value);
+}
+
+/// Iterator over elements of a `CxxSet` by shared reference.
+pub struct Iter<'a, T> {
+    elements: vec::IntoIter<&'a T>,
+}
+
+impl<'a, T> IntoIterator for &'a CxxSet<T>
+where
+    T: SetElement,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.elements.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+}
+
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+impl<T> Debug for CxxSet<T>
+where
+    T: SetElement + Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_set().entries(self).finish()
+    }
+}
+
+// Methods are private; not intended to be implemented outside of cxxbridge
+// codebase.
+#[doc(hidden)]
+pub unsafe trait SetElement: Sized {
+    #[doc(hidden)]
+    fn __set_size(s: &CxxSet<Self>) -> usize;
+    #[doc(hidden)]
+    fn __set_contains(s: &CxxSet<Self>, value: &Self) -> bool;
+    #[doc(hidden)]
+    unsafe fn __set_iterate(
+        s: &CxxSet<Self>,
+        visit: unsafe extern "C" fn(*mut c_void, *const Self),
+        ctx: *mut c_void,
+    );
+}
+
+macro_rules! impl_set_element_for_primitive {
+    ($ty:ty, $name:literal) => {
+        unsafe impl SetElement for $ty {
+            fn __set_size(s: &CxxSet<$ty>) -> usize {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$std$set$", $name, "$size")]
+                        fn __set_size(_: &CxxSet<$ty>) -> usize;
+                    }
+                }
+                unsafe { __set_size(s) }
+            }
+            fn __set_contains(s: &CxxSet<$ty>, value: &$ty) -> bool {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$std$set$", $name, "$contains")]
+                        fn __set_contains(_: &CxxSet<$ty>, value: &$ty) -> bool;
+                    }
+                }
+                unsafe { __set_contains(s, value) }
+            }
+            unsafe fn __set_iterate(
+                s: &CxxSet<$ty>,
+                visit: unsafe extern "C" fn(*mut c_void, *const $ty),
+                ctx: *mut c_void,
+            ) {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$std$set$", $name, "$iterate")]
+                        fn __set_iterate(
+                            _: &CxxSet<$ty>,
+                            visit: unsafe extern "C" fn(*mut c_void, *const $ty),
+                            ctx: *mut c_void,
+                        );
+                    }
+                }
+                __set_iterate(s, visit, ctx)
+            }
+        }
+    };
+}
+
+impl_set_element_for_primitive!(u8, "u8");
+impl_set_element_for_primitive!(u16, "u16");
+impl_set_element_for_primitive!(u32, "u32");
+impl_set_element_for_primitive!(u64, "u64");
+impl_set_element_for_primitive!(usize, "usize");
+impl_set_element_for_primitive!(i8, "i8");
+impl_set_element_for_primitive!(i16, "i16");
+impl_set_element_for_primitive!(i32, "i32");
+impl_set_element_for_primitive!(i64, "i64");
+impl_set_element_for_primitive!(f32, "f32");
+impl_set_element_for_primitive!(f64, "f64");
+impl_set_element_for_primitive!(CxxString, "string");
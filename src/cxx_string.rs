@@ -1,3 +1,4 @@
+use crate::unique_ptr::UniquePtr;
 use std::borrow::Cow;
 use std::fmt::{self, Debug, Display};
 use std::slice;
@@ -8,6 +9,14 @@ extern "C" {
     fn string_data(_: &CxxString) -> *const u8;
     #[link_name = "cxxbridge02$cxx_string$length"]
     fn string_length(_: &CxxString) -> usize;
+    #[link_name = "cxxbridge02$cxx_string$from_bytes"]
+    fn string_from_bytes(ptr: *const u8, len: usize) -> *mut CxxString;
+    #[link_name = "cxxbridge02$cxx_string$push"]
+    fn string_push(_: &mut CxxString, ptr: *const u8, len: usize);
+    #[link_name = "cxxbridge02$cxx_string$clear"]
+    fn string_clear(_: &mut CxxString);
+    #[link_name = "cxxbridge02$cxx_string$reserve_total"]
+    fn string_reserve_total(_: &mut CxxString, cap: usize);
 }
 
 /// Binding to C++ `std::string`.
@@ -26,6 +35,15 @@ pub struct CxxString {
 }
 
 impl CxxString {
+    /// Allocates a new C++ string holding a copy of the given bytes.
+    ///
+    /// The bytes need not be UTF-8 and may contain null bytes, all of which
+    /// are preserved, as with the C++ `std::string(const char *, size_t)`
+    /// constructor.
+    pub fn from_bytes(bytes: &[u8]) -> UniquePtr<CxxString> {
+        unsafe { UniquePtr::from_raw(string_from_bytes(bytes.as_ptr(), bytes.len())) }
+    }
+
     /// Returns the length of the string in bytes.
     ///
     /// Matches the behavior of C++ [std::string::size][size].
@@ -73,9 +91,48 @@ impl CxxString {
     /// Cow::Owned String.
     ///
     /// [replacement character]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
-    pub fn to_string_lossy(&self) -> Cow<str> {
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
         String::from_utf8_lossy(self.as_bytes())
     }
+
+    /// Appends the given string to the end of this C++ string.
+    ///
+    /// Matches the behavior of C++ [std::string::append][append].
+    ///
+    /// [append]: https://en.cppreference.com/w/cpp/string/basic_string/append
+    pub fn push_str(&mut self, s: &str) {
+        self.push_bytes(s.as_bytes());
+    }
+
+    /// Appends arbitrary bytes to the end of this C++ string.
+    ///
+    /// Matches the behavior of C++ [std::string::append][append].
+    ///
+    /// [append]: https://en.cppreference.com/w/cpp/string/basic_string/append
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        unsafe { string_push(self, bytes.as_ptr(), bytes.len()) }
+    }
+
+    /// Removes all bytes from the string, keeping its capacity.
+    ///
+    /// Matches the behavior of C++ [std::string::clear][clear].
+    ///
+    /// [clear]: https://en.cppreference.com/w/cpp/string/basic_string/clear
+    pub fn clear(&mut self) {
+        unsafe { string_clear(self) }
+    }
+
+    /// Ensures the string can hold at least `new_cap` bytes in total without
+    /// reallocating.
+    ///
+    /// Matches the behavior of C++ [std::string::reserve][reserve], so unlike
+    /// Rust's String::reserve the argument is the total capacity, not the
+    /// additional bytes beyond the current length.
+    ///
+    /// [reserve]: https://en.cppreference.com/w/cpp/string/basic_string/reserve
+    pub fn reserve(&mut self, new_cap: usize) {
+        unsafe { string_reserve_total(self, new_cap) }
+    }
 }
 
 impl Display for CxxString {
@@ -107,3 +164,15 @@ impl PartialEq<str> for CxxString {
         self.as_bytes() == other.as_bytes()
     }
 }
+
+impl PartialEq<CxxString> for String {
+    fn eq(&self, other: &CxxString) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<String> for CxxString {
+    fn eq(&self, other: &String) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
@@ -0,0 +1,91 @@
+use crate::unique_ptr::UniquePtr;
+use std::char;
+use std::fmt::{self, Debug, Display};
+use std::slice;
+
+extern "C" {
+    #[link_name = "cxxbridge02$cxx_u16string$data"]
+    fn u16string_data(_: &CxxU16String) -> *const u16;
+    #[link_name = "cxxbridge02$cxx_u16string$length"]
+    fn u16string_length(_: &CxxU16String) -> usize;
+    #[link_name = "cxxbridge02$cxx_u16string$from_units"]
+    fn u16string_from_units(ptr: *const u16, len: usize) -> *mut CxxU16String;
+}
+
+/// Binding to C++ `std::u16string`.
+///
+/// # Invariants
+///
+/// Like [`CxxString`][crate::CxxString], a `CxxU16String` is never obtained
+/// by value in Rust code, only through a reference or smart pointer, as in
+/// `&CxxU16String` or `UniquePtr<CxxU16String>`.
+#[repr(C)]
+pub struct CxxU16String {
+    _private: [u8; 0],
+}
+
+impl CxxU16String {
+    /// Allocates a new C++ string holding a copy of the given UTF-16 code
+    /// units, which need not be valid UTF-16.
+    pub fn from_units(units: &[u16]) -> UniquePtr<CxxU16String> {
+        unsafe { UniquePtr::from_raw(u16string_from_units(units.as_ptr(), units.len())) }
+    }
+
+    /// Returns the length of the string in UTF-16 code units.
+    ///
+    /// Matches the behavior of C++ [std::u16string::size][size].
+    ///
+    /// [size]: https://en.cppreference.com/w/cpp/string/basic_string/size
+    pub fn len(&self) -> usize {
+        unsafe { u16string_length(self) }
+    }
+
+    /// Returns true if `self` has a length of zero code units.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a slice of this string's UTF-16 code units.
+    pub fn as_units(&self) -> &[u16] {
+        let len = self.len();
+        if len == 0 {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(u16string_data(self), len) }
+    }
+
+    /// Decodes the UTF-16 contents of the C++ string, replacing any unpaired
+    /// surrogate with the U+FFFD [replacement character].
+    ///
+    /// [replacement character]: https://doc.rust-lang.org/std/char/constant.REPLACEMENT_CHARACTER.html
+    pub fn to_string_lossy(&self) -> String {
+        char::decode_utf16(self.as_units().iter().copied())
+            .map(|ch| ch.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    }
+}
+
+impl From<&str> for UniquePtr<CxxU16String> {
+    fn from(s: &str) -> Self {
+        let units: Vec<u16> = s.encode_utf16().collect();
+        CxxU16String::from_units(&units)
+    }
+}
+
+impl Display for CxxU16String {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.to_string_lossy(), f)
+    }
+}
+
+impl Debug for CxxU16String {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.to_string_lossy(), f)
+    }
+}
+
+impl PartialEq for CxxU16String {
+    fn eq(&self, other: &CxxU16String) -> bool {
+        self.as_units() == other.as_units()
+    }
+}
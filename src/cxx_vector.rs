@@ -0,0 +1,534 @@
+use crate::unique_ptr::UniquePtrTarget;
+use std::ffi::c_void;
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ops::Index;
+use std::ptr;
+use std::slice;
+
+/// Binding to C++ `std::vector<T, std::allocator<T>>`.
+///
+/// # Invariants
+///
+/// As an invariant of this API and the static analysis of the cxx::bridge
+/// macro, in Rust code we can never obtain a `Vector` by value. Instead in
+/// Rust code we will only ever look at a Vector through a reference or smart
+/// pointer, as in `&Vector<T>` or `UniquePtr<Vector<T>>`.
+#[repr(C)]
+pub struct Vector<T> {
+    _private: [*const c_void; 0],
+    _element: PhantomData<T>,
+}
+
+impl<T> Vector<T>
+where
+    T: VectorElement,
+{
+    /// Returns the number of elements in the vector.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::size][size].
+    ///
+    /// [size]: https://en.cppreference.com/w/cpp/container/vector/size
+    pub fn len(&self) -> usize {
+        T::__vector_size(self)
+    }
+
+    /// Returns true if the vector contains no elements.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::empty][empty].
+    ///
+    /// [empty]: https://en.cppreference.com/w/cpp/container/vector/empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to an element at the given position, or `None` if
+    /// out of bounds.
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        if pos < self.len() {
+            Some(unsafe { self.get_unchecked(pos) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to an element without doing bounds checking.
+    ///
+    /// This is generally not recommended, use with caution! Calling this
+    /// method with an out-of-bounds index is undefined behavior even if the
+    /// resulting reference is not used.
+    ///
+    /// Matches the behavior of C++
+    /// [std::vector\<T\>::operator\[\]][operator_at].
+    ///
+    /// [operator_at]: https://en.cppreference.com/w/cpp/container/vector/operator_at
+    pub unsafe fn get_unchecked(&self, pos: usize) -> &T {
+        &*T::__get_unchecked(self, pos)
+    }
+
+    /// Returns an iterator over the elements of the vector.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { v: self, index: 0 }
+    }
+
+    /// Removes the last element of the vector, if any.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::pop_back][pop_back]
+    /// except that calling this on an empty vector does nothing rather than
+    /// being undefined behavior.
+    ///
+    /// [pop_back]: https://en.cppreference.com/w/cpp/container/vector/pop_back
+    pub fn pop_back(&mut self) {
+        if !self.is_empty() {
+            unsafe { T::__pop_back(self) }
+        }
+    }
+
+    /// Removes all elements from the vector.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::clear][clear].
+    ///
+    /// [clear]: https://en.cppreference.com/w/cpp/container/vector/clear
+    pub fn clear(&mut self) {
+        T::__clear(self)
+    }
+
+    /// Ensures the vector has room for at least `new_cap` elements in total.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::reserve][reserve]. Note
+    /// that unlike `Vec::reserve` the argument is a total capacity, not an
+    /// additional one. Aborts if the allocation fails.
+    ///
+    /// [reserve]: https://en.cppreference.com/w/cpp/container/vector/reserve
+    pub fn reserve(&mut self, new_cap: usize) {
+        T::__reserve(self, new_cap)
+    }
+}
+
+impl<T> Vector<T>
+where
+    T: VectorSliceElement,
+{
+    /// Returns a view of the elements in the range `start..end`, without
+    /// copying them out of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start > end` or if `end` is greater than the vector's
+    /// length.
+    pub fn slice(&self, start: usize, end: usize) -> &[T] {
+        let len = self.len();
+        if start > end {
+            panic!("slice index starts at {} but ends at {}", start, end);
+        }
+        if end > len {
+            panic!(
+                "range end index {} out of range for Vector of length {}",
+                end, len
+            );
+        }
+        if start == end {
+            return &[];
+        }
+        // The elements of a std::vector are stored contiguously.
+        unsafe { slice::from_raw_parts(T::__get_unchecked(self, start), end - start) }
+    }
+}
+
+impl<T> Index<usize> for Vector<T>
+where
+    T: VectorElement,
+{
+    type Output = T;
+
+    fn index(&self, pos: usize) -> &T {
+        match self.get(pos) {
+            Some(element) => element,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                pos
+            ),
+        }
+    }
+}
+
+/// Iterator over elements of a `Vector` by shared reference.
+pub struct Iter<'a, T> {
+    v: &'a Vector<T>,
+    index: usize,
+}
+
+impl<'a, T> IntoIterator for &'a Vector<T>
+where
+    T: VectorElement,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: VectorElement,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.v.get(self.index)?;
+        self.index += 1;
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T>
+where
+    T: VectorElement,
+{
+    fn len(&self) -> usize {
+        self.v.len() - self.index
+    }
+}
+
+impl<'a, T> FusedIterator for Iter<'a, T> where T: VectorElement {}
+
+impl<T> Debug for Vector<T>
+where
+    T: VectorElement + Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_list().entries(self).finish()
+    }
+}
+
+// Methods are private; not intended to be implemented outside of cxxbridge
+// codebase.
+#[doc(hidden)]
+pub unsafe trait VectorElement: Sized {
+    #[doc(hidden)]
+    fn __vector_size(v: &Vector<Self>) -> usize;
+    #[doc(hidden)]
+    unsafe fn __get_unchecked(v: &Vector<Self>, pos: usize) -> *const Self;
+    #[doc(hidden)]
+    unsafe fn __pop_back(v: &mut Vector<Self>);
+    #[doc(hidden)]
+    fn __clear(v: &mut Vector<Self>);
+    #[doc(hidden)]
+    fn __reserve(v: &mut Vector<Self>, new_cap: usize);
+    #[doc(hidden)]
+    fn __unique_ptr_null() -> *mut c_void;
+    #[doc(hidden)]
+    unsafe fn __unique_ptr_raw(raw: *mut Vector<Self>) -> *mut c_void;
+    #[doc(hidden)]
+    unsafe fn __unique_ptr_get(repr: *mut c_void) -> *const Vector<Self>;
+    #[doc(hidden)]
+    unsafe fn __unique_ptr_release(repr: *mut c_void) -> *mut Vector<Self>;
+    #[doc(hidden)]
+    unsafe fn __unique_ptr_drop(repr: *mut c_void);
+}
+
+unsafe impl<T> UniquePtrTarget for Vector<T>
+where
+    T: VectorElement,
+{
+    fn __null() -> *mut c_void {
+        T::__unique_ptr_null()
+    }
+    fn __new(_: Self) -> *mut c_void {
+        // A Vector is never held by value in Rust; see the invariants above.
+        unreachable!()
+    }
+    unsafe fn __raw(raw: *mut Self) -> *mut c_void {
+        T::__unique_ptr_raw(raw)
+    }
+    unsafe fn __get(repr: *mut c_void) -> *const Self {
+        T::__unique_ptr_get(repr)
+    }
+    unsafe fn __release(repr: *mut c_void) -> *mut Self {
+        T::__unique_ptr_release(repr)
+    }
+    unsafe fn __drop(repr: *mut c_void) {
+        T::__unique_ptr_drop(repr)
+    }
+}
+
+// Element types that Rust lays out the same as C++, so that a run of them can be
+// viewed as a Rust slice. Opaque C++ types are zero-sized in Rust, which would
+// make every element of such a slice alias the first.
+#[doc(hidden)]
+pub unsafe trait VectorSliceElement: VectorElement {}
+
+macro_rules! impl_vector_element_for_primitive {
+    ($ty:ident) => {
+        unsafe impl VectorElement for $ty {
+            fn __vector_size(v: &Vector<$ty>) -> usize {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$std$vector$", stringify!($ty), "$size")]
+                        fn __vector_size(_: &Vector<$ty>) -> usize;
+                    }
+                }
+                unsafe { __vector_size(v) }
+            }
+            unsafe fn __get_unchecked(v: &Vector<$ty>, pos: usize) -> *const $ty {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$std$vector$", stringify!($ty), "$get_unchecked")]
+                        fn __get_unchecked(_: &Vector<$ty>, _: usize) -> *const $ty;
+                    }
+                }
+                __get_unchecked(v, pos)
+            }
+            unsafe fn __pop_back(v: &mut Vector<$ty>) {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$std$vector$", stringify!($ty), "$pop_back")]
+                        fn __pop_back(_: &mut Vector<$ty>);
+                    }
+                }
+                __pop_back(v)
+            }
+            fn __clear(v: &mut Vector<$ty>) {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$std$vector$", stringify!($ty), "$clear")]
+                        fn __clear(_: &mut Vector<$ty>);
+                    }
+                }
+                unsafe { __clear(v) }
+            }
+            fn __reserve(v: &mut Vector<$ty>, new_cap: usize) {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$std$vector$", stringify!($ty), "$reserve")]
+                        fn __reserve(_: &mut Vector<$ty>, new_cap: usize);
+                    }
+                }
+                unsafe { __reserve(v, new_cap) }
+            }
+            fn __unique_ptr_null() -> *mut c_void {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$unique_ptr$std$vector$", stringify!($ty), "$null")]
+                        fn __unique_ptr_null(this: *mut *mut c_void);
+                    }
+                }
+                let mut repr = ptr::null_mut::<c_void>();
+                unsafe { __unique_ptr_null(&mut repr) }
+                repr
+            }
+            unsafe fn __unique_ptr_raw(raw: *mut Vector<Self>) -> *mut c_void {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$unique_ptr$std$vector$", stringify!($ty), "$raw")]
+                        fn __unique_ptr_raw(this: *mut *mut c_void, raw: *mut Vector<$ty>);
+                    }
+                }
+                let mut repr = ptr::null_mut::<c_void>();
+                __unique_ptr_raw(&mut repr, raw);
+                repr
+            }
+            unsafe fn __unique_ptr_get(repr: *mut c_void) -> *const Vector<Self> {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$unique_ptr$std$vector$", stringify!($ty), "$get")]
+                        fn __unique_ptr_get(this: *const *mut c_void) -> *const Vector<$ty>;
+                    }
+                }
+                __unique_ptr_get(&repr)
+            }
+            unsafe fn __unique_ptr_release(mut repr: *mut c_void) -> *mut Vector<Self> {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$unique_ptr$std$vector$", stringify!($ty), "$release")]
+                        fn __unique_ptr_release(this: *mut *mut c_void) -> *mut Vector<$ty>;
+                    }
+                }
+                __unique_ptr_release(&mut repr)
+            }
+            unsafe fn __unique_ptr_drop(mut repr: *mut c_void) {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$unique_ptr$std$vector$", stringify!($ty), "$drop")]
+                        fn __unique_ptr_drop(this: *mut *mut c_void);
+                    }
+                }
+                __unique_ptr_drop(&mut repr);
+            }
+        }
+
+        unsafe impl VectorSliceElement for $ty {}
+    };
+}
+
+impl_vector_element_for_primitive!(u8);
+impl_vector_element_for_primitive!(u16);
+impl_vector_element_for_primitive!(u32);
+impl_vector_element_for_primitive!(u64);
+impl_vector_element_for_primitive!(usize);
+impl_vector_element_for_primitive!(i8);
+impl_vector_element_for_primitive!(i16);
+impl_vector_element_for_primitive!(i32);
+impl_vector_element_for_primitive!(i64);
+impl_vector_element_for_primitive!(f32);
+impl_vector_element_for_primitive!(f64);
+
+// A std::vector<bool> is bit-packed, so its elements have no address that a
+// &bool could point to. Vector<bool> gets its own accessors that copy each
+// element out by value instead of going through VectorElement.
+extern "C" {
+    #[link_name = "cxxbridge02$std$vector$bool$size"]
+    fn vector_bool_size(_: &Vector<bool>) -> usize;
+    #[link_name = "cxxbridge02$std$vector$bool$get_unchecked"]
+    fn vector_bool_get_unchecked(_: &Vector<bool>, pos: usize) -> bool;
+    #[link_name = "cxxbridge02$std$vector$bool$push_back"]
+    fn vector_bool_push_back(_: &mut Vector<bool>, value: bool);
+    #[link_name = "cxxbridge02$std$vector$bool$pop_back"]
+    fn vector_bool_pop_back(_: &mut Vector<bool>);
+    #[link_name = "cxxbridge02$std$vector$bool$clear"]
+    fn vector_bool_clear(_: &mut Vector<bool>);
+    #[link_name = "cxxbridge02$std$vector$bool$reserve"]
+    fn vector_bool_reserve(_: &mut Vector<bool>, new_cap: usize);
+    #[link_name = "cxxbridge02$unique_ptr$std$vector$bool$null"]
+    fn unique_ptr_vector_bool_null(this: *mut *mut c_void);
+    #[link_name = "cxxbridge02$unique_ptr$std$vector$bool$raw"]
+    fn unique_ptr_vector_bool_raw(this: *mut *mut c_void, raw: *mut Vector<bool>);
+    #[link_name = "cxxbridge02$unique_ptr$std$vector$bool$get"]
+    fn unique_ptr_vector_bool_get(this: *const *mut c_void) -> *const Vector<bool>;
+    #[link_name = "cxxbridge02$unique_ptr$std$vector$bool$release"]
+    fn unique_ptr_vector_bool_release(this: *mut *mut c_void) -> *mut Vector<bool>;
+    #[link_name = "cxxbridge02$unique_ptr$std$vector$bool$drop"]
+    fn unique_ptr_vector_bool_drop(this: *mut *mut c_void);
+}
+
+impl Vector<bool> {
+    /// Returns the number of elements in the vector.
+    pub fn len(&self) -> usize {
+        unsafe { vector_bool_size(self) }
+    }
+
+    /// Returns true if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a copy of the element at the given position, or `None` if out
+    /// of bounds.
+    pub fn get(&self, pos: usize) -> Option<bool> {
+        if pos < self.len() {
+            Some(unsafe { vector_bool_get_unchecked(self, pos) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over copies of the elements of the vector.
+    pub fn iter(&self) -> BoolIter<'_> {
+        BoolIter { v: self, index: 0 }
+    }
+
+    /// Appends an element to the back of the vector.
+    ///
+    /// Matches the behavior of C++ [std::vector\<T\>::push_back][push_back].
+    ///
+    /// [push_back]: https://en.cppreference.com/w/cpp/container/vector/push_back
+    pub fn push_back(&mut self, value: bool) {
+        unsafe { vector_bool_push_back(self, value) }
+    }
+
+    /// Removes the last element of the vector, if any.
+    pub fn pop_back(&mut self) {
+        if !self.is_empty() {
+            unsafe { vector_bool_pop_back(self) }
+        }
+    }
+
+    /// Removes all elements from the vector.
+    pub fn clear(&mut self) {
+        unsafe { vector_bool_clear(self) }
+    }
+
+    /// Ensures the vector has room for at least `new_cap` elements in total.
+    pub fn reserve(&mut self, new_cap: usize) {
+        unsafe { vector_bool_reserve(self, new_cap) }
+    }
+}
+
+/// Iterator over copies of the elements of a `Vector<bool>`.
+pub struct BoolIter<'a> {
+    v: &'a Vector<bool>,
+    index: usize,
+}
+
+impl<'a> IntoIterator for &'a Vector<bool> {
+    type Item = bool;
+    type IntoIter = BoolIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> Iterator for BoolIter<'a> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.v.get(self.index)?;
+        self.index += 1;
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for BoolIter<'a> {
+    fn len(&self) -> usize {
+        self.v.len() - self.index
+    }
+}
+
+impl<'a> FusedIterator for BoolIter<'a> {}
+
+impl Debug for Vector<bool> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_list().entries(self).finish()
+    }
+}
+
+unsafe impl UniquePtrTarget for Vector<bool> {
+    fn __null() -> *mut c_void {
+        let mut repr = ptr::null_mut::<c_void>();
+        unsafe { unique_ptr_vector_bool_null(&mut repr) }
+        repr
+    }
+    fn __new(_: Self) -> *mut c_void {
+        // A Vector is never held by value in Rust; see the invariants above.
+        unreachable!()
+    }
+    unsafe fn __raw(raw: *mut Self) -> *mut c_void {
+        let mut repr = ptr::null_mut::<c_void>();
+        unique_ptr_vector_bool_raw(&mut repr, raw);
+        repr
+    }
+    unsafe fn __get(repr: *mut c_void) -> *const Self {
+        unique_ptr_vector_bool_get(&repr)
+    }
+    unsafe fn __release(mut repr: *mut c_void) -> *mut Self {
+        unique_ptr_vector_bool_release(&mut repr)
+    }
+    unsafe fn __drop(mut repr: *mut c_void) {
+        unique_ptr_vector_bool_drop(&mut repr);
+    }
+}
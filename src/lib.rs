@@ -134,7 +134,10 @@
 //!
 //! - **Shared structs** &mdash; their fields are made visible to both
 //!   languages. The definition written within cxx::bridge is the single source
-//!   of truth.
+//!   of truth. Fieldless enums are shared the same way, becoming a C++ `enum
+//!   class` with identical discriminants. An integer received from C++
+//!   converts to the Rust enum through `TryFrom`, which rejects undeclared
+//!   discriminants.
 //!
 //! - **Opaque types** &mdash; their fields are secret from the other language.
 //!   These cannot be passed across the FFI by value but only behind an
@@ -252,6 +255,10 @@
 //! $ cxxbridge src/main.rs > path/to/mybridge.cc
 //! ```
 //!
+//! A Cargo build script that hands the C++ compilation to some other build
+//! system, such as CMake, can instead call [`generate_bridge`] to write the
+//! same two files into a directory of its choosing.
+//!
 //! <br>
 //!
 //! # Safety
@@ -286,7 +293,9 @@
 //!   and can insert the necessary zero-cost workaround transparently where
 //!   needed, so go ahead and pass your structs by value without worries. This
 //!   is made possible by owning both sides of the boundary rather than just
-//!   one.
+//!   one. When a large struct only needs to be read, take it as `&T` instead:
+//!   that is passed as a bare pointer and arrives on the C++ side as
+//!   `const T &`, so the struct is never copied regardless of its size.
 //!
 //! - Template instantiations: for example in order to expose a UniquePtr\<T\>
 //!   type in Rust backed by a real C++ unique\_ptr, we have a way of using a
@@ -306,12 +315,34 @@
 //! <table>
 //! <tr><th>name in Rust</th><th>name in C++</th><th>restrictions</th></tr>
 //! <tr><td>String</td><td>rust::String</td><td></td></tr>
+//! <tr><td>char</td><td>char32_t</td><td><sup><i>by value only; an invalid scalar value from C++ is an error or panic</i></sup></td></tr>
 //! <tr><td>&amp;str</td><td>rust::Str</td><td></td></tr>
+//! <tr><td>&amp;[T]</td><td>rust::Slice&lt;const T&gt;</td><td><sup><i>primitive, enum, or shared struct elements without owned fields</i></sup></td></tr>
+//! <tr><td>&amp;mut [T]</td><td>rust::Slice&lt;T&gt;</td><td><sup><i>same elements as &amp;[T]; must not alias another live slice or reference</i></sup></td></tr>
+//! <tr><td>[T; N]</td><td>T[N]</td><td><sup><i>struct fields only; trivially copyable elements</i></sup></td></tr>
+//! <tr><td>Array&lt;T, N&gt;</td><td>std::array&lt;T, N&gt;</td><td><sup><i>[T; N] in Rust; not in struct fields; N must be an integer literal</i></sup></td></tr>
+//! <tr><td>Vec&lt;T&gt;</td><td>rust::Vec&lt;T&gt;</td><td><sup><i>u8, Vec&lt;u8&gt;, String, or shared struct elements; not in struct fields</i></sup></td></tr>
 //! <tr><td><a href="https://docs.rs/cxx/0.2/cxx/struct.CxxString.html">CxxString</a></td><td>std::string</td><td><sup><i>cannot be passed by value</i></sup></td></tr>
+//! <tr><td><a href="https://docs.rs/cxx/0.2/cxx/struct.CxxU16String.html">CxxU16String</a></td><td>std::u16string</td><td><sup><i>cannot be passed by value</i></sup></td></tr>
 //! <tr><td>Box&lt;T&gt;</td><td>rust::Box&lt;T&gt;</td><td><sup><i>cannot hold opaque C++ type</i></sup></td></tr>
+//! <tr><td>Box&lt;[T]&gt;</td><td>rust::Box&lt;T[]&gt;</td><td><sup><i>return type of Rust functions only</i></sup></td></tr>
+//! <tr><td>Arc&lt;T&gt;</td><td>rust::Arc&lt;T&gt;</td><td><sup><i>cannot hold opaque C++ type; not by reference or in struct fields</i></sup></td></tr>
 //! <tr><td><a href="https://docs.rs/cxx/0.2/cxx/struct.UniquePtr.html">UniquePtr&lt;T&gt;</a></td><td>std::unique_ptr&lt;T&gt;</td><td><sup><i>cannot hold opaque Rust type</i></sup></td></tr>
+//! <tr><td><a href="https://docs.rs/cxx/0.2/cxx/struct.SharedPtr.html">SharedPtr&lt;T&gt;</a></td><td>std::shared_ptr&lt;T&gt;</td><td><sup><i>cannot hold opaque Rust type</i></sup></td></tr>
+//! <tr><td><a href="https://docs.rs/cxx/0.2/cxx/struct.UniqueArray.html">UniqueArray&lt;T&gt;</a></td><td>std::unique_ptr&lt;T[]&gt;</td><td><sup><i>primitive elements; length is passed separately</i></sup></td></tr>
+//! <tr><td>Option&lt;UniquePtr&lt;T&gt;&gt;</td><td>std::unique_ptr&lt;T&gt;</td><td><sup><i>return type only; null maps to None</i></sup></td></tr>
+//! <tr><td>Option&lt;&amp;T&gt;</td><td>const T*</td><td><sup><i>null maps to None</i></sup></td></tr>
+//! <tr><td>Pin&lt;&amp;mut T&gt;</td><td>T&amp;</td><td><sup><i>opaque C++ type, CxxString, or CxxU16String; required for mutable access to an opaque C++ type, including self: Pin&lt;&amp;mut T&gt; on C++ methods; get one from UniquePtr::pin_mut</i></sup></td></tr>
+//! <tr><td>Option&lt;T&gt;</td><td>std::optional&lt;T&gt;</td><td><sup><i>T primitive; argument of a C++ function only; requires C++17</i></sup></td></tr>
+//! <tr><td><a href="https://docs.rs/cxx/0.2/cxx/struct.Borrowed.html">Borrowed&lt;T&gt;</a></td><td>const T *</td><td><sup><i>non-owning, may be null; never destroyed from Rust</i></sup></td></tr>
+//! <tr><td><a href="https://docs.rs/cxx/0.2/cxx/struct.Vector.html">Vector&lt;T&gt;</a></td><td>std::vector&lt;T&gt;</td><td><sup><i>behind a reference or UniquePtr only; primitive, shared struct, or opaque C++ elements; Vector&lt;bool&gt; is accessed by value</i></sup></td></tr>
+//! <tr><td><a href="https://docs.rs/cxx/0.2/cxx/struct.CxxMap.html">CxxMap&lt;K, V&gt;</a></td><td>std::map&lt;K, V&gt;</td><td><sup><i>behind a reference only; integer or CxxString keys; primitive, CxxString, or shared struct values</i></sup></td></tr>
+//! <tr><td><a href="https://docs.rs/cxx/0.2/cxx/struct.CxxSet.html">CxxSet&lt;T&gt;</a></td><td>std::set&lt;T&gt;</td><td><sup><i>behind a reference only; primitive or CxxString elements</i></sup></td></tr>
+//! <tr><td>(A, B, ...)</td><td>std::tuple&lt;A, B, ...&gt;</td><td><sup><i>extern "C" return type only; 2 or 3 primitive or shared struct elements</i></sup></td></tr>
 //! <tr><td>fn(T, U) -&gt; V</td><td>rust::Fn&lt;V(T, U)&gt;</td><td><sup><i>only passing from Rust to C++ is implemented so far</i></sup></td></tr>
+//! <tr><td>fn(T, U) -&gt; Result&lt;V&gt;</td><td>rust::TryFn&lt;V(T, U)&gt;</td><td><sup><i>error type is Box&lt;dyn Error&gt;; an Err is thrown to C++ as rust::Error</i></sup></td></tr>
 //! <tr><td>Result&lt;T&gt;</td><td>error &lt;=&gt; exception</td><td><sup><i>allowed as return type only</i></sup></td></tr>
+//! <tr><td>Result&lt;T, E&gt;</td><td>error &lt;=&gt; exception</td><td><sup><i>extern "C" return type only; E is a bridge enum whose variants are tagged #[cxx_exception(...)]</i></sup></td></tr>
 //! </table>
 //!
 //! The C++ API of the `rust` namespace is defined by the *include/cxx.h* file
@@ -324,15 +355,12 @@
 //!
 //! <table>
 //! <tr><th>name in Rust</th><th>name in C++</th></tr>
-//! <tr><td>&amp;[T]</td><td><sup><i>tbd</i></sup></td></tr>
 //! <tr><td>Vec&lt;T&gt;</td><td><sup><i>tbd</i></sup></td></tr>
 //! <tr><td>BTreeMap&lt;K, V&gt;</td><td><sup><i>tbd</i></sup></td></tr>
 //! <tr><td>HashMap&lt;K, V&gt;</td><td><sup><i>tbd</i></sup></td></tr>
 //! <tr><td>Arc&lt;T&gt;</td><td><sup><i>tbd</i></sup></td></tr>
-//! <tr><td><sup><i>tbd</i></sup></td><td>std::vector&lt;T&gt;</td></tr>
 //! <tr><td><sup><i>tbd</i></sup></td><td>std::map&lt;K, V&gt;</td></tr>
 //! <tr><td><sup><i>tbd</i></sup></td><td>std::unordered_map&lt;K, V&gt;</td></tr>
-//! <tr><td><sup><i>tbd</i></sup></td><td>std::shared_ptr&lt;T&gt;</td></tr>
 //! </table>
 //!
 //! [https://github.com/dtolnay/cxx]: https://github.com/dtolnay/cxx
@@ -350,6 +378,7 @@
     clippy::or_fun_call,
     clippy::ptr_arg,
     clippy::toplevel_ref_arg,
+    clippy::unnecessary_map_or,
     clippy::useless_let_if_seq
 )]
 
@@ -357,34 +386,65 @@ extern crate link_cplusplus;
 
 #[macro_use]
 mod assert;
+#[macro_use]
+mod macros;
 
+mod borrowed;
+mod cxx_map;
+mod cxx_set;
 mod cxx_string;
+mod cxx_u16string;
+mod cxx_vector;
 mod error;
 mod exception;
 mod function;
 mod gen;
 mod opaque;
 mod paths;
+mod placement;
+mod reentrancy;
 mod result;
+mod rust_char;
+mod rust_slice;
 mod rust_str;
 mod rust_string;
+mod rust_vec;
+mod shared_ptr;
 mod syntax;
+mod unique_array;
 mod unique_ptr;
 mod unwind;
 
+pub use crate::borrowed::Borrowed;
+pub use crate::cxx_map::CxxMap;
+pub use crate::cxx_set::CxxSet;
 pub use crate::cxx_string::CxxString;
+pub use crate::cxx_u16string::CxxU16String;
+pub use crate::cxx_vector::Vector;
 pub use crate::exception::Exception;
+pub use crate::placement::Placement;
+pub use crate::shared_ptr::SharedPtr;
+pub use crate::unique_array::UniqueArray;
 pub use crate::unique_ptr::UniquePtr;
 pub use cxxbridge_macro::bridge;
 
 // Not public API.
 #[doc(hidden)]
 pub mod private {
+    pub use crate::cxx_map::MapValue;
+    pub use crate::cxx_set::SetElement;
+    pub use crate::cxx_vector::{VectorElement, VectorSliceElement};
     pub use crate::function::FatFunction;
     pub use crate::opaque::Opaque;
-    pub use crate::result::{r#try, Result};
+    pub use crate::reentrancy::ReentrancyGuard;
+    pub use crate::result::{r#try, try_typed, Result};
+    pub use crate::rust_char::{char_from_u32, expect_char};
+    pub use crate::rust_slice::RustSlice;
     pub use crate::rust_str::RustStr;
     pub use crate::rust_string::RustString;
+    pub use crate::rust_vec::RustVec;
+    pub use crate::shared_ptr::SharedPtrTarget;
+    pub use crate::unique_array::ArrayElement;
     pub use crate::unique_ptr::UniquePtrTarget;
     pub use crate::unwind::catch_unwind;
 }
@@ -394,9 +454,50 @@ use crate::gen::Opt;
 use anyhow::anyhow;
 use std::fs;
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process;
 
+/// Generate the C++ header and source for a `#[cxx::bridge]` module and write
+/// them into `out_dir`.
+///
+/// This is the code generator behind [`Build`] without the [`cc`] driver, for
+/// build systems like CMake that compile the C++ themselves. The files are
+/// named after the Rust source file, as `<file>.rs.h` and `<file>.rs.cc`, and
+/// their paths are returned in that order. The generated code includes
+/// `"rust/cxx.h"`, which is printed by `cxxbridge --header`.
+///
+/// A bridge that fails to parse or typecheck is reported as an error of kind
+/// [`InvalidData`] whose message has one `file:line:column: message` line for
+/// each problem.
+///
+/// [`InvalidData`]: std::io::ErrorKind::InvalidData
+///
+/// ```no_run
+/// use std::path::Path;
+///
+/// let (header, source) = cxx::generate_bridge(
+///     Path::new("src/main.rs"),
+///     Path::new("build/generated"),
+/// )?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn generate_bridge(rust_source_file: &Path, out_dir: &Path) -> io::Result<(PathBuf, PathBuf)> {
+    let (header, bridge) = gen::try_generate_header_and_bridge(rust_source_file, Opt::default())?;
+    let file_name = match rust_source_file.file_name() {
+        Some(file_name) => file_name.to_string_lossy(),
+        None => {
+            let msg = format!("not a file: {}", rust_source_file.display());
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        }
+    };
+    fs::create_dir_all(out_dir)?;
+    let header_path = out_dir.join(format!("{}.h", file_name));
+    fs::write(&header_path, header)?;
+    let bridge_path = out_dir.join(format!("{}.cc", file_name));
+    fs::write(&bridge_path, bridge)?;
+    Ok((header_path, bridge_path))
+}
+
 /// The CXX code generator for constructing and compiling C++ code.
 ///
 /// This is intended to be used from Cargo build scripts to execute CXX's
@@ -446,13 +547,86 @@ use std::process;
 /// ```
 #[must_use]
 pub struct Build {
-    _private: (),
+    no_exceptions: bool,
+    signatures: bool,
+    layout_constants: bool,
+    catch_unknown_exceptions: bool,
+    cxx_usize: Option<String>,
+    cxx_isize: Option<String>,
 }
 
 impl Build {
     /// Begin with a [`cc::Build`] in its default configuration.
     pub fn new() -> Self {
-        Build { _private: () }
+        Build {
+            no_exceptions: false,
+            signatures: false,
+            layout_constants: false,
+            catch_unknown_exceptions: false,
+            cxx_usize: None,
+            cxx_isize: None,
+        }
+    }
+
+    /// Generate C++ code that compiles with exceptions disabled, as with
+    /// `-fno-exceptions`. Bridges containing any function that returns
+    /// `Result` are rejected in this mode, since propagating those errors is
+    /// what requires exceptions.
+    ///
+    /// This does not pass `-fno-exceptions` to the compiler; add it to the
+    /// returned [`cc::Build`] as usual.
+    pub fn no_exceptions(&mut self) -> &mut Self {
+        self.no_exceptions = true;
+        self
+    }
+
+    /// Also write a JSON description of the bridged functions and types next
+    /// to the generated C++ code, as `$OUT_DIR/.../<file>.rs.json`. The same
+    /// output comes from `cxxbridge <file>.rs --signatures`; see the README
+    /// for its schema.
+    pub fn signatures(&mut self) -> &mut Self {
+        self.signatures = true;
+        self
+    }
+
+    /// Emit `constexpr size_t T_size`, `T_align` and per-field
+    /// `T_field_offset` constants after each shared struct in the generated
+    /// header, for C++ code that needs the struct layout at compile time.
+    /// Field offsets are left out for structs holding a `UniquePtr` or
+    /// `SharedPtr`, which need not be standard-layout.
+    pub fn layout_constants(&mut self) -> &mut Self {
+        self.layout_constants = true;
+        self
+    }
+
+    /// Also catch C++ exceptions that do not derive from `std::exception`,
+    /// such as a thrown `int`, in functions returning `Result`. These reach
+    /// Rust as an error whose `what()` is "unknown C++ exception" rather than
+    /// terminating the program.
+    ///
+    /// This applies only to the default `rust::behavior::trycatch`; a
+    /// project that defines its own is unaffected.
+    pub fn catch_unknown_exceptions(&mut self) -> &mut Self {
+        self.catch_unknown_exceptions = true;
+        self
+    }
+
+    /// Spell `usize` as the given C++ type, such as `uintptr_t`, instead of
+    /// `size_t` in the generated code. Same as `cxxbridge --cxx-usize`. The
+    /// generated code asserts that the type is unsigned, pointer-sized and
+    /// pointer-aligned.
+    pub fn cxx_usize(&mut self, cxx_type: &str) -> &mut Self {
+        self.cxx_usize = Some(cxx_type.to_owned());
+        self
+    }
+
+    /// Spell `isize` as the given C++ type, such as `intptr_t`, instead of
+    /// `ssize_t` in the generated code. Same as `cxxbridge --cxx-isize`. The
+    /// generated code asserts that the type is signed, pointer-sized and
+    /// pointer-aligned.
+    pub fn cxx_isize(&mut self, cxx_type: &str) -> &mut Self {
+        self.cxx_isize = Some(cxx_type.to_owned());
+        self
     }
 
     /// This returns a [`cc::Build`] on which you should continue to set up
@@ -462,7 +636,15 @@ impl Build {
     /// [`compile`]: https://docs.rs/cc/1.0.49/cc/struct.Build.html#method.compile
     #[must_use]
     pub fn bridge(&self, rust_source_file: impl AsRef<Path>) -> cc::Build {
-        match try_generate_bridge(rust_source_file.as_ref()) {
+        let opt = Opt {
+            no_exceptions: self.no_exceptions,
+            layout_constants: self.layout_constants,
+            catch_unknown_exceptions: self.catch_unknown_exceptions,
+            cxx_usize: self.cxx_usize.clone(),
+            cxx_isize: self.cxx_isize.clone(),
+            ..Opt::default()
+        };
+        match try_generate_bridge(rust_source_file.as_ref(), opt, self.signatures) {
             Ok(build) => build,
             Err(err) => {
                 let _ = writeln!(io::stderr(), "\n\ncxxbridge error: {:?}\n\n", anyhow!(err));
@@ -472,14 +654,21 @@ impl Build {
     }
 }
 
-fn try_generate_bridge(rust_source_file: &Path) -> Result<cc::Build> {
-    let header = gen::do_generate_header(rust_source_file, Opt::default());
+fn try_generate_bridge(rust_source_file: &Path, opt: Opt, signatures: bool) -> Result<cc::Build> {
+    if signatures {
+        let json = gen::do_generate_signatures(rust_source_file, opt.clone());
+        let json_path = paths::out_with_extension(rust_source_file, ".json")?;
+        fs::create_dir_all(json_path.parent().unwrap())?;
+        fs::write(&json_path, json)?;
+    }
+
+    let header = gen::do_generate_header(rust_source_file, opt.clone());
     let header_path = paths::out_with_extension(rust_source_file, ".h")?;
     fs::create_dir_all(header_path.parent().unwrap())?;
     fs::write(&header_path, header)?;
     paths::symlink_header(&header_path, rust_source_file);
 
-    let bridge = gen::do_generate_bridge(rust_source_file, Opt::default());
+    let bridge = gen::do_generate_bridge(rust_source_file, opt);
     let bridge_path = paths::out_with_extension(rust_source_file, ".cc")?;
     fs::write(&bridge_path, bridge)?;
     let mut build = paths::cc_build();
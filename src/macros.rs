@@ -0,0 +1,7 @@
+// Allows a concat!'d link_name, which attributes do not accept directly.
+macro_rules! attr {
+    (#[$name:ident = $value:expr] $($rest:tt)*) => {
+        #[$name = $value]
+        $($rest)*
+    };
+}
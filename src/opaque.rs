@@ -1,3 +1,4 @@
+use std::marker::{PhantomData, PhantomPinned};
 use std::mem;
 
 // . size = 0
@@ -5,9 +6,11 @@ use std::mem;
 // . ffi-safe
 // . !Send
 // . !Sync
+// . !Unpin
 #[repr(C, packed)]
 pub struct Opaque {
     _private: [*const u8; 0],
+    _pinned: PhantomData<PhantomPinned>,
 }
 
 fn _assert() {
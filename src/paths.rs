@@ -42,7 +42,8 @@ fn try_symlink_header(path: &Path, original: &Path) -> Result<()> {
 
     let mut file_name = dst.file_name().unwrap().to_os_string();
     file_name.push(".h");
-    let dst2 = dst.with_file_name(file_name);
+    let ref dst2 = dst.with_file_name(file_name);
+    let _ = fs::remove_file(dst2);
     symlink(path, dst2)?;
 
     Ok(())
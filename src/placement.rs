@@ -0,0 +1,39 @@
+use std::alloc::Layout;
+
+/// Opaque C++ types that can be constructed in storage owned by Rust.
+///
+/// Implemented by the `#[cxx::bridge]` macro for extern C++ types annotated
+/// `#[cxx_placement]`. The C++ side of these is a placement new of the type's
+/// default constructor and an explicit call of its destructor, which lets the
+/// object live in an arena or other Rust-managed buffer rather than on the C++
+/// heap.
+///
+/// ```ignore
+/// let layout = ffi::Component::layout();
+/// let ptr = unsafe { std::alloc::alloc(layout) } as *mut ffi::Component;
+/// unsafe { ffi::Component::construct_at(ptr) };
+/// // ...
+/// unsafe { ffi::Component::destroy_at(ptr) };
+/// unsafe { std::alloc::dealloc(ptr as *mut u8, layout) };
+/// ```
+pub unsafe trait Placement {
+    /// The `sizeof` and `alignof` of the C++ type.
+    fn layout() -> Layout;
+
+    /// Default-constructs the C++ object at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for writes of [`layout()`][Placement::layout] and
+    /// must not hold a live object.
+    unsafe fn construct_at(ptr: *mut Self);
+
+    /// Runs the C++ destructor of the object at `ptr`, leaving the storage
+    /// uninitialized.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to an object previously constructed by
+    /// [`construct_at`][Placement::construct_at] and not yet destroyed.
+    unsafe fn destroy_at(ptr: *mut Self);
+}
@@ -0,0 +1,27 @@
+use std::cell::Cell;
+use std::thread::LocalKey;
+
+// Held for the duration of a call into a #[cxx_non_reentrant] function. The
+// flag is per function and per thread, so only a call that reaches the same
+// function again before returning, typically through a C++ callback, trips it.
+pub struct ReentrancyGuard {
+    entered: &'static LocalKey<Cell<bool>>,
+}
+
+impl ReentrancyGuard {
+    pub fn enter(entered: &'static LocalKey<Cell<bool>>, label: &'static str) -> Self {
+        if entered.with(|entered| entered.replace(true)) {
+            panic!(
+                "reentrant call to {}, which is declared #[cxx_non_reentrant]",
+                label,
+            );
+        }
+        ReentrancyGuard { entered }
+    }
+}
+
+impl Drop for ReentrancyGuard {
+    fn drop(&mut self) {
+        self.entered.with(|entered| entered.set(false));
+    }
+}
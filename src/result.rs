@@ -25,6 +25,22 @@ where
     }
 }
 
+// For a Result whose error type is a shared struct, the error is moved into the
+// caller's out-param instead of being formatted, and the return value says
+// which of the two out-params was written.
+pub unsafe fn try_typed<T, E>(ret: *mut T, err: *mut E, result: StdResult<T, E>) -> bool {
+    match result {
+        Ok(ok) => {
+            ptr::write(ret, ok);
+            false
+        }
+        Err(e) => {
+            ptr::write(err, e);
+            true
+        }
+    }
+}
+
 unsafe fn to_c_error(msg: String) -> Result {
     let mut msg = msg;
     msg.as_mut_vec().push(b'\0');
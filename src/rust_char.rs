@@ -0,0 +1,20 @@
+use crate::exception::Exception;
+use std::char;
+
+// C++ has no type restricted to Unicode scalar values, so every char32_t that
+// crosses into Rust is checked before it is allowed to become a char.
+pub fn char_from_u32(value: u32) -> Result<char, Exception> {
+    char::from_u32(value).ok_or_else(|| {
+        let what = format!("invalid Unicode scalar value 0x{:X} from C++", value);
+        Exception {
+            what: what.into_boxed_str(),
+        }
+    })
+}
+
+pub fn expect_char(value: u32) -> char {
+    match char_from_u32(value) {
+        Ok(ch) => ch,
+        Err(err) => panic!("{}", err),
+    }
+}
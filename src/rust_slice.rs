@@ -0,0 +1,74 @@
+use std::mem;
+use std::ptr::{self, NonNull};
+use std::slice;
+
+// Not necessarily ABI compatible with &[T] or &mut [T]. Codegen performs the
+// translation.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct RustSlice {
+    pub(crate) ptr: NonNull<()>,
+    pub(crate) len: usize,
+}
+
+impl RustSlice {
+    pub fn from_ref<T>(s: &[T]) -> Self {
+        RustSlice {
+            ptr: NonNull::from(s).cast::<()>(),
+            len: s.len(),
+        }
+    }
+
+    pub fn from_mut<T>(s: &mut [T]) -> Self {
+        let len = s.len();
+        RustSlice {
+            ptr: NonNull::from(s).cast::<()>(),
+            len,
+        }
+    }
+
+    pub fn from_box<T>(s: Box<[T]>) -> Self {
+        let len = s.len();
+        RustSlice {
+            ptr: NonNull::from(Box::leak(s)).cast::<()>(),
+            len,
+        }
+    }
+
+    pub unsafe fn into_box<T>(self) -> Box<[T]> {
+        let ptr = self.ptr.as_ptr().cast::<T>();
+        Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, self.len))
+    }
+
+    pub unsafe fn as_slice<'a, T>(self) -> &'a [T] {
+        slice::from_raw_parts(self.ptr.as_ptr().cast::<T>(), self.len)
+    }
+
+    pub unsafe fn as_mut_slice<'a, T>(self) -> &'a mut [T] {
+        slice::from_raw_parts_mut(self.ptr.as_ptr().cast::<T>(), self.len)
+    }
+}
+
+const_assert!(mem::size_of::<Option<RustSlice>>() == mem::size_of::<RustSlice>());
+
+macro_rules! box_slice_drop_for_primitive {
+    ($ty:ident, $link_name:literal) => {
+        const _: () = {
+            #[export_name = $link_name]
+            unsafe extern "C" fn box_slice_drop(repr: RustSlice) {
+                mem::drop(repr.into_box::<$ty>());
+            }
+        };
+    };
+}
+
+box_slice_drop_for_primitive!(u8, "cxxbridge02$box_slice$u8$drop");
+box_slice_drop_for_primitive!(u16, "cxxbridge02$box_slice$u16$drop");
+box_slice_drop_for_primitive!(u32, "cxxbridge02$box_slice$u32$drop");
+box_slice_drop_for_primitive!(u64, "cxxbridge02$box_slice$u64$drop");
+box_slice_drop_for_primitive!(i8, "cxxbridge02$box_slice$i8$drop");
+box_slice_drop_for_primitive!(i16, "cxxbridge02$box_slice$i16$drop");
+box_slice_drop_for_primitive!(i32, "cxxbridge02$box_slice$i32$drop");
+box_slice_drop_for_primitive!(i64, "cxxbridge02$box_slice$i64$drop");
+box_slice_drop_for_primitive!(f32, "cxxbridge02$box_slice$f32$drop");
+box_slice_drop_for_primitive!(f64, "cxxbridge02$box_slice$f64$drop");
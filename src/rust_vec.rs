@@ -0,0 +1,163 @@
+use std::mem;
+use std::ptr;
+
+// Bridge signatures only accept Vec<T> with a single type argument, i.e.
+// Vec<T, Global>, so the reserve shim below may always grow through the global
+// allocator. A Vec borrowed from Rust is never dropped or freed from the C++
+// side; only ones C++ constructed itself through $new are.
+#[repr(C)]
+pub struct RustVec<T> {
+    repr: Vec<T>,
+}
+
+impl<T> RustVec<T> {
+    pub fn from_ref(v: &Vec<T>) -> &Self {
+        unsafe { &*(v as *const Vec<T> as *const RustVec<T>) }
+    }
+
+    pub fn from_mut(v: &mut Vec<T>) -> &mut Self {
+        unsafe { &mut *(v as *mut Vec<T> as *mut RustVec<T>) }
+    }
+
+    pub fn as_vec(&self) -> &Vec<T> {
+        &self.repr
+    }
+
+    pub fn as_mut_vec(&mut self) -> &mut Vec<T> {
+        &mut self.repr
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.repr
+    }
+}
+
+impl<T> From<Vec<T>> for RustVec<T> {
+    fn from(repr: Vec<T>) -> Self {
+        RustVec { repr }
+    }
+}
+
+#[export_name = "cxxbridge02$rust_vec$u8$len"]
+unsafe extern "C" fn rust_vec_u8_len(this: &Vec<u8>) -> usize {
+    this.len()
+}
+
+#[export_name = "cxxbridge02$rust_vec$u8$capacity"]
+unsafe extern "C" fn rust_vec_u8_capacity(this: &Vec<u8>) -> usize {
+    this.capacity()
+}
+
+#[export_name = "cxxbridge02$rust_vec$u8$data"]
+unsafe extern "C" fn rust_vec_u8_data(this: &Vec<u8>) -> *const u8 {
+    this.as_ptr()
+}
+
+// Grows geometrically like Vec::reserve, so that pushing one element at a time
+// from C++ stays amortized O(1).
+#[export_name = "cxxbridge02$rust_vec$u8$reserve_total"]
+unsafe extern "C" fn rust_vec_u8_reserve_total(this: &mut Vec<u8>, cap: usize) {
+    if cap > this.capacity() {
+        this.reserve(cap - this.len());
+    }
+}
+
+#[export_name = "cxxbridge02$rust_vec$u8$set_len"]
+unsafe extern "C" fn rust_vec_u8_set_len(this: &mut Vec<u8>, len: usize) {
+    this.set_len(len);
+}
+
+#[export_name = "cxxbridge02$rust_vec$u8$new"]
+unsafe extern "C" fn rust_vec_u8_new(this: *mut Vec<u8>) {
+    ptr::write(this, Vec::new());
+}
+
+#[export_name = "cxxbridge02$rust_vec$u8$drop"]
+unsafe extern "C" fn rust_vec_u8_drop(this: *mut Vec<u8>) {
+    ptr::drop_in_place(this);
+}
+
+// Vec<Vec<u8>>: the elements own their buffers, so shrinking on the C++ side
+// runs the element destructors, which come back here through $drop above.
+
+#[export_name = "cxxbridge02$rust_vec$rust_vec$u8$len"]
+unsafe extern "C" fn rust_vec_rust_vec_u8_len(this: &Vec<Vec<u8>>) -> usize {
+    this.len()
+}
+
+#[export_name = "cxxbridge02$rust_vec$rust_vec$u8$capacity"]
+unsafe extern "C" fn rust_vec_rust_vec_u8_capacity(this: &Vec<Vec<u8>>) -> usize {
+    this.capacity()
+}
+
+#[export_name = "cxxbridge02$rust_vec$rust_vec$u8$data"]
+unsafe extern "C" fn rust_vec_rust_vec_u8_data(this: &Vec<Vec<u8>>) -> *const Vec<u8> {
+    this.as_ptr()
+}
+
+#[export_name = "cxxbridge02$rust_vec$rust_vec$u8$reserve_total"]
+unsafe extern "C" fn rust_vec_rust_vec_u8_reserve_total(this: &mut Vec<Vec<u8>>, cap: usize) {
+    if cap > this.capacity() {
+        this.reserve(cap - this.len());
+    }
+}
+
+#[export_name = "cxxbridge02$rust_vec$rust_vec$u8$set_len"]
+unsafe extern "C" fn rust_vec_rust_vec_u8_set_len(this: &mut Vec<Vec<u8>>, len: usize) {
+    this.set_len(len);
+}
+
+#[export_name = "cxxbridge02$rust_vec$rust_vec$u8$new"]
+unsafe extern "C" fn rust_vec_rust_vec_u8_new(this: *mut Vec<Vec<u8>>) {
+    ptr::write(this, Vec::new());
+}
+
+#[export_name = "cxxbridge02$rust_vec$rust_vec$u8$drop"]
+unsafe extern "C" fn rust_vec_rust_vec_u8_drop(this: *mut Vec<Vec<u8>>) {
+    ptr::drop_in_place(this);
+}
+
+// Vec<String>: rust::String has the same layout as String, and C++ moves each
+// element individually through rust::String's own constructors.
+
+#[export_name = "cxxbridge02$rust_vec$string$len"]
+unsafe extern "C" fn rust_vec_string_len(this: &Vec<String>) -> usize {
+    this.len()
+}
+
+#[export_name = "cxxbridge02$rust_vec$string$capacity"]
+unsafe extern "C" fn rust_vec_string_capacity(this: &Vec<String>) -> usize {
+    this.capacity()
+}
+
+#[export_name = "cxxbridge02$rust_vec$string$data"]
+unsafe extern "C" fn rust_vec_string_data(this: &Vec<String>) -> *const String {
+    this.as_ptr()
+}
+
+#[export_name = "cxxbridge02$rust_vec$string$reserve_total"]
+unsafe extern "C" fn rust_vec_string_reserve_total(this: &mut Vec<String>, cap: usize) {
+    if cap > this.capacity() {
+        this.reserve(cap - this.len());
+    }
+}
+
+#[export_name = "cxxbridge02$rust_vec$string$set_len"]
+unsafe extern "C" fn rust_vec_string_set_len(this: &mut Vec<String>, len: usize) {
+    this.set_len(len);
+}
+
+#[export_name = "cxxbridge02$rust_vec$string$new"]
+unsafe extern "C" fn rust_vec_string_new(this: *mut Vec<String>) {
+    ptr::write(this, Vec::new());
+}
+
+#[export_name = "cxxbridge02$rust_vec$string$drop"]
+unsafe extern "C" fn rust_vec_string_drop(this: *mut Vec<String>) {
+    ptr::drop_in_place(this);
+}
+
+fn _assert() {
+    let _: [(); mem::size_of::<[usize; 3]>()] = [(); mem::size_of::<Vec<u8>>()];
+    let _: [(); mem::align_of::<usize>()] = [(); mem::align_of::<Vec<u8>>()];
+}
@@ -0,0 +1,217 @@
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr;
+use std::slice;
+
+/// Binding to C++ `std::unique_ptr<T[]>`.
+///
+/// Unlike a Vector, the array does not know its own length; as with a
+/// unique_ptr<T[]> in C++, the length travels separately and the caller
+/// vouches for it when looking at the elements.
+#[repr(C)]
+pub struct UniqueArray<T>
+where
+    T: ArrayElement,
+{
+    repr: *mut c_void,
+    ty: PhantomData<T>,
+}
+
+impl<T> UniqueArray<T>
+where
+    T: ArrayElement,
+{
+    /// Makes a new UniqueArray wrapping a null pointer.
+    ///
+    /// Matches the behavior of default-constructing a std::unique\_ptr.
+    pub fn null() -> Self {
+        UniqueArray {
+            repr: T::__null(),
+            ty: PhantomData,
+        }
+    }
+
+    /// Checks whether the UniqueArray does not own an array.
+    ///
+    /// This is the opposite of [std::unique_ptr\<T\[\]\>::operator bool](https://en.cppreference.com/w/cpp/memory/unique_ptr/operator_bool).
+    pub fn is_null(&self) -> bool {
+        self.as_ptr().is_null()
+    }
+
+    /// Returns a pointer to the first element of the owned array, or null.
+    ///
+    /// Matches the behavior of [std::unique_ptr\<T\[\]\>::get](https://en.cppreference.com/w/cpp/memory/unique_ptr/get).
+    pub fn as_ptr(&self) -> *const T {
+        unsafe { T::__get(self.repr) }
+    }
+
+    /// Returns a mutable pointer to the first element of the owned array, or
+    /// null.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.as_ptr() as *mut T
+    }
+
+    /// Returns a reference to the element at the given index.
+    ///
+    /// Matches the behavior of C++
+    /// [std::unique_ptr\<T\[\]\>::operator\[\]][operator_at].
+    ///
+    /// # Safety
+    ///
+    /// The UniqueArray must be non-null and `index` must be less than the
+    /// length of the array it owns.
+    ///
+    /// [operator_at]: https://en.cppreference.com/w/cpp/memory/unique_ptr/operator_at
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        &*self.as_ptr().add(index)
+    }
+
+    /// Views the owned array as a slice of the given length.
+    ///
+    /// # Safety
+    ///
+    /// The array must hold at least `len` elements, and must be non-null
+    /// unless `len` is 0.
+    pub unsafe fn as_slice(&self, len: usize) -> &[T] {
+        if len == 0 {
+            &[]
+        } else {
+            slice::from_raw_parts(self.as_ptr(), len)
+        }
+    }
+
+    /// Views the owned array as a mutable slice of the given length.
+    ///
+    /// # Safety
+    ///
+    /// The array must hold at least `len` elements, and must be non-null
+    /// unless `len` is 0.
+    pub unsafe fn as_mut_slice(&mut self, len: usize) -> &mut [T] {
+        if len == 0 {
+            &mut []
+        } else {
+            slice::from_raw_parts_mut(self.as_mut_ptr(), len)
+        }
+    }
+
+    /// Consumes the UniqueArray, releasing its ownership of the array.
+    ///
+    /// Matches the behavior of [std::unique_ptr\<T\[\]\>::release](https://en.cppreference.com/w/cpp/memory/unique_ptr/release).
+    pub fn into_raw(self) -> *mut T {
+        let ptr = unsafe { T::__release(self.repr) };
+        mem::forget(self);
+        ptr
+    }
+
+    /// Constructs a UniqueArray retaking ownership of a pointer previously
+    /// obtained from `into_raw`, or allocated in C++ by `new T[n]`.
+    ///
+    /// # Safety
+    ///
+    /// This function is unsafe because improper use may lead to memory
+    /// problems. For example a double-free may occur if the function is called
+    /// twice on the same raw pointer.
+    pub unsafe fn from_raw(raw: *mut T) -> Self {
+        UniqueArray {
+            repr: T::__raw(raw),
+            ty: PhantomData,
+        }
+    }
+}
+
+unsafe impl<T> Send for UniqueArray<T> where T: Send + ArrayElement {}
+unsafe impl<T> Sync for UniqueArray<T> where T: Sync + ArrayElement {}
+
+impl<T> Drop for UniqueArray<T>
+where
+    T: ArrayElement,
+{
+    fn drop(&mut self) {
+        unsafe { T::__drop(self.repr) }
+    }
+}
+
+// Methods are private; not intended to be implemented outside of cxxbridge
+// codebase.
+#[doc(hidden)]
+pub unsafe trait ArrayElement: Sized {
+    #[doc(hidden)]
+    fn __null() -> *mut c_void;
+    #[doc(hidden)]
+    unsafe fn __raw(raw: *mut Self) -> *mut c_void;
+    #[doc(hidden)]
+    unsafe fn __get(repr: *mut c_void) -> *const Self;
+    #[doc(hidden)]
+    unsafe fn __release(repr: *mut c_void) -> *mut Self;
+    #[doc(hidden)]
+    unsafe fn __drop(repr: *mut c_void);
+}
+
+macro_rules! impl_array_element_for_primitive {
+    ($ty:ty, $name:literal) => {
+        unsafe impl ArrayElement for $ty {
+            fn __null() -> *mut c_void {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$unique_ptr$array$", $name, "$null")]
+                        fn __null(this: *mut *mut c_void);
+                    }
+                }
+                let mut repr = ptr::null_mut::<c_void>();
+                unsafe { __null(&mut repr) }
+                repr
+            }
+            unsafe fn __raw(raw: *mut Self) -> *mut c_void {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$unique_ptr$array$", $name, "$raw")]
+                        fn __raw(this: *mut *mut c_void, raw: *mut $ty);
+                    }
+                }
+                let mut repr = ptr::null_mut::<c_void>();
+                __raw(&mut repr, raw);
+                repr
+            }
+            unsafe fn __get(repr: *mut c_void) -> *const Self {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$unique_ptr$array$", $name, "$get")]
+                        fn __get(this: *const *mut c_void) -> *const $ty;
+                    }
+                }
+                __get(&repr)
+            }
+            unsafe fn __release(mut repr: *mut c_void) -> *mut Self {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$unique_ptr$array$", $name, "$release")]
+                        fn __release(this: *mut *mut c_void) -> *mut $ty;
+                    }
+                }
+                __release(&mut repr)
+            }
+            unsafe fn __drop(mut repr: *mut c_void) {
+                extern "C" {
+                    attr! {
+                        #[link_name = concat!("cxxbridge02$unique_ptr$array$", $name, "$drop")]
+                        fn __drop(this: *mut *mut c_void);
+                    }
+                }
+                __drop(&mut repr);
+            }
+        }
+    };
+}
+
+impl_array_element_for_primitive!(u8, "u8");
+impl_array_element_for_primitive!(u16, "u16");
+impl_array_element_for_primitive!(u32, "u32");
+impl_array_element_for_primitive!(u64, "u64");
+impl_array_element_for_primitive!(usize, "usize");
+impl_array_element_for_primitive!(i8, "i8");
+impl_array_element_for_primitive!(i16, "i16");
+impl_array_element_for_primitive!(i32, "i32");
+impl_array_element_for_primitive!(i64, "i64");
+impl_array_element_for_primitive!(f32, "f32");
+impl_array_element_for_primitive!(f64, "f64");
@@ -1,8 +1,12 @@
 use crate::cxx_string::CxxString;
+use crate::cxx_u16string::CxxU16String;
+use std::any::type_name;
 use std::ffi::c_void;
 use std::fmt::{self, Debug, Display};
 use std::marker::PhantomData;
 use std::mem::{self, MaybeUninit};
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
 use std::ptr;
 
 /// Binding to C++ `std::unique_ptr<T, std::default_delete<T>>`.
@@ -51,6 +55,32 @@ where
         unsafe { T::__get(self.repr).as_ref() }
     }
 
+    /// Returns a mutable reference to the object owned by this UniquePtr if
+    /// any, otherwise None.
+    ///
+    /// Opaque C++ types are not `Unpin`; use [`pin_mut`][UniquePtr::pin_mut]
+    /// for those instead.
+    pub fn as_mut(&mut self) -> Option<&mut T>
+    where
+        T: Unpin,
+    {
+        unsafe { (T::__get(self.repr) as *mut T).as_mut() }
+    }
+
+    /// Returns a pinned mutable reference to the object owned by this
+    /// UniquePtr, for calling C++ methods that take `self: Pin<&mut T>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the UniquePtr holds a null pointer.
+    pub fn pin_mut(&mut self) -> Pin<&mut T> {
+        match unsafe { (T::__get(self.repr) as *mut T).as_mut() } {
+            // The heap-allocated T never moves while the UniquePtr owns it.
+            Some(target) => unsafe { Pin::new_unchecked(target) },
+            None => panic!("called pin_mut on a null UniquePtr<{}>", type_name::<T>()),
+        }
+    }
+
     /// Consumes the UniquePtr, releasing its ownership of the heap-allocated T.
     ///
     /// Matches the behavior of [std::unique_ptr\<T\>::release](https://en.cppreference.com/w/cpp/memory/unique_ptr/release).
@@ -88,6 +118,32 @@ where
     }
 }
 
+impl<T> Deref for UniquePtr<T>
+where
+    T: UniquePtrTarget,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self.as_ref() {
+            Some(target) => target,
+            None => panic!("called deref on a null UniquePtr<{}>", type_name::<T>()),
+        }
+    }
+}
+
+impl<T> DerefMut for UniquePtr<T>
+where
+    T: UniquePtrTarget + Unpin,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self.as_mut() {
+            Some(target) => target,
+            None => panic!("called deref_mut on a null UniquePtr<{}>", type_name::<T>()),
+        }
+    }
+}
+
 impl<T> Debug for UniquePtr<T>
 where
     T: Debug + UniquePtrTarget,
@@ -153,7 +209,7 @@ unsafe impl UniquePtrTarget for CxxString {
     fn __new(value: Self) -> *mut c_void {
         let mut repr = ptr::null_mut::<c_void>();
         let mut value = MaybeUninit::new(value);
-        unsafe { unique_ptr_std_string_new(&mut repr, value.as_mut_ptr() as *mut Self) }
+        unsafe { unique_ptr_std_string_new(&mut repr, value.as_mut_ptr()) }
         repr
     }
     unsafe fn __raw(raw: *mut Self) -> *mut c_void {
@@ -171,3 +227,46 @@ unsafe impl UniquePtrTarget for CxxString {
         unique_ptr_std_string_drop(&mut repr);
     }
 }
+
+extern "C" {
+    #[link_name = "cxxbridge02$unique_ptr$std$u16string$null"]
+    fn unique_ptr_std_u16string_null(this: *mut *mut c_void);
+    #[link_name = "cxxbridge02$unique_ptr$std$u16string$new"]
+    fn unique_ptr_std_u16string_new(this: *mut *mut c_void, value: *mut CxxU16String);
+    #[link_name = "cxxbridge02$unique_ptr$std$u16string$raw"]
+    fn unique_ptr_std_u16string_raw(this: *mut *mut c_void, raw: *mut CxxU16String);
+    #[link_name = "cxxbridge02$unique_ptr$std$u16string$get"]
+    fn unique_ptr_std_u16string_get(this: *const *mut c_void) -> *const CxxU16String;
+    #[link_name = "cxxbridge02$unique_ptr$std$u16string$release"]
+    fn unique_ptr_std_u16string_release(this: *mut *mut c_void) -> *mut CxxU16String;
+    #[link_name = "cxxbridge02$unique_ptr$std$u16string$drop"]
+    fn unique_ptr_std_u16string_drop(this: *mut *mut c_void);
+}
+
+unsafe impl UniquePtrTarget for CxxU16String {
+    fn __null() -> *mut c_void {
+        let mut repr = ptr::null_mut::<c_void>();
+        unsafe { unique_ptr_std_u16string_null(&mut repr) }
+        repr
+    }
+    fn __new(value: Self) -> *mut c_void {
+        let mut repr = ptr::null_mut::<c_void>();
+        let mut value = MaybeUninit::new(value);
+        unsafe { unique_ptr_std_u16string_new(&mut repr, value.as_mut_ptr()) }
+        repr
+    }
+    unsafe fn __raw(raw: *mut Self) -> *mut c_void {
+        let mut repr = ptr::null_mut::<c_void>();
+        unique_ptr_std_u16string_raw(&mut repr, raw);
+        repr
+    }
+    unsafe fn __get(repr: *mut c_void) -> *const Self {
+        unique_ptr_std_u16string_get(&repr)
+    }
+    unsafe fn __release(mut repr: *mut c_void) -> *mut Self {
+        unique_ptr_std_u16string_release(&mut repr)
+    }
+    unsafe fn __drop(mut repr: *mut c_void) {
+        unique_ptr_std_u16string_drop(&mut repr);
+    }
+}
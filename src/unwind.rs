@@ -1,12 +1,14 @@
 use std::io::{self, Write};
-use std::panic::{self, UnwindSafe};
+use std::panic::{self, AssertUnwindSafe};
 use std::process;
 
 pub fn catch_unwind<F, R>(label: &'static str, foreign_call: F) -> R
 where
-    F: FnOnce() -> R + UnwindSafe,
+    F: FnOnce() -> R,
 {
-    match panic::catch_unwind(foreign_call) {
+    // A panic aborts the process, so nothing the closure left half-mutated
+    // through a &mut argument can ever be observed.
+    match panic::catch_unwind(AssertUnwindSafe(foreign_call)) {
         Ok(ret) => ret,
         Err(_) => abort(label),
     }
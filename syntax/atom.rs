@@ -4,19 +4,23 @@ use proc_macro2::Ident;
 #[derive(Copy, Clone, PartialEq)]
 pub enum Atom {
     Bool,
+    Char,
     U8,
     U16,
     U32,
     U64,
+    U128,
     Usize,
     I8,
     I16,
     I32,
     I64,
+    I128,
     Isize,
     F32,
     F64,
     CxxString,
+    CxxU16String,
     RustString,
 }
 
@@ -25,19 +29,23 @@ impl Atom {
         use self::Atom::*;
         match ident.to_string().as_str() {
             "bool" => Some(Bool),
+            "char" => Some(Char),
             "u8" => Some(U8),
             "u16" => Some(U16),
             "u32" => Some(U32),
             "u64" => Some(U64),
+            "u128" => Some(U128),
             "usize" => Some(Usize),
             "i8" => Some(I8),
             "i16" => Some(I16),
             "i32" => Some(I32),
             "i64" => Some(I64),
+            "i128" => Some(I128),
             "isize" => Some(Isize),
             "f32" => Some(F32),
             "f64" => Some(F64),
             "CxxString" => Some(CxxString),
+            "CxxU16String" => Some(CxxU16String),
             "String" => Some(RustString),
             _ => None,
         }
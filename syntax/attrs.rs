@@ -1,30 +1,150 @@
-use crate::syntax::{Derive, Doc};
+use crate::syntax::{parse, Deprecated, Derive, Doc, Template};
 use proc_macro2::Ident;
-use syn::parse::{ParseStream, Parser};
+use syn::parse::{ParseStream, Parser as _};
 use syn::{Attribute, Error, LitStr, Path, Result, Token};
 
-pub(super) fn parse_doc(attrs: &[Attribute]) -> Result<Doc> {
-    let mut doc = Doc::new();
-    let derives = None;
-    parse(attrs, &mut doc, derives)?;
-    Ok(doc)
+// The attributes accepted by an item; any attribute whose slot is None is
+// rejected as unsupported.
+#[derive(Default)]
+pub(super) struct Parser<'a> {
+    pub doc: Option<&'a mut Doc>,
+    pub derives: Option<&'a mut Vec<Ident>>,
+    pub deprecated: Option<&'a mut Option<Deprecated>>,
+    pub non_exhaustive: Option<&'a mut Option<Ident>>,
+    pub fixed_width: Option<&'a mut Option<Ident>>,
+    pub constructor: Option<&'a mut Option<Ident>>,
+    pub debug: Option<&'a mut Option<Ident>>,
+    pub placement: Option<&'a mut Option<Ident>>,
+    pub pure: Option<&'a mut Option<Ident>>,
+    pub init_once: Option<&'a mut Option<Ident>>,
+    pub non_reentrant: Option<&'a mut Option<Ident>>,
+    pub time_point: Option<&'a mut Option<Ident>>,
+    pub exception: Option<&'a mut Option<Path>>,
+    pub repr: Option<&'a mut Option<Ident>>,
+    pub template: Option<&'a mut Option<Template>>,
+    pub link_section: Option<&'a mut Option<LitStr>>,
+    pub abi: Option<&'a mut Option<LitStr>>,
+    pub error_convert: Option<&'a mut Option<Path>>,
+    pub cxx_name: Option<&'a mut Option<Ident>>,
 }
 
-pub(super) fn parse(
-    attrs: &[Attribute],
-    doc: &mut Doc,
-    mut derives: Option<&mut Vec<Ident>>,
-) -> Result<()> {
+pub(super) fn parse(attrs: &[Attribute], mut parser: Parser) -> Result<()> {
     for attr in attrs {
         if attr.path.is_ident("doc") {
-            let lit = parse_doc_attribute.parse2(attr.tokens.clone())?;
-            doc.push(lit);
-            continue;
+            if let Some(doc) = &mut parser.doc {
+                let lit = parse_doc_attribute.parse2(attr.tokens.clone())?;
+                doc.push(lit);
+                continue;
+            }
         } else if attr.path.is_ident("derive") {
-            if let Some(derives) = &mut derives {
+            if let Some(derives) = &mut parser.derives {
                 derives.extend(attr.parse_args_with(parse_derive_attribute)?);
                 continue;
             }
+        } else if attr.path.is_ident("deprecated") {
+            if let Some(deprecated) = &mut parser.deprecated {
+                let ident = attr.path.get_ident().unwrap().clone();
+                let note = parse_deprecated_attribute.parse2(attr.tokens.clone())?;
+                **deprecated = Some(Deprecated { ident, note });
+                continue;
+            }
+        } else if attr.path.is_ident("template") {
+            if let Some(template) = &mut parser.template {
+                **template = Some(attr.parse_args_with(parse_template_attribute)?);
+                continue;
+            }
+        } else if attr.path.is_ident("link_section") {
+            if let Some(link_section) = &mut parser.link_section {
+                **link_section = Some(parse_doc_attribute.parse2(attr.tokens.clone())?);
+                continue;
+            }
+        } else if attr.path.is_ident("cxx_abi") {
+            if let Some(abi) = &mut parser.abi {
+                **abi = Some(parse_doc_attribute.parse2(attr.tokens.clone())?);
+                continue;
+            }
+        } else if is_cxx_name(&attr.path) {
+            if let Some(cxx_name) = &mut parser.cxx_name {
+                **cxx_name = Some(parse_cxx_name_attribute.parse2(attr.tokens.clone())?);
+                continue;
+            }
+        } else if attr.path.is_ident("cxx_error_convert") {
+            if let Some(error_convert) = &mut parser.error_convert {
+                **error_convert = Some(attr.parse_args()?);
+                continue;
+            }
+        } else if attr.path.is_ident("cxx_exception") {
+            if let Some(exception) = &mut parser.exception {
+                **exception = Some(attr.parse_args()?);
+                continue;
+            }
+        } else if attr.path.is_ident("repr") {
+            if let Some(repr) = &mut parser.repr {
+                **repr = Some(attr.parse_args()?);
+                continue;
+            }
+        } else if attr.path.is_ident("non_exhaustive") {
+            if let Some(non_exhaustive) = &mut parser.non_exhaustive {
+                if attr.tokens.is_empty() {
+                    **non_exhaustive = attr.path.get_ident().cloned();
+                    continue;
+                }
+            }
+        } else if attr.path.is_ident("cxx_fixed_width") {
+            if let Some(fixed_width) = &mut parser.fixed_width {
+                if attr.tokens.is_empty() {
+                    **fixed_width = attr.path.get_ident().cloned();
+                    continue;
+                }
+            }
+        } else if attr.path.is_ident("cxx_constructor") {
+            if let Some(constructor) = &mut parser.constructor {
+                if attr.tokens.is_empty() {
+                    **constructor = attr.path.get_ident().cloned();
+                    continue;
+                }
+            }
+        } else if attr.path.is_ident("cxx_pure") {
+            if let Some(pure) = &mut parser.pure {
+                if attr.tokens.is_empty() {
+                    **pure = attr.path.get_ident().cloned();
+                    continue;
+                }
+            }
+        } else if attr.path.is_ident("cxx_init_once") {
+            if let Some(init_once) = &mut parser.init_once {
+                if attr.tokens.is_empty() {
+                    **init_once = attr.path.get_ident().cloned();
+                    continue;
+                }
+            }
+        } else if attr.path.is_ident("cxx_non_reentrant") {
+            if let Some(non_reentrant) = &mut parser.non_reentrant {
+                if attr.tokens.is_empty() {
+                    **non_reentrant = attr.path.get_ident().cloned();
+                    continue;
+                }
+            }
+        } else if attr.path.is_ident("cxx_time_point") {
+            if let Some(time_point) = &mut parser.time_point {
+                attr.parse_args_with(parse_time_point_attribute)?;
+                **time_point = attr.path.get_ident().cloned();
+                continue;
+            }
+        } else if attr.path.is_ident("cxx_debug") {
+            if let Some(debug) = &mut parser.debug {
+                if attr.tokens.is_empty() {
+                    **debug = attr.path.get_ident().cloned();
+                    continue;
+                }
+            }
+        } else if attr.path.is_ident("cxx_placement") {
+            if let Some(placement) = &mut parser.placement {
+                if attr.tokens.is_empty() {
+                    **placement = attr.path.get_ident().cloned();
+                    continue;
+                }
+            }
         }
         return Err(Error::new_spanned(attr, "unsupported attribute"));
     }
@@ -37,6 +157,77 @@ fn parse_doc_attribute(input: ParseStream) -> Result<LitStr> {
     Ok(lit)
 }
 
+fn is_cxx_name(path: &Path) -> bool {
+    let segments = &path.segments;
+    path.leading_colon.is_none()
+        && segments.len() == 2
+        && segments[0].ident == "cxx"
+        && segments[1].ident == "name"
+}
+
+fn parse_cxx_name_attribute(input: ParseStream) -> Result<Ident> {
+    let lit = parse_doc_attribute(input)?;
+    let name = lit.value();
+    let mut chars = name.chars();
+    let valid = match chars.next() {
+        Some(first) => {
+            (first.is_ascii_alphabetic() || first == '_')
+                && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+        }
+        None => false,
+    };
+    if !valid {
+        return Err(Error::new_spanned(lit, "invalid C++ identifier"));
+    }
+    Ok(Ident::new(&name, lit.span()))
+}
+
+fn parse_deprecated_attribute(input: ParseStream) -> Result<Option<LitStr>> {
+    if input.is_empty() {
+        return Ok(None);
+    }
+    input.parse::<Token![=]>()?;
+    let lit: LitStr = input.parse()?;
+    Ok(Some(lit))
+}
+
+// Only system_clock at nanosecond resolution is supported so far. Both are
+// spelled out anyway so that other clocks and resolutions can be added without
+// changing the meaning of existing bridges.
+fn parse_time_point_attribute(input: ParseStream) -> Result<()> {
+    let clock: Ident = input.parse()?;
+    input.parse::<Token![,]>()?;
+    let resolution: Ident = input.parse()?;
+    if clock != "system_clock" {
+        return Err(Error::new_spanned(
+            clock,
+            "unsupported clock, expected system_clock",
+        ));
+    }
+    if resolution != "nanoseconds" {
+        return Err(Error::new_spanned(
+            resolution,
+            "unsupported resolution, expected nanoseconds",
+        ));
+    }
+    Ok(())
+}
+
+fn parse_template_attribute(input: ParseStream) -> Result<Template> {
+    let ident: Ident = input.parse()?;
+    input.parse::<Token![<]>()?;
+    let mut args = Vec::new();
+    while !input.peek(Token![>]) {
+        let ty: syn::Type = input.parse()?;
+        args.push(parse::parse_type(&ty)?);
+        if input.parse::<Option<Token![,]>>()?.is_none() {
+            break;
+        }
+    }
+    input.parse::<Token![>]>()?;
+    Ok(Template { ident, args })
+}
+
 fn parse_derive_attribute(input: ParseStream) -> Result<Vec<Ident>> {
     input
         .parse_terminated::<Path, Token![,]>(Path::parse_mod_style)?
@@ -53,6 +244,9 @@ impl Derive {
         match ident.to_string().as_str() {
             "Clone" => Some(Derive::Clone),
             "Copy" => Some(Derive::Copy),
+            "Default" => Some(Derive::Default),
+            "Hash" => Some(Derive::Hash),
+            "PartialEq" => Some(Derive::PartialEq),
             _ => None,
         }
     }
@@ -63,6 +257,9 @@ impl AsRef<str> for Derive {
         match self {
             Derive::Clone => "Clone",
             Derive::Copy => "Copy",
+            Derive::Default => "Default",
+            Derive::Hash => "Hash",
+            Derive::PartialEq => "PartialEq",
         }
     }
 }
@@ -1,10 +1,27 @@
 use crate::syntax::atom::Atom::{self, *};
-use crate::syntax::{error, ident, Api, ExternFn, Lang, Ref, Struct, Ty1, Type, Types};
+use crate::syntax::{
+    error, ident, mangle, Api, Array, Enum, ExceptionEnum, ExternFn, ExternType, Lang, Ref, Slice,
+    Struct, Tuple, Ty1, Ty2, Type, Types,
+};
 use proc_macro2::{Delimiter, Group, Ident, TokenStream};
 use quote::{quote, ToTokens};
+use std::collections::HashSet;
 use std::fmt::Display;
 use syn::{Error, Result};
 
+// Rust validates each char32_t it receives from C++, which only works for a
+// value that is copied across, not for one that Rust views in place.
+const CHAR_BY_VALUE: &str =
+    "char is only supported by value as a function argument or return value";
+const BOX_SLICE_POSITION: &str =
+    "Box<[T]> is only supported as the return type of a Rust function so far";
+// C++ does not pass or return a T[N] by value.
+const ARRAY_POSITION: &str =
+    "fixed-size arrays are only supported as struct fields; use Array<T, N> for std::array";
+// Opaque C++ types are !Unpin, so Rust must not hand out a movable &mut.
+const MUT_REF_REQUIRES_PIN: &str =
+    "mutable reference to an opaque C++ type requires a pin; use Pin<&mut T>";
+
 struct Check<'a> {
     apis: &'a [Api],
     types: &'a Types<'a>,
@@ -27,8 +44,22 @@ fn do_typecheck(cx: &mut Check) {
         match ty {
             Type::Ident(ident) => check_type_ident(cx, ident),
             Type::RustBox(ptr) => check_type_box(cx, ptr),
+            Type::RustArc(ptr) => check_type_arc(cx, ptr),
+            Type::RustVec(ty) => check_type_rust_vec(cx, ty),
             Type::UniquePtr(ptr) => check_type_unique_ptr(cx, ptr),
+            Type::UniqueArray(ptr) => check_type_unique_array(cx, ptr),
+            Type::SharedPtr(ptr) => check_type_shared_ptr(cx, ptr),
+            Type::CxxVector(ty) => check_type_cxx_vector(cx, ty),
+            Type::CxxMap(ty) => check_type_cxx_map(cx, ty),
+            Type::CxxSet(ty) => check_type_cxx_set(cx, ty),
+            Type::Borrowed(ty) => check_type_borrowed(cx, ty),
+            Type::Optional(ty) => check_type_optional(cx, ty),
             Type::Ref(ty) => check_type_ref(cx, ty),
+            Type::Slice(ty) => check_type_slice(cx, ty),
+            Type::SliceBox(ty) => check_type_slice_box(cx, ty),
+            Type::Tuple(ty) => check_type_tuple(cx, ty),
+            Type::Array(ty) => check_type_array(cx, ty),
+            Type::CxxArray(ty) => check_type_cxx_array(cx, ty),
             _ => {}
         }
     }
@@ -36,11 +67,61 @@ fn do_typecheck(cx: &mut Check) {
     for api in cx.apis {
         match api {
             Api::Struct(strct) => check_api_struct(cx, strct),
+            Api::Enum(enm) => check_api_enum(cx, enm),
+            Api::ExceptionEnum(enm) => check_api_exception_enum(cx, enm),
+            Api::RustType(ety) => check_api_rust_type(cx, ety),
             Api::CxxFunction(efn) | Api::RustFunction(efn) => check_api_fn(cx, efn),
             _ => {}
         }
     }
 
+    check_recursive_structs(cx);
+
+    let mut link_names = HashSet::new();
+    for api in cx.apis {
+        if let Api::CxxFunction(efn) | Api::RustFunction(efn) = api {
+            // A C++ function and a Rust function may share a name, since the
+            // direction is part of the mangled name. The namespace and module
+            // are the same for every function of the bridge.
+            if !link_names.insert(mangle::extern_fn("", "", efn)) {
+                let lang = match efn.lang {
+                    Lang::Cxx => "C",
+                    Lang::Rust => "Rust",
+                };
+                let msg = format!(
+                    "duplicate function name among extern \"{}\" functions",
+                    lang
+                );
+                cx.error(efn.cxx_ident(), msg);
+            }
+        }
+    }
+
+    // A #[cxx::name] must not land on the C++ name of another type.
+    let mut cxx_type_names = HashSet::new();
+    for api in cx.apis {
+        match api {
+            Api::Struct(Struct { ident, .. })
+            | Api::Enum(Enum { ident, .. })
+            | Api::ExceptionEnum(ExceptionEnum { ident, .. }) => {
+                cxx_type_names.insert(ident.to_string());
+            }
+            Api::CxxType(ety) | Api::RustType(ety) if ety.cxx_name.is_none() => {
+                cxx_type_names.insert(ety.ident.to_string());
+            }
+            _ => {}
+        }
+    }
+    for api in cx.apis {
+        if let Api::CxxType(ety) | Api::RustType(ety) = api {
+            if let Some(cxx_name) = &ety.cxx_name {
+                if !cxx_type_names.insert(cxx_name.to_string()) {
+                    cx.error(cxx_name, "duplicate C++ name among types");
+                }
+            }
+        }
+    }
+
     for api in cx.apis {
         if let Api::CxxFunction(efn) = api {
             check_mut_return_restriction(cx, efn);
@@ -62,6 +143,7 @@ impl Check<'_> {
 fn check_type_ident(cx: &mut Check, ident: &Ident) {
     if Atom::from(ident).is_none()
         && !cx.types.structs.contains_key(ident)
+        && !cx.types.enums.contains_key(ident)
         && !cx.types.cxx.contains(ident)
         && !cx.types.rust.contains(ident)
     {
@@ -83,14 +165,47 @@ fn check_type_box(cx: &mut Check, ptr: &Ty1) {
     cx.error(ptr, "unsupported target type of Box");
 }
 
+fn check_type_arc(cx: &mut Check, ptr: &Ty1) {
+    if let Type::Ident(ident) = &ptr.inner {
+        if cx.types.cxx.contains(ident) {
+            cx.error(ptr, "Arc of a C++ type is not supported; use SharedPtr<>");
+        }
+
+        if Atom::from(ident).is_none() {
+            return;
+        }
+    }
+
+    cx.error(ptr, "unsupported target type of Arc");
+}
+
+fn check_type_rust_vec(cx: &mut Check, ty: &Ty1) {
+    match &ty.inner {
+        Type::Ident(ident) if Atom::from(ident) == Some(U8) => return,
+        Type::Ident(ident) if Atom::from(ident) == Some(RustString) => return,
+        Type::Ident(ident) if cx.types.structs.contains_key(ident) => return,
+        Type::RustVec(inner) => match &inner.inner {
+            Type::Ident(ident) if Atom::from(ident) == Some(U8) => return,
+            _ => {}
+        },
+        _ => {}
+    }
+
+    cx.error(ty, "unsupported element type of Vec");
+}
+
 fn check_type_unique_ptr(cx: &mut Check, ptr: &Ty1) {
+    if let Type::CxxVector(_) = &ptr.inner {
+        return;
+    }
+
     if let Type::Ident(ident) = &ptr.inner {
         if cx.types.rust.contains(ident) {
             cx.error(ptr, "unique_ptr of a Rust type is not supported yet");
         }
 
         match Atom::from(ident) {
-            None | Some(CxxString) => return,
+            None | Some(CxxString) | Some(CxxU16String) => return,
             _ => {}
         }
     }
@@ -98,21 +213,343 @@ fn check_type_unique_ptr(cx: &mut Check, ptr: &Ty1) {
     cx.error(ptr, "unsupported unique_ptr target type");
 }
 
+fn check_type_unique_array(cx: &mut Check, ptr: &Ty1) {
+    if let Type::Ident(ident) = &ptr.inner {
+        match Atom::from(ident) {
+            Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(Usize) | Some(I8) | Some(I16)
+            | Some(I32) | Some(I64) | Some(F32) | Some(F64) => return,
+            _ => {}
+        }
+    }
+
+    cx.error(ptr, "unsupported element type of UniqueArray");
+}
+
+fn check_type_shared_ptr(cx: &mut Check, ptr: &Ty1) {
+    if let Type::Ident(ident) = &ptr.inner {
+        if cx.types.rust.contains(ident) {
+            cx.error(ptr, "shared_ptr of a Rust type is not supported yet");
+        }
+
+        if Atom::from(ident).is_none() {
+            return;
+        }
+    }
+
+    cx.error(ptr, "unsupported shared_ptr target type");
+}
+
+fn check_type_cxx_vector(cx: &mut Check, ty: &Ty1) {
+    if let Type::Ident(ident) = &ty.inner {
+        if cx.types.structs.contains_key(ident) || cx.types.cxx.contains(ident) {
+            return;
+        }
+
+        match Atom::from(ident) {
+            Some(Bool) | Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(Usize) | Some(I8)
+            | Some(I16) | Some(I32) | Some(I64) | Some(F32) | Some(F64) => return,
+            _ => {}
+        }
+    }
+
+    cx.error(ty, "unsupported element type of Vector");
+}
+
+fn check_type_cxx_map(cx: &mut Check, ty: &Ty2) {
+    let key_ok = match &ty.first {
+        Type::Ident(ident) => matches!(
+            Atom::from(ident),
+            Some(U8)
+                | Some(U16)
+                | Some(U32)
+                | Some(U64)
+                | Some(Usize)
+                | Some(I8)
+                | Some(I16)
+                | Some(I32)
+                | Some(I64)
+                | Some(CxxString)
+        ),
+        _ => false,
+    };
+    if !key_ok {
+        cx.error(&ty.first, "unsupported key type of CxxMap");
+    }
+
+    let value_ok = match &ty.second {
+        Type::Ident(ident) => match Atom::from(ident) {
+            Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(Usize) | Some(I8) | Some(I16)
+            | Some(I32) | Some(I64) | Some(F32) | Some(F64) | Some(CxxString) => true,
+            None => cx.types.structs.contains_key(ident),
+            _ => false,
+        },
+        _ => false,
+    };
+    if !value_ok {
+        cx.error(&ty.second, "unsupported value type of CxxMap");
+    }
+}
+
+fn check_type_cxx_set(cx: &mut Check, ty: &Ty1) {
+    if let Type::Ident(ident) = &ty.inner {
+        match Atom::from(ident) {
+            Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(Usize) | Some(I8) | Some(I16)
+            | Some(I32) | Some(I64) | Some(F32) | Some(F64) | Some(CxxString) => return,
+            _ => {}
+        }
+    }
+
+    cx.error(ty, "unsupported element type of CxxSet");
+}
+
+fn check_type_borrowed(cx: &mut Check, ty: &Ty1) {
+    if let Type::Ident(ident) = &ty.inner {
+        if cx.types.structs.contains_key(ident)
+            || cx.types.cxx.contains(ident)
+            || cx.types.rust.contains(ident)
+            || ident == CxxString
+        {
+            return;
+        }
+    }
+
+    cx.error(ty, "unsupported target type of Borrowed");
+}
+
+fn check_type_optional(cx: &mut Check, ty: &Ty1) {
+    match &ty.inner {
+        Type::UniquePtr(_) => return,
+        inner if is_optional_primitive(inner) => return,
+        Type::Ref(r) => {
+            if let Type::Ident(ident) = &r.inner {
+                if Atom::from(ident) != Some(RustString) {
+                    return;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    cx.error(
+        ty,
+        "unsupported target type of Option; only Option<UniquePtr<T>>, Option<&T> and Option of a primitive are supported",
+    );
+}
+
 fn check_type_ref(cx: &mut Check, ty: &Ref) {
+    if ty.inner == Char {
+        cx.error(ty, CHAR_BY_VALUE);
+        return;
+    }
+
+    if ty.pinned {
+        if let Type::Ident(ident) = &ty.inner {
+            if ident == CxxString || ident == CxxU16String || cx.types.cxx.contains(ident) {
+                return;
+            }
+        }
+        cx.error(
+            ty,
+            "Pin<&mut T> is only supported for CxxString, CxxU16String and opaque C++ types",
+        );
+        return;
+    }
+
+    if ty.mutability.is_some() {
+        if let Type::Ident(ident) = &ty.inner {
+            if cx.types.cxx.contains(ident) {
+                cx.error(ty, MUT_REF_REQUIRES_PIN);
+                return;
+            }
+        }
+    }
+
     match ty.inner {
-        Type::Fn(_) | Type::Void(_) => {}
+        // C++ holds an Arc as the pointer to its contents, not as the Arc
+        // itself, so there is nothing for a reference to point at.
+        Type::Fn(_)
+        | Type::Void(_)
+        | Type::Optional(_)
+        | Type::Tuple(_)
+        | Type::Array(_)
+        | Type::RustArc(_)
+        | Type::SliceBox(_) => {}
         _ => return,
     }
 
     cx.error(ty, "unsupported reference type");
 }
 
+fn check_type_slice(cx: &mut Check, ty: &Slice) {
+    if ty.inner == Char {
+        cx.error(ty, CHAR_BY_VALUE);
+        return;
+    }
+
+    if let Type::Ident(_) = &ty.inner {
+        // The elements are viewed in place from the other language, so they
+        // must not own anything with its own drop or move semantics.
+        if is_trivially_copyable(cx, &ty.inner, &mut Vec::new()) {
+            return;
+        }
+        let desc = describe(cx, &ty.inner);
+        let msg = format!(
+            "slice of {} is not supported; elements must be trivially copyable",
+            desc
+        );
+        cx.error(ty, msg);
+        return;
+    }
+
+    cx.error(ty, "unsupported element type of slice");
+}
+
+// The element type itself is checked as a slice. Slices of primitives are freed
+// by the runtime, which does not cover usize because size_t may be the same
+// C++ type as uint64_t.
+fn check_type_slice_box(cx: &mut Check, ty: &Ty1) {
+    if let Type::Slice(slice) = &ty.inner {
+        if let Type::Ident(ident) = &slice.inner {
+            match Atom::from(ident) {
+                None | Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(I8) | Some(I16)
+                | Some(I32) | Some(I64) | Some(F32) | Some(F64) => return,
+                _ => {}
+            }
+        }
+    }
+
+    cx.error(ty, "unsupported element type of Box<[T]>");
+}
+
+fn check_type_tuple(cx: &mut Check, ty: &Tuple) {
+    if ty.elems.len() < 2 || ty.elems.len() > 3 {
+        cx.error(ty, "only tuples of 2 or 3 elements are supported");
+    }
+    for elem in &ty.elems {
+        if elem == Char {
+            cx.error(elem, "char is not supported as a tuple element yet");
+            continue;
+        }
+        if let Type::Ident(ident) = elem {
+            match Atom::from(ident) {
+                Some(Char) | Some(RustString) | Some(CxxString) | Some(CxxU16String) => {}
+                Some(_) => continue,
+                None if cx.types.structs.contains_key(ident) => continue,
+                None => {}
+            }
+        }
+        cx.error(
+            elem,
+            "unsupported tuple element type; elements must be primitives or shared structs",
+        );
+    }
+}
+
+fn check_type_array(cx: &mut Check, ty: &Array) {
+    if ty.len == 0 {
+        cx.error(&ty.len_token, "zero-length arrays are not supported");
+    }
+
+    if let Type::Ident(_) = &ty.inner {
+        // Laid out as a C array inside the struct, so the elements must be
+        // plain bytes that both languages can copy.
+        if is_trivially_copyable(cx, &ty.inner, &mut Vec::new()) {
+            return;
+        }
+        let desc = describe(cx, &ty.inner);
+        let msg = format!(
+            "array of {} is not supported; elements must be trivially copyable",
+            desc
+        );
+        cx.error(ty, msg);
+        return;
+    }
+
+    cx.error(ty, "unsupported element type of array");
+}
+
+// Moved across as the Rust [T; N], which only has the layout of std::array<T, N>
+// for a nonzero length; std::array<T, 0> still occupies a byte.
+fn check_type_cxx_array(cx: &mut Check, ty: &Array) {
+    if ty.len == 0 {
+        cx.error(&ty.len_token, "zero-length Array is not supported");
+    }
+
+    if let Type::Ident(_) = &ty.inner {
+        if is_trivially_copyable(cx, &ty.inner, &mut Vec::new()) {
+            return;
+        }
+        let desc = describe(cx, &ty.inner);
+        let msg = format!(
+            "Array of {} is not supported; elements must be trivially copyable",
+            desc
+        );
+        cx.error(ty, msg);
+        return;
+    }
+
+    cx.error(ty, "unsupported element type of Array");
+}
+
+fn is_trivially_copyable<'a>(cx: &Check<'a>, ty: &'a Type, visiting: &mut Vec<&'a Ident>) -> bool {
+    let ident = match ty {
+        Type::Ident(ident) => ident,
+        Type::Array(array) => return is_trivially_copyable(cx, &array.inner, visiting),
+        _ => return false,
+    };
+    if let Some(strct) = cx.types.structs.get(ident) {
+        // A struct containing itself by value is reported by
+        // check_recursive_structs.
+        if visiting.contains(&ident) {
+            return true;
+        }
+        visiting.push(ident);
+        let mut fields = strct.fields.iter();
+        let trivial = fields.all(|field| is_trivially_copyable(cx, &field.ty, visiting));
+        visiting.pop();
+        return trivial;
+    }
+    match Atom::from(ident) {
+        Some(RustString) | Some(CxxString) | Some(CxxU16String) => false,
+        Some(_) => true,
+        None => cx.types.enums.contains_key(ident),
+    }
+}
+
 fn check_api_struct(cx: &mut Check, strct: &Struct) {
     if strct.fields.is_empty() {
         let span = span_for_struct_error(strct);
         cx.error(span, "structs without any fields are not supported");
     }
 
+    let derives_default = strct.derives.iter().any(|derive| derive == "Default");
+    if derives_default && strct.union_token.is_some() {
+        let span = span_for_struct_error(strct);
+        cx.error(span, "derive(Default) is not supported on a union");
+    }
+    let derives_partial_eq = strct.derives.iter().any(|derive| derive == "PartialEq");
+    if derives_partial_eq && strct.union_token.is_some() {
+        let span = span_for_struct_error(strct);
+        cx.error(span, "derive(PartialEq) is not supported on a union");
+    }
+    let derives_hash = strct.derives.iter().any(|derive| derive == "Hash");
+    if derives_hash && strct.union_token.is_some() {
+        let span = span_for_struct_error(strct);
+        cx.error(span, "derive(Hash) is not supported on a union");
+    }
+
+    let has_array = strct
+        .fields
+        .iter()
+        .any(|field| matches!(field.ty, Type::Array(_)));
+    if has_array && strct.constructor.is_some() {
+        let span = span_for_struct_error(strct);
+        cx.error(
+            span,
+            "#[cxx_constructor] is not supported on a struct with an array field",
+        );
+    }
+
     for field in &strct.fields {
         if is_unsized(cx, &field.ty) {
             let desc = describe(cx, &field.ty);
@@ -125,23 +562,561 @@ fn check_api_struct(cx: &mut Check, strct: &Struct) {
                 "function pointers in a struct field are not implemented yet",
             );
         }
+        if let Type::Optional(_) = field.ty {
+            cx.error(field, "Option in a struct field is not supported yet");
+        }
+        if let Type::SliceBox(_) = field.ty {
+            cx.error(field, BOX_SLICE_POSITION);
+        }
+        if let Type::Tuple(_) = field.ty {
+            cx.error(field, "tuple in a struct field is not supported");
+        }
+        if let Type::RustArc(_) = field.ty {
+            cx.error(field, "Arc in a struct field is not supported yet");
+        }
+        if let Type::RustVec(_) = field.ty {
+            cx.error(field, "Vec in a struct field is not supported yet");
+        }
+        if let Type::CxxArray(_) = field.ty {
+            cx.error(
+                field,
+                "Array in a struct field is not supported; use [T; N]",
+            );
+        }
+        if field.ty == Char {
+            cx.error(field, CHAR_BY_VALUE);
+        }
+        if strct.fixed_width.is_some() && !is_fixed_width(cx, &field.ty) {
+            let desc = describe(cx, &field.ty);
+            let msg = format!(
+                "field `{}` of type {} has a different layout on 32-bit and 64-bit targets",
+                field.ident, desc,
+            );
+            cx.error(field, msg);
+        }
+        if derives_default && !has_default(cx, &field.ty) {
+            let desc = describe(cx, &field.ty);
+            let msg = format!(
+                "derive(Default) requires field `{}` of type {} to have a default value on both sides",
+                field.ident, desc,
+            );
+            cx.error(field, msg);
+        }
+        if derives_partial_eq && strct.union_token.is_none() && !has_same_eq(cx, &field.ty) {
+            let desc = describe(cx, &field.ty);
+            let msg = format!(
+                "derive(PartialEq) requires field `{}` of type {} to compare the same way on both sides",
+                field.ident, desc,
+            );
+            cx.error(field, msg);
+        }
+        if derives_hash && strct.union_token.is_none() && !is_hashable(cx, &field.ty) {
+            let desc = describe(cx, &field.ty);
+            let msg = format!(
+                "derive(Hash) requires field `{}` of type {} to be hashable on both sides",
+                field.ident, desc,
+            );
+            cx.error(field, msg);
+        }
+        if strct.union_token.is_some() && !is_union_field(cx, &field.ty) {
+            let desc = describe(cx, &field.ty);
+            let msg = format!(
+                "union field `{}` of type {} must be a fixed-width primitive, or a Copy struct or union of them",
+                field.ident, desc,
+            );
+            cx.error(field, msg);
+        }
+    }
+}
+
+// Fields that C++ can value-initialize to what the Rust derive(Default) would
+// produce. A rust::Box has no empty state, and a nested shared struct only
+// agrees if it derives Default too.
+fn has_default(cx: &mut Check, ty: &Type) -> bool {
+    match ty {
+        Type::Ident(ident) => match cx.types.structs.get(ident) {
+            Some(strct) => strct.derives.iter().any(|derive| derive == "Default"),
+            None => !cx.types.enums.contains_key(ident),
+        },
+        // Neither a Box nor a &T has an empty value to start from.
+        Type::RustBox(_) | Type::Ref(_) => false,
+        // Rust only implements Default for arrays of up to 32 elements.
+        Type::Array(array) => array.len <= 32 && has_default(cx, &array.inner),
+        _ => true,
+    }
+}
+
+// Fields whose C++ operator== matches the Rust PartialEq. Smart pointers are
+// left out: Rust compares what they point to, C++ compares the addresses.
+fn has_same_eq(cx: &mut Check, ty: &Type) -> bool {
+    match ty {
+        Type::Ident(ident) => match cx.types.structs.get(ident) {
+            Some(strct) => strct.derives.iter().any(|derive| derive == "PartialEq"),
+            None => Atom::from(ident).is_some() || cx.types.enums.contains_key(ident),
+        },
+        _ => false,
+    }
+}
+
+// Fields that the generated std::hash specialization knows how to hash, which
+// are also the ones Rust implements Hash for. Floats are neither; a nested
+// shared struct needs its own derive(Hash) to get a specialization.
+fn is_hashable(cx: &mut Check, ty: &Type) -> bool {
+    match ty {
+        Type::Ident(ident) => match cx.types.structs.get(ident) {
+            Some(strct) => strct.derives.iter().any(|derive| derive == "Hash"),
+            None => match Atom::from(ident) {
+                Some(Bool) | Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(Usize)
+                | Some(I8) | Some(I16) | Some(I32) | Some(I64) | Some(Isize) | Some(RustString) => {
+                    true
+                }
+                Some(_) => false,
+                None => cx.types.enums.contains_key(ident),
+            },
+        },
+        _ => false,
+    }
+}
+
+// Rust only allows Copy fields in a union, and keeping to fixed-width ones lets
+// the generated C++ assert the exact layout.
+fn is_union_field(cx: &mut Check, ty: &Type) -> bool {
+    if !is_fixed_width(cx, ty) {
+        return false;
+    }
+    match ty {
+        Type::Ident(ident) => match cx.types.structs.get(ident) {
+            Some(strct) => strct.derives.iter().any(|derive| derive == "Copy"),
+            None => true,
+        },
+        _ => false,
+    }
+}
+
+// A shared struct that holds itself by value, directly or through other shared
+// structs, would have infinite size. Each cycle is reported at the field that
+// closes it; a Box or UniquePtr in between gives the struct a finite size.
+fn check_recursive_structs(cx: &mut Check) {
+    let mut finished = HashSet::new();
+    for api in cx.apis {
+        if let Api::Struct(strct) = api {
+            let mut path = Vec::new();
+            visit_struct_fields(cx, strct, &mut path, &mut finished);
+        }
+    }
+}
+
+fn visit_struct_fields<'a>(
+    cx: &mut Check<'a>,
+    strct: &'a Struct,
+    path: &mut Vec<&'a Ident>,
+    finished: &mut HashSet<&'a Ident>,
+) {
+    if finished.contains(&strct.ident) {
+        return;
+    }
+    path.push(&strct.ident);
+    for field in &strct.fields {
+        let ident = match &field.ty {
+            Type::Ident(ident) => ident,
+            Type::Array(array) => match &array.inner {
+                Type::Ident(ident) => ident,
+                _ => continue,
+            },
+            _ => continue,
+        };
+        if let Some(start) = path.iter().position(|visited| *visited == ident) {
+            let mut cycle = String::new();
+            for visited in &path[start..] {
+                cycle += &format!("{} -> ", visited);
+            }
+            cycle += &ident.to_string();
+            let msg = format!(
+                "recursive struct `{}` has infinite size ({}); use Box or UniquePtr to add indirection",
+                ident, cycle,
+            );
+            cx.error(field, msg);
+        } else if let Some(inner) = cx.types.structs.get(ident) {
+            visit_struct_fields(cx, inner, path, finished);
+        }
+    }
+    path.pop();
+    finished.insert(&strct.ident);
+}
+
+fn is_fixed_width(cx: &mut Check, ty: &Type) -> bool {
+    let ident = match ty {
+        Type::Ident(ident) => ident,
+        Type::Array(array) => return is_fixed_width(cx, &array.inner),
+        _ => return false,
+    };
+    match Atom::from(ident) {
+        Some(Bool) | Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(I8) | Some(I16)
+        | Some(I32) | Some(I64) | Some(F32) | Some(F64) => true,
+        // Older Rust releases align i128 to 8 bytes where C aligns __int128
+        // to 16, so the layout is not the same everywhere.
+        Some(Char) | Some(U128) | Some(I128) | Some(Usize) | Some(Isize) | Some(CxxString)
+        | Some(CxxU16String) | Some(RustString) => false,
+        None => match cx.types.structs.get(ident) {
+            Some(strct) => strct.fixed_width.is_some() || strct.union_token.is_some(),
+            None => match cx.types.enums.get(ident) {
+                Some(enm) => enm.repr.is_some(),
+                None => false,
+            },
+        },
+    }
+}
+
+fn check_api_enum(cx: &mut Check, enm: &Enum) {
+    if enm.variants.is_empty() {
+        let enum_token = enm.enum_token;
+        let ident = &enm.ident;
+        let span = quote!(#enum_token #ident);
+        cx.error(span, "enums without any variants are not supported");
+    }
+
+    let (bits, signed) = match enm.repr.as_ref().and_then(Atom::from) {
+        Some(U8) => (8, false),
+        Some(U16) => (16, false),
+        Some(U32) => (32, false),
+        Some(U64) => (64, false),
+        Some(I8) => (8, true),
+        Some(I16) => (16, true),
+        Some(I64) => (64, true),
+        // Also #[repr(C)], which is a C int on every supported target.
+        _ => (32, true),
+    };
+    let (min, max): (i128, i128) = if signed {
+        (-(1 << (bits - 1)), (1 << (bits - 1)) - 1)
+    } else {
+        (0, (1 << bits) - 1)
+    };
+    let mut discriminants = HashSet::new();
+    for variant in &enm.variants {
+        let discriminant = variant.discriminant;
+        if discriminant < min || discriminant > max {
+            let repr = match &enm.repr {
+                Some(repr) => repr.to_string(),
+                None => "c_int".to_owned(),
+            };
+            let msg = format!(
+                "discriminant value {} does not fit in the enum's repr {}",
+                discriminant, repr,
+            );
+            cx.error(&variant.ident, msg);
+        }
+        if !discriminants.insert(discriminant) {
+            let msg = format!(
+                "discriminant value {} assigned more than once",
+                discriminant,
+            );
+            cx.error(&variant.ident, msg);
+        }
+    }
+}
+
+fn check_api_exception_enum(cx: &mut Check, enm: &ExceptionEnum) {
+    // Parsing only produces an exception enum if some variant is mapped.
+    let fallback = enm
+        .variants
+        .iter()
+        .filter(|v| v.exception.is_none())
+        .count();
+    if fallback != 1 {
+        let enum_token = enm.enum_token;
+        let ident = &enm.ident;
+        let span = quote!(#enum_token #ident);
+        cx.error(
+            span,
+            "enum requires exactly one variant without #[cxx_exception(...)] to catch any other exception",
+        );
+    }
+}
+
+fn check_api_rust_type(cx: &mut Check, ety: &ExternType) {
+    if let Some(debug) = &ety.debug {
+        cx.error(debug, "#[cxx_debug] is only supported on extern C++ types");
+    }
+    if let Some(placement) = &ety.placement {
+        cx.error(
+            placement,
+            "#[cxx_placement] is only supported on extern C++ types",
+        );
     }
 }
 
 fn check_api_fn(cx: &mut Check, efn: &ExternFn) {
+    if let Some(link_section) = &efn.link_section {
+        if efn.lang == Lang::Cxx {
+            cx.error(
+                link_section,
+                "link_section is only supported on extern \"Rust\" functions",
+            );
+        }
+    }
+
+    if let Some(abi) = &efn.abi {
+        match abi.value().as_str() {
+            "C" | "system" | "stdcall" => {}
+            _ => cx.error(
+                abi,
+                "unsupported calling convention; expected \"C\", \"system\" or \"stdcall\"",
+            ),
+        }
+    }
+
+    if let Some(receiver) = &efn.receiver {
+        if receiver.pinned && efn.lang == Lang::Rust {
+            cx.error(
+                receiver,
+                "Pin<&mut Self> receiver is only supported on C++ methods so far",
+            );
+        }
+        if !receiver.pinned
+            && receiver.mutability.is_some()
+            && cx.types.cxx.contains(&receiver.ident)
+        {
+            cx.error(receiver, MUT_REF_REQUIRES_PIN);
+        }
+    }
+
+    if let Some(template) = &efn.template {
+        if efn.receiver.is_none() {
+            cx.error(
+                &template.ident,
+                "template instantiation is only supported on C++ methods so far",
+            );
+        }
+        for arg in &template.args {
+            if let Type::Ident(_) = arg {
+                continue;
+            }
+            cx.error(arg, "unsupported template argument");
+        }
+    }
+
+    if let Some(receiver) = &efn.receiver {
+        if efn.lang == Lang::Rust {
+            if !cx.types.rust.contains(&receiver.ident) {
+                cx.error(
+                    &receiver.ident,
+                    "unsupported receiver type; Rust methods are only supported on opaque Rust types",
+                );
+            }
+        } else if !cx.types.cxx.contains(&receiver.ident) {
+            cx.error(
+                &receiver.ident,
+                "unsupported receiver type; C++ methods are only supported on opaque C++ types",
+            );
+        }
+    }
+
+    if let Some(error_convert) = &efn.error_convert {
+        if efn.lang == Lang::Cxx {
+            cx.error(
+                error_convert,
+                "cxx_error_convert is only supported on extern \"Rust\" functions",
+            );
+        } else if !efn.throws {
+            cx.error(
+                error_convert,
+                "cxx_error_convert requires a function that returns Result",
+            );
+        }
+    }
+
+    if let Some(pure) = &efn.pure {
+        // The C++ optimizer is allowed to elide or merge calls to a pure
+        // function, so it must not write through any of its parameters.
+        match &efn.ret {
+            Some(ret) if !efn.throws => {
+                if cx.types.needs_indirect_abi(ret) {
+                    let desc = describe(cx, ret);
+                    let msg = format!(
+                        "#[cxx_pure] is not supported on a function returning {}",
+                        desc
+                    );
+                    cx.error(pure, msg);
+                }
+            }
+            _ => cx.error(
+                pure,
+                "#[cxx_pure] requires a function that returns a value and no Result",
+            ),
+        }
+        let mutable_receiver = efn
+            .receiver
+            .as_ref()
+            .map_or(false, |receiver| receiver.mutability.is_some());
+        let mutable_arg = efn.args.iter().any(|arg| match &arg.ty {
+            Type::Ref(ty) | Type::SliceRef(ty) => ty.mutability.is_some(),
+            _ => false,
+        });
+        if mutable_receiver || mutable_arg {
+            cx.error(
+                pure,
+                "#[cxx_pure] is not supported on a function taking a mutable reference",
+            );
+        }
+    }
+
+    if let Some(non_reentrant) = &efn.non_reentrant {
+        if efn.lang == Lang::Cxx {
+            cx.error(
+                non_reentrant,
+                "#[cxx_non_reentrant] is only supported on Rust functions",
+            );
+        }
+    }
+
+    if let Some(init_once) = &efn.init_once {
+        // The result of the one call is cached for every later caller, so
+        // there must be nothing for those callers to pass in.
+        if efn.lang == Lang::Rust {
+            cx.error(
+                init_once,
+                "#[cxx_init_once] is only supported on C++ functions",
+            );
+        }
+        if efn.receiver.is_some() || !efn.args.is_empty() {
+            cx.error(
+                init_once,
+                "#[cxx_init_once] requires a function without arguments",
+            );
+        }
+        match &efn.ret {
+            _ if efn.throws => cx.error(
+                init_once,
+                "#[cxx_init_once] is not supported on a function returning Result",
+            ),
+            None => {}
+            Some(ret @ Type::Ident(_)) if !cx.types.needs_indirect_abi(ret) => {}
+            Some(ret) => {
+                let desc = describe(cx, ret);
+                let msg = format!(
+                    "#[cxx_init_once] is not supported on a function returning {}",
+                    desc
+                );
+                cx.error(init_once, msg);
+            }
+        }
+    }
+
+    if let Some(time_point) = &efn.time_point {
+        if efn.lang == Lang::Rust {
+            cx.error(
+                time_point,
+                "#[cxx_time_point] is only supported on C++ functions",
+            );
+        }
+        if !matches!(&efn.ret, Some(Type::Ident(ret)) if Atom::from(ret) == Some(I64)) {
+            cx.error(
+                time_point,
+                "#[cxx_time_point] requires a function that returns i64",
+            );
+        }
+    }
+
+    for arg in &efn.args {
+        if !efn.time_point_args.contains(&arg.ident) {
+            continue;
+        }
+        if efn.lang == Lang::Rust {
+            cx.error(
+                &arg.ident,
+                "#[cxx_time_point] is only supported on C++ functions",
+            );
+        }
+        if !matches!(&arg.ty, Type::Ident(ty) if Atom::from(ty) == Some(I64)) {
+            cx.error(
+                &arg.ident,
+                "#[cxx_time_point] requires an argument of type i64",
+            );
+        }
+    }
+
+    if let Some(error_type) = &efn.error_type {
+        if efn.lang == Lang::Rust {
+            if !cx.types.structs.contains_key(error_type) {
+                cx.error(
+                    error_type,
+                    "error type of an extern \"Rust\" function must be a shared struct",
+                );
+            }
+        } else if !cx.types.exceptions.contains_key(error_type) {
+            cx.error(
+                error_type,
+                "error type must be an enum of #[cxx_exception] variants declared in the bridge",
+            );
+        }
+    }
+
     for arg in &efn.args {
         if is_unsized(cx, &arg.ty) {
             let desc = describe(cx, &arg.ty);
             let msg = format!("passing {} by value is not supported", desc);
             cx.error(arg, msg);
         }
-        if let Type::Fn(_) = arg.ty {
+        if let Type::Optional(ty) = &arg.ty {
+            if let Type::UniquePtr(_) = ty.inner {
+                cx.error(
+                    arg,
+                    "Option<UniquePtr<T>> is only supported as a return type so far",
+                );
+            } else if is_optional_primitive(&ty.inner) && efn.lang == Lang::Rust {
+                cx.error(
+                    arg,
+                    "Option of a primitive is only supported as an argument of a C++ function so far",
+                );
+            }
+        }
+        if let Type::Fn(f) = &arg.ty {
             if efn.lang == Lang::Rust {
                 cx.error(
                     arg,
                     "passing a function pointer from C++ to Rust is not implemented yet",
                 );
             }
+            let tuple = f.args.iter().map(|arg| &arg.ty).chain(&f.ret);
+            for ty in tuple.clone().filter(|ty| matches!(ty, Type::Tuple(_))) {
+                cx.error(
+                    ty,
+                    "tuples are only supported as the return type of a C++ function",
+                );
+            }
+            for ty in tuple.clone().filter(|ty| match ty {
+                Type::Optional(ty) => is_optional_primitive(&ty.inner),
+                _ => false,
+            }) {
+                cx.error(
+                    ty,
+                    "Option of a primitive is only supported as an argument of a C++ function so far",
+                );
+            }
+            for ty in tuple.clone().filter(|ty| matches!(ty, Type::SliceBox(_))) {
+                cx.error(ty, BOX_SLICE_POSITION);
+            }
+            for ty in tuple.clone().filter(|ty| matches!(ty, Type::Array(_))) {
+                cx.error(ty, ARRAY_POSITION);
+            }
+            for ty in tuple.filter(|ty| matches!(ty, Type::CxxArray(_))) {
+                cx.error(
+                    ty,
+                    "Array in a function pointer signature is not supported yet",
+                );
+            }
+        }
+        if let Type::Tuple(_) = arg.ty {
+            cx.error(
+                arg,
+                "tuples are only supported as the return type of a C++ function",
+            );
+        }
+        if let Type::SliceBox(_) = arg.ty {
+            cx.error(arg, BOX_SLICE_POSITION);
+        }
+        if let Type::Array(_) = arg.ty {
+            cx.error(arg, ARRAY_POSITION);
         }
     }
 
@@ -154,17 +1129,44 @@ fn check_api_fn(cx: &mut Check, efn: &ExternFn) {
         if let Type::Fn(_) = ty {
             cx.error(ty, "returning a function pointer is not implemented yet");
         }
+        if let Type::Optional(optional) = ty {
+            if is_optional_primitive(&optional.inner) {
+                cx.error(ty, "returning Option of a primitive is not implemented yet");
+            }
+        }
+        if let Type::Tuple(_) = ty {
+            if efn.lang == Lang::Rust {
+                cx.error(
+                    ty,
+                    "returning a tuple from Rust to C++ is not implemented yet",
+                );
+            }
+        }
+        if let Type::SliceBox(_) = ty {
+            if efn.lang == Lang::Cxx {
+                cx.error(ty, BOX_SLICE_POSITION);
+            }
+        }
+        if let Type::Array(_) = ty {
+            cx.error(ty, ARRAY_POSITION);
+        }
     }
 }
 
 fn check_mut_return_restriction(cx: &mut Check, efn: &ExternFn) {
-    match &efn.ret {
-        Some(Type::Ref(ty)) if ty.mutability.is_some() => {}
+    match efn.ret.as_ref().map(optional_ref) {
+        Some(Type::Ref(ty)) | Some(Type::SliceRef(ty)) if ty.mutability.is_some() => {}
         _ => return,
     }
 
+    if let Some(receiver) = &efn.receiver {
+        if receiver.mutability.is_some() {
+            return;
+        }
+    }
+
     for arg in &efn.args {
-        if let Type::Ref(ty) = &arg.ty {
+        if let Type::Ref(ty) | Type::SliceRef(ty) = optional_ref(&arg.ty) {
             if ty.mutability.is_some() {
                 return;
             }
@@ -178,14 +1180,20 @@ fn check_mut_return_restriction(cx: &mut Check, efn: &ExternFn) {
 }
 
 fn check_multiple_arg_lifetimes(cx: &mut Check, efn: &ExternFn) {
-    match &efn.ret {
-        Some(Type::Ref(_)) => {}
+    match efn.ret.as_ref().map(optional_ref) {
+        Some(Type::Ref(_)) | Some(Type::SliceRef(_)) | Some(Type::Borrowed(_)) => {}
         _ => return,
     }
 
+    // As in Rust's elision rules, a returned reference borrows from the
+    // receiver of a method.
+    if efn.receiver.is_some() {
+        return;
+    }
+
     let mut reference_args = 0;
     for arg in &efn.args {
-        if let Type::Ref(_) = &arg.ty {
+        if let Type::Ref(_) | Type::SliceRef(_) | Type::Borrowed(_) = optional_ref(&arg.ty) {
             reference_args += 1;
         }
     }
@@ -198,13 +1206,41 @@ fn check_multiple_arg_lifetimes(cx: &mut Check, efn: &ExternFn) {
     }
 }
 
+// Option<&T> borrows exactly like the &T inside it.
+// Crosses as a nullable pointer to the value and becomes std::optional in C++.
+fn is_optional_primitive(ty: &Type) -> bool {
+    match ty {
+        Type::Ident(ident) => match Atom::from(ident) {
+            Some(Bool) | Some(U8) | Some(U16) | Some(U32) | Some(U64) | Some(Usize) | Some(I8)
+            | Some(I16) | Some(I32) | Some(I64) | Some(Isize) | Some(F32) | Some(F64) => true,
+            Some(Char) | Some(U128) | Some(I128) | Some(CxxString) | Some(CxxU16String)
+            | Some(RustString) | None => false,
+        },
+        _ => false,
+    }
+}
+
+fn optional_ref(ty: &Type) -> &Type {
+    if let Type::Optional(optional) = ty {
+        if let inner @ Type::Ref(_) = &optional.inner {
+            return inner;
+        }
+    }
+    ty
+}
+
 fn is_unsized(cx: &mut Check, ty: &Type) -> bool {
     let ident = match ty {
         Type::Ident(ident) => ident,
-        Type::Void(_) => return true,
+        Type::Void(_) | Type::Slice(_) => return true,
+        // Opaque to Rust, like an extern C++ type.
+        Type::CxxVector(_) | Type::CxxMap(_) | Type::CxxSet(_) => return true,
         _ => return false,
     };
-    ident == CxxString || cx.types.cxx.contains(ident) || cx.types.rust.contains(ident)
+    ident == CxxString
+        || ident == CxxU16String
+        || cx.types.cxx.contains(ident)
+        || cx.types.rust.contains(ident)
 }
 
 fn span_for_struct_error(strct: &Struct) -> TokenStream {
@@ -229,23 +1265,47 @@ fn combine_errors(errors: Vec<Error>) -> Result<()> {
 fn describe(cx: &mut Check, ty: &Type) -> String {
     match ty {
         Type::Ident(ident) => {
-            if cx.types.structs.contains_key(ident) {
-                "struct".to_owned()
+            if let Some(strct) = cx.types.structs.get(ident) {
+                if strct.union_token.is_some() {
+                    "union".to_owned()
+                } else {
+                    "struct".to_owned()
+                }
+            } else if cx.types.enums.contains_key(ident) {
+                "enum".to_owned()
             } else if cx.types.cxx.contains(ident) {
                 "C++ type".to_owned()
             } else if cx.types.rust.contains(ident) {
                 "opaque Rust type".to_owned()
             } else if Atom::from(ident) == Some(CxxString) {
                 "C++ string".to_owned()
+            } else if Atom::from(ident) == Some(CxxU16String) {
+                "C++ UTF-16 string".to_owned()
             } else {
                 ident.to_string()
             }
         }
         Type::RustBox(_) => "Box".to_owned(),
+        Type::RustArc(_) => "Arc".to_owned(),
+        Type::RustVec(_) => "Vec".to_owned(),
         Type::UniquePtr(_) => "unique_ptr".to_owned(),
+        Type::UniqueArray(_) => "unique_ptr array".to_owned(),
+        Type::SharedPtr(_) => "shared_ptr".to_owned(),
+        Type::CxxVector(_) => "C++ vector".to_owned(),
+        Type::CxxMap(_) => "C++ map".to_owned(),
+        Type::CxxSet(_) => "C++ set".to_owned(),
+        Type::Borrowed(_) => "Borrowed".to_owned(),
+        Type::Optional(_) => "Option".to_owned(),
         Type::Ref(_) => "reference".to_owned(),
         Type::Str(_) => "&str".to_owned(),
         Type::Fn(_) => "function pointer".to_owned(),
+        Type::Slice(_) => "slice".to_owned(),
+        Type::SliceRef(r) if r.mutability.is_some() => "&mut [T]".to_owned(),
+        Type::SliceRef(_) => "&[T]".to_owned(),
+        Type::SliceBox(_) => "Box<[T]>".to_owned(),
+        Type::Tuple(_) => "tuple".to_owned(),
+        Type::Array(_) => "array".to_owned(),
+        Type::CxxArray(_) => "std::array".to_owned(),
         Type::Void(_) => "()".to_owned(),
     }
 }
@@ -1,6 +1,7 @@
 use std::fmt::{self, Display};
 
 #[derive(Copy, Clone)]
+#[allow(dead_code)] // label and note are only used by the C++ code generator
 pub struct Error {
     pub msg: &'static str,
     pub label: Option<&'static str>,
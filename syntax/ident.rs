@@ -1,6 +1,6 @@
 use crate::syntax::{error, Api};
 use proc_macro2::Ident;
-use syn::{Error, Result};
+use syn::{Error, LitStr, Result};
 
 pub(crate) fn check(ident: &Ident) -> Result<()> {
     let s = ident.to_string();
@@ -13,6 +13,29 @@ pub(crate) fn check(ident: &Ident) -> Result<()> {
     }
 }
 
+// The string form of the bridge namespace, as in `namespace = "a::inline b"`,
+// with one identifier per `::`-separated segment and whether that segment was
+// marked `inline`.
+pub(crate) fn split_namespace(lit: &LitStr) -> Result<Vec<(bool, Ident)>> {
+    let mut segments = Vec::new();
+    for segment in lit.value().split("::") {
+        let mut words = segment.split_whitespace();
+        let (inline, name) = match (words.next(), words.next(), words.next()) {
+            (Some("inline"), Some(name), None) => (true, name),
+            (Some(name), None, None) => (false, name),
+            _ => return Err(Error::new(lit.span(), "invalid namespace segment")),
+        };
+        let mut ident: Ident = match syn::parse_str(name) {
+            Ok(ident) => ident,
+            Err(_) => return Err(Error::new(lit.span(), "invalid namespace segment")),
+        };
+        ident.set_span(lit.span());
+        check(&ident)?;
+        segments.push((inline, ident));
+    }
+    Ok(segments)
+}
+
 pub(crate) fn check_all(apis: &[Api], errors: &mut Vec<Error>) {
     for api in apis {
         match api {
@@ -23,11 +46,29 @@ pub(crate) fn check_all(apis: &[Api], errors: &mut Vec<Error>) {
                     errors.extend(check(&field.ident).err());
                 }
             }
+            Api::Enum(enm) => {
+                errors.extend(check(&enm.ident).err());
+                for variant in &enm.variants {
+                    errors.extend(check(&variant.ident).err());
+                }
+            }
+            Api::ExceptionEnum(enm) => {
+                errors.extend(check(&enm.ident).err());
+                for variant in &enm.variants {
+                    errors.extend(check(&variant.ident).err());
+                }
+            }
             Api::CxxType(ety) | Api::RustType(ety) => {
                 errors.extend(check(&ety.ident).err());
+                if let Some(cxx_name) = &ety.cxx_name {
+                    errors.extend(check(cxx_name).err());
+                }
             }
             Api::CxxFunction(efn) | Api::RustFunction(efn) => {
                 errors.extend(check(&efn.ident).err());
+                if let Some(cxx_name) = &efn.cxx_name {
+                    errors.extend(check(cxx_name).err());
+                }
                 for arg in &efn.args {
                     errors.extend(check(&arg.ident).err());
                 }
@@ -1,4 +1,5 @@
-use crate::syntax::{ExternFn, Receiver, Ref, Signature, Ty1, Type};
+use crate::syntax::{Array, ExternFn, Receiver, Ref, Signature, Slice, Tuple, Ty1, Ty2, Type};
+use proc_macro2::Ident;
 use std::hash::{Hash, Hasher};
 use std::mem;
 use std::ops::Deref;
@@ -11,16 +12,37 @@ impl Deref for ExternFn {
     }
 }
 
+impl ExternFn {
+    pub fn cxx_ident(&self) -> &Ident {
+        self.cxx_name.as_ref().unwrap_or(&self.ident)
+    }
+}
+
 impl Hash for Type {
     fn hash<H: Hasher>(&self, state: &mut H) {
         mem::discriminant(self).hash(state);
         match self {
             Type::Ident(t) => t.hash(state),
             Type::RustBox(t) => t.hash(state),
+            Type::RustArc(t) => t.hash(state),
+            Type::RustVec(t) => t.hash(state),
             Type::UniquePtr(t) => t.hash(state),
+            Type::UniqueArray(t) => t.hash(state),
+            Type::SharedPtr(t) => t.hash(state),
+            Type::CxxVector(t) => t.hash(state),
+            Type::CxxMap(t) => t.hash(state),
+            Type::CxxSet(t) => t.hash(state),
+            Type::Borrowed(t) => t.hash(state),
+            Type::Optional(t) => t.hash(state),
             Type::Ref(t) => t.hash(state),
             Type::Str(t) => t.hash(state),
             Type::Fn(t) => t.hash(state),
+            Type::Slice(t) => t.hash(state),
+            Type::SliceRef(t) => t.hash(state),
+            Type::SliceBox(t) => t.hash(state),
+            Type::Tuple(t) => t.hash(state),
+            Type::Array(t) => t.hash(state),
+            Type::CxxArray(t) => t.hash(state),
             Type::Void(_) => {}
         }
     }
@@ -33,10 +55,25 @@ impl PartialEq for Type {
         match (self, other) {
             (Type::Ident(lhs), Type::Ident(rhs)) => lhs == rhs,
             (Type::RustBox(lhs), Type::RustBox(rhs)) => lhs == rhs,
+            (Type::RustArc(lhs), Type::RustArc(rhs)) => lhs == rhs,
+            (Type::RustVec(lhs), Type::RustVec(rhs)) => lhs == rhs,
             (Type::UniquePtr(lhs), Type::UniquePtr(rhs)) => lhs == rhs,
+            (Type::UniqueArray(lhs), Type::UniqueArray(rhs)) => lhs == rhs,
+            (Type::SharedPtr(lhs), Type::SharedPtr(rhs)) => lhs == rhs,
+            (Type::CxxVector(lhs), Type::CxxVector(rhs)) => lhs == rhs,
+            (Type::CxxMap(lhs), Type::CxxMap(rhs)) => lhs == rhs,
+            (Type::CxxSet(lhs), Type::CxxSet(rhs)) => lhs == rhs,
+            (Type::Borrowed(lhs), Type::Borrowed(rhs)) => lhs == rhs,
+            (Type::Optional(lhs), Type::Optional(rhs)) => lhs == rhs,
             (Type::Ref(lhs), Type::Ref(rhs)) => lhs == rhs,
             (Type::Str(lhs), Type::Str(rhs)) => lhs == rhs,
             (Type::Fn(lhs), Type::Fn(rhs)) => lhs == rhs,
+            (Type::Slice(lhs), Type::Slice(rhs)) => lhs == rhs,
+            (Type::SliceRef(lhs), Type::SliceRef(rhs)) => lhs == rhs,
+            (Type::SliceBox(lhs), Type::SliceBox(rhs)) => lhs == rhs,
+            (Type::Tuple(lhs), Type::Tuple(rhs)) => lhs == rhs,
+            (Type::Array(lhs), Type::Array(rhs)) => lhs == rhs,
+            (Type::CxxArray(lhs), Type::CxxArray(rhs)) => lhs == rhs,
             (Type::Void(_), Type::Void(_)) => true,
             (_, _) => false,
         }
@@ -76,36 +113,156 @@ impl Hash for Ty1 {
     }
 }
 
+impl Eq for Ty2 {}
+
+impl PartialEq for Ty2 {
+    fn eq(&self, other: &Ty2) -> bool {
+        let Ty2 {
+            name,
+            langle: _,
+            first,
+            comma: _,
+            second,
+            rangle: _,
+        } = self;
+        let Ty2 {
+            name: name2,
+            langle: _,
+            first: first2,
+            comma: _,
+            second: second2,
+            rangle: _,
+        } = other;
+        name == name2 && first == first2 && second == second2
+    }
+}
+
+impl Hash for Ty2 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Ty2 {
+            name,
+            langle: _,
+            first,
+            comma: _,
+            second,
+            rangle: _,
+        } = self;
+        name.hash(state);
+        first.hash(state);
+        second.hash(state);
+    }
+}
+
 impl Eq for Ref {}
 
 impl PartialEq for Ref {
     fn eq(&self, other: &Ref) -> bool {
         let Ref {
+            pinned,
             ampersand: _,
             mutability,
             inner,
         } = self;
         let Ref {
+            pinned: pinned2,
             ampersand: _,
             mutability: mutability2,
             inner: inner2,
         } = other;
-        mutability.is_some() == mutability2.is_some() && inner == inner2
+        pinned == pinned2 && mutability.is_some() == mutability2.is_some() && inner == inner2
     }
 }
 
 impl Hash for Ref {
     fn hash<H: Hasher>(&self, state: &mut H) {
         let Ref {
+            pinned,
             ampersand: _,
             mutability,
             inner,
         } = self;
+        pinned.hash(state);
         mutability.is_some().hash(state);
         inner.hash(state);
     }
 }
 
+impl Eq for Slice {}
+
+impl PartialEq for Slice {
+    fn eq(&self, other: &Slice) -> bool {
+        let Slice { bracket: _, inner } = self;
+        let Slice {
+            bracket: _,
+            inner: inner2,
+        } = other;
+        inner == inner2
+    }
+}
+
+impl Hash for Slice {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Slice { bracket: _, inner } = self;
+        inner.hash(state);
+    }
+}
+
+impl Eq for Tuple {}
+
+impl PartialEq for Tuple {
+    fn eq(&self, other: &Tuple) -> bool {
+        let Tuple { paren: _, elems } = self;
+        let Tuple {
+            paren: _,
+            elems: elems2,
+        } = other;
+        elems == elems2
+    }
+}
+
+impl Hash for Tuple {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Tuple { paren: _, elems } = self;
+        elems.hash(state);
+    }
+}
+
+impl Eq for Array {}
+
+impl PartialEq for Array {
+    fn eq(&self, other: &Array) -> bool {
+        let Array {
+            bracket: _,
+            inner,
+            semi_token: _,
+            len,
+            len_token: _,
+        } = self;
+        let Array {
+            bracket: _,
+            inner: inner2,
+            semi_token: _,
+            len: len2,
+            len_token: _,
+        } = other;
+        inner == inner2 && len == len2
+    }
+}
+
+impl Hash for Array {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Array {
+            bracket: _,
+            inner,
+            semi_token: _,
+            len,
+            len_token: _,
+        } = self;
+        inner.hash(state);
+        len.hash(state);
+    }
+}
+
 impl Eq for Signature {}
 
 impl PartialEq for Signature {
@@ -151,18 +308,34 @@ impl Eq for Receiver {}
 
 impl PartialEq for Receiver {
     fn eq(&self, other: &Receiver) -> bool {
-        let Receiver { mutability, ident } = self;
         let Receiver {
+            var: _,
+            pinned,
+            ampersand: _,
+            mutability,
+            ident,
+        } = self;
+        let Receiver {
+            var: _,
+            pinned: pinned2,
+            ampersand: _,
             mutability: mutability2,
             ident: ident2,
         } = other;
-        mutability.is_some() == mutability2.is_some() && ident == ident2
+        pinned == pinned2 && mutability.is_some() == mutability2.is_some() && ident == ident2
     }
 }
 
 impl Hash for Receiver {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        let Receiver { mutability, ident } = self;
+        let Receiver {
+            var: _,
+            pinned,
+            ampersand: _,
+            mutability,
+            ident,
+        } = self;
+        pinned.hash(state);
         mutability.is_some().hash(state);
         ident.hash(state);
     }
@@ -0,0 +1,98 @@
+use crate::syntax::atom::Atom::{self, *};
+use crate::syntax::{Struct, Type, Types};
+use proc_macro2::Ident;
+
+// The #[repr(C)] layout of a shared struct, predicted from its field types so
+// that the Rust side and the C++ side can each be checked against it.
+pub struct Layout {
+    #[allow(dead_code)] // offsets are only asserted by the C++ code generator
+    pub offsets: Vec<usize>,
+    pub size: usize,
+    pub align: usize,
+}
+
+// With no pointer width, only fixed-width types are laid out, each at its
+// natural alignment. Otherwise None means the layout is not the same on every
+// target of that pointer width, as for 64-bit integers which i386 aligns to 4
+// bytes but other 32-bit targets align to 8.
+pub fn struct_layout(
+    strct: &Struct,
+    types: &Types,
+    pointer_width: Option<usize>,
+) -> Option<Layout> {
+    let mut offsets = Vec::new();
+    let mut size = 0;
+    let mut align = 1;
+    for field in &strct.fields {
+        let (field_size, field_align) = type_layout(&field.ty, types, pointer_width)?;
+        if strct.union_token.is_some() {
+            offsets.push(0);
+            size = size.max(field_size);
+        } else {
+            size = align_up(size, field_align);
+            offsets.push(size);
+            size += field_size;
+        }
+        align = align.max(field_align);
+    }
+    size = align_up(size, align);
+    Some(Layout {
+        offsets,
+        size,
+        align,
+    })
+}
+
+fn type_layout(ty: &Type, types: &Types, pointer_width: Option<usize>) -> Option<(usize, usize)> {
+    let ptr = match ty {
+        Type::Ident(ident) => return ident_layout(ident, types, pointer_width),
+        Type::Array(array) => {
+            let (size, align) = type_layout(&array.inner, types, pointer_width)?;
+            return Some((array.len * size, align));
+        }
+        _ => pointer_width?,
+    };
+    match ty {
+        Type::RustBox(_) | Type::UniquePtr(_) | Type::UniqueArray(_) | Type::Ref(_) => {
+            Some((ptr, ptr))
+        }
+        Type::SharedPtr(_) | Type::Str(_) | Type::SliceRef(_) => Some((2 * ptr, ptr)),
+        _ => None,
+    }
+}
+
+fn ident_layout(
+    ident: &Ident,
+    types: &Types,
+    pointer_width: Option<usize>,
+) -> Option<(usize, usize)> {
+    match Atom::from(ident) {
+        Some(Bool) | Some(U8) | Some(I8) => Some((1, 1)),
+        Some(U16) | Some(I16) => Some((2, 2)),
+        Some(U32) | Some(I32) | Some(F32) | Some(Char) => Some((4, 4)),
+        Some(U64) | Some(I64) | Some(F64) => match pointer_width {
+            Some(4) => None,
+            _ => Some((8, 8)),
+        },
+        Some(Usize) | Some(Isize) => pointer_width.map(|ptr| (ptr, ptr)),
+        Some(RustString) => pointer_width.map(|ptr| (3 * ptr, ptr)),
+        // Older Rust releases disagree with C on the alignment of i128.
+        Some(U128) | Some(I128) | Some(CxxString) | Some(CxxU16String) => None,
+        None => {
+            if let Some(enm) = types.enums.get(ident) {
+                return match &enm.repr {
+                    Some(repr) => ident_layout(repr, types, pointer_width),
+                    // Emitted as #[repr(C)] in Rust and `int` in C++.
+                    None => Some((4, 4)),
+                };
+            }
+            let strct = types.structs.get(ident)?;
+            let layout = struct_layout(strct, types, pointer_width)?;
+            Some((layout.size, layout.align))
+        }
+    }
+}
+
+fn align_up(n: usize, align: usize) -> usize {
+    (n + align - 1) & !(align - 1)
+}
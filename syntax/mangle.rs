@@ -0,0 +1,65 @@
+// Link names of the extern "C" shims generated for each side of the bridge:
+//
+//     C++ function:   {namespace}cxxbridge02${module}$cxx${name}
+//     C++ method:     {namespace}cxxbridge02${module}$cxx${type}${name}
+//     Rust function:  {namespace}cxxbridge02${module}$rust${name}
+//     Rust method:    {namespace}cxxbridge02${module}$rust${type}${name}
+//     C++ type Debug: {namespace}cxxbridge02$debug${type}
+//     placement op:   {namespace}cxxbridge02$placement${type}${op}
+//     std::map op:    cxxbridge02$std$map${key}${namespace}{value}${op}
+//
+// Incorporating the direction keeps a C++ function and a Rust function of the
+// same name within one bridge from colliding, and the {module} does the same
+// for two bridges in one namespace. It is the ident of the #[cxx::bridge] mod
+// unless the bridge passes `module = path::to::ffi`, whose segments are then
+// joined by `$`. Neither the macro nor the cxxbridge code generator can see
+// the path of the enclosing Rust module on its own, so two `mod ffi` bridges
+// that share a namespace need that argument. The namespace is anything whose
+// Display renders each segment followed by `$`. The {name} of a function is its
+// C++ name, while {type} is always the Rust name of the receiver or type. A map
+// key is an atom, spelled as in Rust except that CxxString is `string`.
+
+use crate::syntax::atom::Atom::{self, *};
+use crate::syntax::{ExternFn, Lang};
+use proc_macro2::Ident;
+use std::fmt::Display;
+
+pub fn extern_fn(namespace: impl Display, module: impl Display, efn: &ExternFn) -> String {
+    let lang = match efn.lang {
+        Lang::Cxx => "cxx",
+        Lang::Rust => "rust",
+    };
+    match &efn.receiver {
+        Some(receiver) => format!(
+            "{}cxxbridge02${}${}${}${}",
+            namespace,
+            module,
+            lang,
+            receiver.ident,
+            efn.cxx_ident(),
+        ),
+        None => format!(
+            "{}cxxbridge02${}${}${}",
+            namespace,
+            module,
+            lang,
+            efn.cxx_ident(),
+        ),
+    }
+}
+
+pub fn cxx_type_debug(namespace: impl Display, ident: &Ident) -> String {
+    format!("{}cxxbridge02$debug${}", namespace, ident)
+}
+
+pub fn cxx_type_placement(namespace: impl Display, ident: &Ident) -> String {
+    format!("{}cxxbridge02$placement${}$", namespace, ident)
+}
+
+pub fn std_map(namespace: impl Display, key: &Ident, value: &Ident) -> String {
+    let key = match Atom::from(key) {
+        Some(CxxString) => "string".to_owned(),
+        _ => key.to_string(),
+    };
+    format!("cxxbridge02$std$map${}${}{}$", key, namespace, value)
+}
@@ -7,13 +7,16 @@ mod doc;
 pub mod error;
 pub mod ident;
 mod impls;
+pub mod layout;
+pub mod mangle;
 mod parse;
 pub mod set;
 mod tokens;
 pub mod types;
 
 use proc_macro2::{Ident, Span, TokenStream};
-use syn::{token::Brace, LitStr, Token};
+use syn::token::{Brace, Bracket, Paren};
+use syn::{LitInt, LitStr, Path, Token};
 
 pub use self::atom::Atom;
 pub use self::doc::Doc;
@@ -23,6 +26,8 @@ pub use self::types::Types;
 pub enum Api {
     Include(LitStr),
     Struct(Struct),
+    Enum(Enum),
+    ExceptionEnum(ExceptionEnum),
     CxxType(ExternType),
     CxxFunction(ExternFn),
     RustType(ExternType),
@@ -31,6 +36,10 @@ pub enum Api {
 
 pub struct ExternType {
     pub doc: Doc,
+    pub debug: Option<Ident>,
+    pub placement: Option<Ident>,
+    // The name the type has in C++, if given by #[cxx::name = "..."].
+    pub cxx_name: Option<Ident>,
     pub type_token: Token![type],
     pub ident: Ident,
 }
@@ -38,15 +47,70 @@ pub struct ExternType {
 pub struct Struct {
     pub doc: Doc,
     pub derives: Vec<Ident>,
+    pub non_exhaustive: Option<Ident>,
+    pub fixed_width: Option<Ident>,
+    pub constructor: Option<Ident>,
     pub struct_token: Token![struct],
+    // Present for a `union`, in which case struct_token only carries its span.
+    pub union_token: Option<Token![union]>,
     pub ident: Ident,
     pub brace_token: Brace,
     pub fields: Vec<Var>,
 }
 
+pub struct Enum {
+    pub doc: Doc,
+    // One of the fixed-width integer types, or None for #[repr(C)].
+    pub repr: Option<Ident>,
+    pub enum_token: Token![enum],
+    pub ident: Ident,
+    pub brace_token: Brace,
+    pub variants: Vec<Variant>,
+}
+
+pub struct Variant {
+    pub doc: Doc,
+    pub ident: Ident,
+    pub discriminant: i128,
+}
+
+// An enum whose variants each catch one C++ exception type, plus exactly one
+// variant without #[cxx_exception] that catches everything else.
+pub struct ExceptionEnum {
+    pub doc: Doc,
+    pub enum_token: Token![enum],
+    pub ident: Ident,
+    pub brace_token: Brace,
+    pub variants: Vec<ExceptionVariant>,
+}
+
+pub struct ExceptionVariant {
+    pub doc: Doc,
+    pub ident: Ident,
+    pub exception: Option<Path>,
+}
+
 pub struct ExternFn {
     pub lang: Lang,
     pub doc: Doc,
+    pub deprecated: Option<Deprecated>,
+    pub template: Option<Template>,
+    pub link_section: Option<LitStr>,
+    pub abi: Option<LitStr>,
+    pub error_convert: Option<Path>,
+    // The name the function has in C++, if given by #[cxx::name = "..."].
+    pub cxx_name: Option<Ident>,
+    pub pure: Option<Ident>,
+    pub init_once: Option<Ident>,
+    pub non_reentrant: Option<Ident>,
+    // #[cxx_time_point(system_clock, nanoseconds)] on the function, making C++
+    // return a std::chrono::system_clock::time_point for the i64 return value.
+    pub time_point: Option<Ident>,
+    // The arguments carrying that same attribute, passed to C++ as time_point.
+    pub time_point_args: Vec<Ident>,
+    // The E of a Result<T, E> return type, naming an ExceptionEnum for a C++
+    // function or a shared struct for a Rust function.
+    pub error_type: Option<Ident>,
     pub ident: Ident,
     pub sig: Signature,
     pub semi_token: Token![;],
@@ -68,17 +132,50 @@ pub struct Var {
 }
 
 pub struct Receiver {
+    pub var: Ident,
+    // Spelled `self: Pin<&mut T>`, always with `mut`.
+    pub pinned: bool,
+    pub ampersand: Token![&],
     pub mutability: Option<Token![mut]>,
     pub ident: Ident,
 }
 
+pub struct Deprecated {
+    pub ident: Ident,
+    pub note: Option<LitStr>,
+}
+
+pub struct Template {
+    pub ident: Ident,
+    pub args: Vec<Type>,
+}
+
 pub enum Type {
     Ident(Ident),
     RustBox(Box<Ty1>),
+    RustArc(Box<Ty1>),
+    RustVec(Box<Ty1>),
     UniquePtr(Box<Ty1>),
+    // std::unique_ptr<T[]>, whose length is not part of the type.
+    UniqueArray(Box<Ty1>),
+    SharedPtr(Box<Ty1>),
+    CxxVector(Box<Ty1>),
+    CxxMap(Box<Ty2>),
+    CxxSet(Box<Ty1>),
+    Borrowed(Box<Ty1>),
+    Optional(Box<Ty1>),
     Ref(Box<Ref>),
     Str(Box<Ref>),
     Fn(Box<Signature>),
+    Slice(Box<Slice>),
+    SliceRef(Box<Ref>),
+    // Box<[T]>, whose inner is a Type::Slice.
+    SliceBox(Box<Ty1>),
+    Tuple(Box<Tuple>),
+    // [T; N], only as a struct field.
+    Array(Box<Array>),
+    // std::array<T, N>, spelled Array<T, N> in the bridge and [T; N] in Rust.
+    CxxArray(Box<Array>),
     Void(Span),
 }
 
@@ -89,12 +186,41 @@ pub struct Ty1 {
     pub rangle: Token![>],
 }
 
+pub struct Ty2 {
+    pub name: Ident,
+    pub langle: Token![<],
+    pub first: Type,
+    pub comma: Token![,],
+    pub second: Type,
+    pub rangle: Token![>],
+}
+
 pub struct Ref {
+    // Spelled `Pin<&mut T>`, always with `mut`.
+    pub pinned: bool,
     pub ampersand: Token![&],
     pub mutability: Option<Token![mut]>,
     pub inner: Type,
 }
 
+pub struct Slice {
+    pub bracket: Bracket,
+    pub inner: Type,
+}
+
+pub struct Tuple {
+    pub paren: Paren,
+    pub elems: Vec<Type>,
+}
+
+pub struct Array {
+    pub bracket: Bracket,
+    pub inner: Type,
+    pub semi_token: Token![;],
+    pub len: usize,
+    pub len_token: LitInt,
+}
+
 #[derive(Copy, Clone, PartialEq)]
 pub enum Lang {
     Cxx,
@@ -105,4 +231,7 @@ pub enum Lang {
 pub enum Derive {
     Clone,
     Copy,
+    Default,
+    Hash,
+    PartialEq,
 }
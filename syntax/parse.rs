@@ -1,13 +1,15 @@
 use crate::syntax::{
-    attrs, error, Api, Atom, Doc, ExternFn, ExternType, Lang, Receiver, Ref, Signature, Struct,
-    Ty1, Type, Var,
+    attrs, error, Api, Array, Atom, Doc, Enum, ExceptionEnum, ExceptionVariant, ExternFn,
+    ExternType, Lang, Receiver, Ref, Signature, Slice, Struct, Tuple, Ty1, Ty2, Type, Var, Variant,
 };
 use proc_macro2::Ident;
 use quote::{format_ident, quote};
+use syn::token::Bracket;
 use syn::{
-    Abi, Error, Fields, FnArg, ForeignItem, ForeignItemFn, ForeignItemType, GenericArgument, Item,
-    ItemForeignMod, ItemStruct, Pat, PathArguments, Result, ReturnType, Type as RustType,
-    TypeBareFn, TypePath, TypeReference,
+    Abi, Error, Expr, ExprLit, ExprUnary, Fields, FnArg, ForeignItem, ForeignItemFn,
+    ForeignItemType, GenericArgument, Item, ItemEnum, ItemForeignMod, ItemStruct, ItemUnion, Lit,
+    Pat, PathArguments, Result, ReturnType, Token, Type as RustType, TypeArray, TypeBareFn,
+    TypePath, TypeReference, TypeSlice, TypeTuple, UnOp,
 };
 
 pub fn parse_items(items: Vec<Item>) -> Result<Vec<Api>> {
@@ -18,6 +20,24 @@ pub fn parse_items(items: Vec<Item>) -> Result<Vec<Api>> {
                 let strct = parse_struct(item)?;
                 apis.push(strct);
             }
+            Item::Enum(item) => {
+                let is_exception_enum = item.variants.iter().any(|variant| {
+                    variant
+                        .attrs
+                        .iter()
+                        .any(|attr| attr.path.is_ident("cxx_exception"))
+                });
+                let enm = if is_exception_enum {
+                    parse_exception_enum(item)?
+                } else {
+                    parse_enum(item)?
+                };
+                apis.push(enm);
+            }
+            Item::Union(item) => {
+                let union = parse_union(item)?;
+                apis.push(union);
+            }
             Item::ForeignMod(foreign_mod) => {
                 let functions = parse_foreign_mod(foreign_mod)?;
                 apis.extend(functions);
@@ -44,7 +64,20 @@ fn parse_struct(item: ItemStruct) -> Result<Api> {
 
     let mut doc = Doc::new();
     let mut derives = Vec::new();
-    attrs::parse(&item.attrs, &mut doc, Some(&mut derives))?;
+    let mut non_exhaustive = None;
+    let mut fixed_width = None;
+    let mut constructor = None;
+    attrs::parse(
+        &item.attrs,
+        attrs::Parser {
+            doc: Some(&mut doc),
+            derives: Some(&mut derives),
+            non_exhaustive: Some(&mut non_exhaustive),
+            fixed_width: Some(&mut fixed_width),
+            constructor: Some(&mut constructor),
+            ..Default::default()
+        },
+    )?;
     check_reserved_name(&item.ident)?;
 
     let fields = match item.fields {
@@ -58,7 +91,11 @@ fn parse_struct(item: ItemStruct) -> Result<Api> {
     Ok(Api::Struct(Struct {
         doc,
         derives,
+        non_exhaustive,
+        fixed_width,
+        constructor,
         struct_token: item.struct_token,
+        union_token: None,
         ident: item.ident,
         brace_token: fields.brace_token,
         fields: fields
@@ -74,6 +111,224 @@ fn parse_struct(item: ItemStruct) -> Result<Api> {
     }))
 }
 
+fn parse_union(item: ItemUnion) -> Result<Api> {
+    let generics = &item.generics;
+    if !generics.params.is_empty() || generics.where_clause.is_some() {
+        let union_token = item.union_token;
+        let ident = &item.ident;
+        let where_clause = &generics.where_clause;
+        let span = quote!(#union_token #ident #generics #where_clause);
+        return Err(Error::new_spanned(
+            span,
+            "union with generic parameters is not supported yet",
+        ));
+    }
+
+    let mut doc = Doc::new();
+    let mut derives = Vec::new();
+    attrs::parse(
+        &item.attrs,
+        attrs::Parser {
+            doc: Some(&mut doc),
+            derives: Some(&mut derives),
+            ..Default::default()
+        },
+    )?;
+    check_reserved_name(&item.ident)?;
+
+    Ok(Api::Struct(Struct {
+        doc,
+        derives,
+        non_exhaustive: None,
+        fixed_width: None,
+        constructor: None,
+        struct_token: Token![struct](item.union_token.span),
+        union_token: Some(item.union_token),
+        ident: item.ident,
+        brace_token: item.fields.brace_token,
+        fields: item
+            .fields
+            .named
+            .into_iter()
+            .map(|field| {
+                Ok(Var {
+                    ident: field.ident.unwrap(),
+                    ty: parse_type(&field.ty)?,
+                })
+            })
+            .collect::<Result<_>>()?,
+    }))
+}
+
+fn parse_enum(item: ItemEnum) -> Result<Api> {
+    let generics = &item.generics;
+    if !generics.params.is_empty() || generics.where_clause.is_some() {
+        let enum_token = item.enum_token;
+        let ident = &item.ident;
+        let where_clause = &generics.where_clause;
+        let span = quote!(#enum_token #ident #generics #where_clause);
+        return Err(Error::new_spanned(
+            span,
+            "enum with generic parameters is not supported yet",
+        ));
+    }
+
+    let mut doc = Doc::new();
+    let mut repr = None;
+    attrs::parse(
+        &item.attrs,
+        attrs::Parser {
+            doc: Some(&mut doc),
+            repr: Some(&mut repr),
+            ..Default::default()
+        },
+    )?;
+    check_reserved_name(&item.ident)?;
+
+    if let Some(repr) = &repr {
+        match Atom::from(repr) {
+            Some(Atom::U8) | Some(Atom::U16) | Some(Atom::U32) | Some(Atom::U64)
+            | Some(Atom::I8) | Some(Atom::I16) | Some(Atom::I32) | Some(Atom::I64) => {}
+            _ => {
+                return Err(Error::new_spanned(
+                    repr,
+                    "unsupported repr; expected a fixed-width integer type such as u8 or i32",
+                ))
+            }
+        }
+    }
+
+    let mut variants = Vec::new();
+    let mut discriminant = 0;
+    for variant in item.variants {
+        match variant.fields {
+            Fields::Unit => {}
+            _ => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "enum variants with data are not supported",
+                ))
+            }
+        }
+        if let Some((_, expr)) = &variant.discriminant {
+            discriminant = parse_discriminant(expr)?;
+        }
+        let mut doc = Doc::new();
+        attrs::parse(
+            &variant.attrs,
+            attrs::Parser {
+                doc: Some(&mut doc),
+                ..Default::default()
+            },
+        )?;
+        variants.push(Variant {
+            doc,
+            ident: variant.ident,
+            discriminant,
+        });
+        discriminant += 1;
+    }
+
+    Ok(Api::Enum(Enum {
+        doc,
+        repr,
+        enum_token: item.enum_token,
+        ident: item.ident,
+        brace_token: item.brace_token,
+        variants,
+    }))
+}
+
+fn parse_discriminant(expr: &Expr) -> Result<i128> {
+    let (neg, lit) = match expr {
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => (true, expr.as_ref()),
+        expr => (false, expr),
+    };
+    if let Expr::Lit(ExprLit {
+        lit: Lit::Int(lit), ..
+    }) = lit
+    {
+        if lit.suffix().is_empty() {
+            let value = lit.base10_parse::<i128>()?;
+            return Ok(if neg { -value } else { value });
+        }
+    }
+    Err(Error::new_spanned(
+        expr,
+        "enum discriminant must be an unsuffixed integer literal",
+    ))
+}
+
+fn parse_exception_enum(item: ItemEnum) -> Result<Api> {
+    let generics = &item.generics;
+    if !generics.params.is_empty() || generics.where_clause.is_some() {
+        let enum_token = item.enum_token;
+        let ident = &item.ident;
+        let where_clause = &generics.where_clause;
+        let span = quote!(#enum_token #ident #generics #where_clause);
+        return Err(Error::new_spanned(
+            span,
+            "enum with generic parameters is not supported yet",
+        ));
+    }
+
+    let mut doc = Doc::new();
+    attrs::parse(
+        &item.attrs,
+        attrs::Parser {
+            doc: Some(&mut doc),
+            ..Default::default()
+        },
+    )?;
+    check_reserved_name(&item.ident)?;
+
+    let mut variants = Vec::new();
+    for variant in item.variants {
+        match variant.fields {
+            Fields::Unit => {}
+            _ => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "enum variants with data are not supported",
+                ))
+            }
+        }
+        if let Some((_, discriminant)) = &variant.discriminant {
+            return Err(Error::new_spanned(
+                discriminant,
+                "explicit discriminants are not supported on exception enums",
+            ));
+        }
+        let mut doc = Doc::new();
+        let mut exception = None;
+        attrs::parse(
+            &variant.attrs,
+            attrs::Parser {
+                doc: Some(&mut doc),
+                exception: Some(&mut exception),
+                ..Default::default()
+            },
+        )?;
+        variants.push(ExceptionVariant {
+            doc,
+            ident: variant.ident,
+            exception,
+        });
+    }
+
+    Ok(Api::ExceptionEnum(ExceptionEnum {
+        doc,
+        enum_token: item.enum_token,
+        ident: item.ident,
+        brace_token: item.brace_token,
+        variants,
+    }))
+}
+
 fn parse_foreign_mod(foreign_mod: ItemForeignMod) -> Result<Vec<Api>> {
     let lang = parse_lang(foreign_mod.abi)?;
     let api_type = match lang {
@@ -125,11 +380,27 @@ fn parse_lang(abi: Abi) -> Result<Lang> {
 }
 
 fn parse_extern_type(foreign_type: &ForeignItemType) -> Result<ExternType> {
-    let doc = attrs::parse_doc(&foreign_type.attrs)?;
+    let mut doc = Doc::new();
+    let mut debug = None;
+    let mut placement = None;
+    let mut cxx_name = None;
+    attrs::parse(
+        &foreign_type.attrs,
+        attrs::Parser {
+            doc: Some(&mut doc),
+            debug: Some(&mut debug),
+            placement: Some(&mut placement),
+            cxx_name: Some(&mut cxx_name),
+            ..Default::default()
+        },
+    )?;
     let type_token = foreign_type.type_token;
     let ident = foreign_type.ident.clone();
     Ok(ExternType {
         doc,
+        debug,
+        placement,
+        cxx_name,
         type_token,
         ident,
     })
@@ -152,6 +423,7 @@ fn parse_extern_fn(foreign_fn: &ForeignItemFn, lang: Lang) -> Result<ExternFn> {
 
     let mut receiver = None;
     let mut args = Vec::new();
+    let mut time_point_args = Vec::new();
     for arg in &foreign_fn.sig.inputs {
         match arg {
             FnArg::Receiver(receiver) => {
@@ -162,16 +434,31 @@ fn parse_extern_fn(foreign_fn: &ForeignItemFn, lang: Lang) -> Result<ExternFn> {
                     Pat::Ident(pat) => pat.ident.clone(),
                     _ => return Err(Error::new_spanned(arg, "unsupported signature")),
                 };
-                let ty = parse_type(&arg.ty)?;
                 if ident != "self" {
+                    let mut time_point = None;
+                    attrs::parse(
+                        &arg.attrs,
+                        attrs::Parser {
+                            time_point: Some(&mut time_point),
+                            ..Default::default()
+                        },
+                    )?;
+                    if time_point.is_some() {
+                        time_point_args.push(ident.clone());
+                    }
+                    let ty = parse_type(&arg.ty)?;
                     args.push(Var { ident, ty });
                     continue;
                 }
-                if let Type::Ref(reference) = ty {
-                    if let Type::Ident(ident) = reference.inner {
+                attrs::parse(&arg.attrs, attrs::Parser::default())?;
+                if let Type::Ref(reference) = parse_type(&arg.ty)? {
+                    if let Type::Ident(inner) = reference.inner {
                         receiver = Some(Receiver {
+                            var: ident,
+                            pinned: reference.pinned,
+                            ampersand: reference.ampersand,
                             mutability: reference.mutability,
-                            ident,
+                            ident: inner,
                         });
                         continue;
                     }
@@ -182,8 +469,36 @@ fn parse_extern_fn(foreign_fn: &ForeignItemFn, lang: Lang) -> Result<ExternFn> {
     }
 
     let mut throws = false;
-    let ret = parse_return_type(&foreign_fn.sig.output, &mut throws)?;
-    let doc = attrs::parse_doc(&foreign_fn.attrs)?;
+    let mut error_type = None;
+    let ret = parse_return_type(&foreign_fn.sig.output, &mut throws, &mut error_type)?;
+    let mut doc = Doc::new();
+    let mut deprecated = None;
+    let mut template = None;
+    let mut link_section = None;
+    let mut abi = None;
+    let mut error_convert = None;
+    let mut cxx_name = None;
+    let mut pure = None;
+    let mut init_once = None;
+    let mut non_reentrant = None;
+    let mut time_point = None;
+    attrs::parse(
+        &foreign_fn.attrs,
+        attrs::Parser {
+            doc: Some(&mut doc),
+            deprecated: Some(&mut deprecated),
+            template: Some(&mut template),
+            link_section: Some(&mut link_section),
+            abi: Some(&mut abi),
+            error_convert: Some(&mut error_convert),
+            cxx_name: Some(&mut cxx_name),
+            pure: Some(&mut pure),
+            init_once: Some(&mut init_once),
+            non_reentrant: Some(&mut non_reentrant),
+            time_point: Some(&mut time_point),
+            ..Default::default()
+        },
+    )?;
     let fn_token = foreign_fn.sig.fn_token;
     let ident = foreign_fn.sig.ident.clone();
     let mut foreign_fn2 = foreign_fn.clone();
@@ -194,6 +509,18 @@ fn parse_extern_fn(foreign_fn: &ForeignItemFn, lang: Lang) -> Result<ExternFn> {
     Ok(ExternFn {
         lang,
         doc,
+        deprecated,
+        template,
+        link_section,
+        abi,
+        error_convert,
+        cxx_name,
+        pure,
+        init_once,
+        non_reentrant,
+        time_point,
+        time_point_args,
+        error_type,
         ident,
         sig: Signature {
             fn_token,
@@ -207,12 +534,15 @@ fn parse_extern_fn(foreign_fn: &ForeignItemFn, lang: Lang) -> Result<ExternFn> {
     })
 }
 
-fn parse_type(ty: &RustType) -> Result<Type> {
+pub(super) fn parse_type(ty: &RustType) -> Result<Type> {
     match ty {
         RustType::Reference(ty) => parse_type_reference(ty),
         RustType::Path(ty) => parse_type_path(ty),
         RustType::BareFn(ty) => parse_type_fn(ty),
+        RustType::Slice(ty) => parse_type_slice(ty),
         RustType::Tuple(ty) if ty.elems.is_empty() => Ok(Type::Void(ty.paren_token.span)),
+        RustType::Tuple(ty) => parse_type_tuple(ty),
+        RustType::Array(ty) => parse_type_array(ty),
         _ => Err(Error::new_spanned(ty, "unsupported type")),
     }
 }
@@ -227,9 +557,11 @@ fn parse_type_reference(ty: &TypeReference) -> Result<Type> {
                 Type::Str
             }
         }
+        Type::Slice(_) => Type::SliceRef,
         _ => Type::Ref,
     };
     Ok(which(Box::new(Ref {
+        pinned: false,
         ampersand: ty.and_token,
         mutability: ty.mutability,
         inner,
@@ -254,10 +586,142 @@ fn parse_type_path(ty: &TypePath) -> Result<Type> {
                             rangle: generic.gt_token,
                         })));
                     }
+                } else if ident == "Pin" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(RustType::Reference(arg)) = &generic.args[0] {
+                        if arg.mutability.is_some() {
+                            if let Type::Ref(mut inner) = parse_type_reference(arg)? {
+                                inner.pinned = true;
+                                return Ok(Type::Ref(inner));
+                            }
+                        }
+                    }
+                } else if ident == "UniqueArray" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::UniqueArray(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "SharedPtr" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::SharedPtr(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
                 } else if ident == "Box" && generic.args.len() == 1 {
                     if let GenericArgument::Type(arg) = &generic.args[0] {
                         let inner = parse_type(arg)?;
-                        return Ok(Type::RustBox(Box::new(Ty1 {
+                        let which = match &inner {
+                            Type::Slice(_) => Type::SliceBox,
+                            _ => Type::RustBox,
+                        };
+                        return Ok(which(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "Arc" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::RustArc(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "Option" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::Optional(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "Vector" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::CxxVector(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "CxxMap" && generic.args.len() == 2 {
+                    let mut pairs = generic.args.pairs();
+                    let first = pairs.next().unwrap();
+                    let second = pairs.next().unwrap();
+                    if let (GenericArgument::Type(key), GenericArgument::Type(value), Some(comma)) =
+                        (first.value(), second.value(), first.punct())
+                    {
+                        return Ok(Type::CxxMap(Box::new(Ty2 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            first: parse_type(key)?,
+                            comma: **comma,
+                            second: parse_type(value)?,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "CxxSet" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::CxxSet(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "Borrowed" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::Borrowed(Box::new(Ty1 {
+                            name: ident,
+                            langle: generic.lt_token,
+                            inner,
+                            rangle: generic.gt_token,
+                        })));
+                    }
+                } else if ident == "Array" && generic.args.len() == 2 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        let len_token = match &generic.args[1] {
+                            GenericArgument::Const(Expr::Lit(ExprLit {
+                                lit: Lit::Int(lit),
+                                ..
+                            })) => lit.clone(),
+                            len => {
+                                let msg = "length of Array must be an integer literal";
+                                return Err(Error::new_spanned(len, msg));
+                            }
+                        };
+                        let len = len_token.base10_parse::<usize>()?;
+                        let span = ident.span();
+                        return Ok(Type::CxxArray(Box::new(Array {
+                            bracket: Bracket(span),
+                            inner,
+                            semi_token: Token![;](span),
+                            len,
+                            len_token,
+                        })));
+                    }
+                } else if ident == "Vec" && generic.args.len() == 1 {
+                    if let GenericArgument::Type(arg) = &generic.args[0] {
+                        let inner = parse_type(arg)?;
+                        return Ok(Type::RustVec(Box::new(Ty1 {
                             name: ident,
                             langle: generic.lt_token,
                             inner,
@@ -272,6 +736,43 @@ fn parse_type_path(ty: &TypePath) -> Result<Type> {
     Err(Error::new_spanned(ty, "unsupported type"))
 }
 
+fn parse_type_slice(ty: &TypeSlice) -> Result<Type> {
+    let inner = parse_type(&ty.elem)?;
+    Ok(Type::Slice(Box::new(Slice {
+        bracket: ty.bracket_token,
+        inner,
+    })))
+}
+
+fn parse_type_tuple(ty: &TypeTuple) -> Result<Type> {
+    let elems = ty.elems.iter().map(parse_type).collect::<Result<_>>()?;
+    Ok(Type::Tuple(Box::new(Tuple {
+        paren: ty.paren_token,
+        elems,
+    })))
+}
+
+fn parse_type_array(ty: &TypeArray) -> Result<Type> {
+    let inner = parse_type(&ty.elem)?;
+    let len_token = match &ty.len {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.clone(),
+        _ => {
+            let msg = "array length must be an integer literal";
+            return Err(Error::new_spanned(&ty.len, msg));
+        }
+    };
+    let len = len_token.base10_parse::<usize>()?;
+    Ok(Type::Array(Box::new(Array {
+        bracket: ty.bracket_token,
+        inner,
+        semi_token: ty.semi_token,
+        len,
+        len_token,
+    })))
+}
+
 fn parse_type_fn(ty: &TypeBareFn) -> Result<Type> {
     if ty.lifetimes.is_some() {
         return Err(Error::new_spanned(
@@ -299,7 +800,14 @@ fn parse_type_fn(ty: &TypeBareFn) -> Result<Type> {
         })
         .collect::<Result<_>>()?;
     let mut throws = false;
-    let ret = parse_return_type(&ty.output, &mut throws)?;
+    let mut error_type = None;
+    let ret = parse_return_type(&ty.output, &mut throws, &mut error_type)?;
+    if error_type.is_some() {
+        return Err(Error::new_spanned(
+            &ty.output,
+            "function pointer with a custom error type is not supported yet",
+        ));
+    }
     let tokens = quote!(#ty);
     Ok(Type::Fn(Box::new(Signature {
         fn_token: ty.fn_token,
@@ -311,7 +819,11 @@ fn parse_type_fn(ty: &TypeBareFn) -> Result<Type> {
     })))
 }
 
-fn parse_return_type(ty: &ReturnType, throws: &mut bool) -> Result<Option<Type>> {
+fn parse_return_type(
+    ty: &ReturnType,
+    throws: &mut bool,
+    error_type: &mut Option<Ident>,
+) -> Result<Option<Type>> {
     let mut ret = match ty {
         ReturnType::Default => return Ok(None),
         ReturnType::Type(_, ret) => ret.as_ref(),
@@ -327,6 +839,16 @@ fn parse_return_type(ty: &ReturnType, throws: &mut bool) -> Result<Option<Type>>
                         ret = arg;
                         *throws = true;
                     }
+                } else if ident == "Result" && generic.args.len() == 2 {
+                    if let (GenericArgument::Type(arg), GenericArgument::Type(err)) =
+                        (&generic.args[0], &generic.args[1])
+                    {
+                        if let Type::Ident(err) = parse_type(err)? {
+                            ret = arg;
+                            *throws = true;
+                            *error_type = Some(err);
+                        }
+                    }
                 }
             }
         }
@@ -338,7 +860,19 @@ fn parse_return_type(ty: &ReturnType, throws: &mut bool) -> Result<Option<Type>>
 }
 
 fn check_reserved_name(ident: &Ident) -> Result<()> {
-    if ident == "Box" || ident == "UniquePtr" || Atom::from(ident).is_some() {
+    if ident == "Box"
+        || ident == "Arc"
+        || ident == "UniquePtr"
+        || ident == "UniqueArray"
+        || ident == "SharedPtr"
+        || ident == "Borrowed"
+        || ident == "Vec"
+        || ident == "Vector"
+        || ident == "CxxMap"
+        || ident == "CxxSet"
+        || ident == "Option"
+        || Atom::from(ident).is_some()
+    {
         Err(Error::new(ident.span(), "reserved name"))
     } else {
         Ok(())
@@ -1,21 +1,38 @@
 use crate::syntax::atom::Atom::*;
-use crate::syntax::{Derive, ExternFn, Ref, Signature, Ty1, Type, Var};
+use crate::syntax::{
+    Array, Deprecated, Derive, ExternFn, Receiver, Ref, Signature, Slice, Tuple, Ty1, Ty2, Type,
+    Var,
+};
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{quote_spanned, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::Token;
 
 impl ToTokens for Type {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
             Type::Ident(ident) => {
-                if ident == CxxString {
+                if ident == CxxString || ident == CxxU16String {
                     let span = ident.span();
                     tokens.extend(quote_spanned!(span=> ::cxx::));
                 }
                 ident.to_tokens(tokens);
             }
-            Type::RustBox(ty) | Type::UniquePtr(ty) => ty.to_tokens(tokens),
-            Type::Ref(r) | Type::Str(r) => r.to_tokens(tokens),
+            Type::RustBox(ty)
+            | Type::RustArc(ty)
+            | Type::RustVec(ty)
+            | Type::UniquePtr(ty)
+            | Type::UniqueArray(ty)
+            | Type::SharedPtr(ty)
+            | Type::CxxVector(ty)
+            | Type::CxxSet(ty)
+            | Type::Borrowed(ty)
+            | Type::Optional(ty)
+            | Type::SliceBox(ty) => ty.to_tokens(tokens),
+            Type::CxxMap(ty) => ty.to_tokens(tokens),
+            Type::Ref(r) | Type::Str(r) | Type::SliceRef(r) => r.to_tokens(tokens),
+            Type::Slice(s) => s.to_tokens(tokens),
+            Type::Tuple(t) => t.to_tokens(tokens),
+            Type::Array(a) | Type::CxxArray(a) => a.to_tokens(tokens),
             Type::Fn(f) => f.to_tokens(tokens),
             Type::Void(span) => tokens.extend(quote_spanned!(*span=> ())),
         }
@@ -32,22 +49,104 @@ impl ToTokens for Var {
 
 impl ToTokens for Ty1 {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        if self.name == "UniquePtr" {
+        if self.name == "UniquePtr"
+            || self.name == "UniqueArray"
+            || self.name == "SharedPtr"
+            || self.name == "Vector"
+            || self.name == "CxxSet"
+            || self.name == "Borrowed"
+        {
             let span = self.name.span();
             tokens.extend(quote_spanned!(span=> ::cxx::));
+        } else if self.name == "Arc" {
+            let span = self.name.span();
+            tokens.extend(quote_spanned!(span=> ::std::sync::));
         }
         self.name.to_tokens(tokens);
         self.langle.to_tokens(tokens);
+        if self.name == "Borrowed" {
+            // Spelled out so that the elided lifetime is visible.
+            let span = self.name.span();
+            tokens.extend(quote_spanned!(span=> '_,));
+        }
         self.inner.to_tokens(tokens);
         self.rangle.to_tokens(tokens);
     }
 }
 
+impl ToTokens for Ty2 {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let span = self.name.span();
+        tokens.extend(quote_spanned!(span=> ::cxx::));
+        self.name.to_tokens(tokens);
+        self.langle.to_tokens(tokens);
+        self.first.to_tokens(tokens);
+        self.comma.to_tokens(tokens);
+        self.second.to_tokens(tokens);
+        self.rangle.to_tokens(tokens);
+    }
+}
+
 impl ToTokens for Ref {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        if self.pinned {
+            let span = self.ampersand.span;
+            tokens.extend(quote_spanned!(span=> ::std::pin::Pin<));
+        }
         self.ampersand.to_tokens(tokens);
         self.mutability.to_tokens(tokens);
         self.inner.to_tokens(tokens);
+        if self.pinned {
+            let span = self.ampersand.span;
+            tokens.extend(quote_spanned!(span=> >));
+        }
+    }
+}
+
+impl ToTokens for Receiver {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.var.to_tokens(tokens);
+        Token![:](self.var.span()).to_tokens(tokens);
+        if self.pinned {
+            let span = self.ampersand.span;
+            tokens.extend(quote_spanned!(span=> ::std::pin::Pin<));
+        }
+        self.ampersand.to_tokens(tokens);
+        self.mutability.to_tokens(tokens);
+        self.ident.to_tokens(tokens);
+        if self.pinned {
+            let span = self.ampersand.span;
+            tokens.extend(quote_spanned!(span=> >));
+        }
+    }
+}
+
+impl ToTokens for Slice {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.bracket.surround(tokens, |tokens| {
+            self.inner.to_tokens(tokens);
+        });
+    }
+}
+
+impl ToTokens for Tuple {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.paren.surround(tokens, |tokens| {
+            for elem in &self.elems {
+                elem.to_tokens(tokens);
+                Token![,](self.paren.span).to_tokens(tokens);
+            }
+        });
+    }
+}
+
+impl ToTokens for Array {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.bracket.surround(tokens, |tokens| {
+            self.inner.to_tokens(tokens);
+            self.semi_token.to_tokens(tokens);
+            self.len_token.to_tokens(tokens);
+        });
     }
 }
 
@@ -56,11 +155,24 @@ impl ToTokens for Derive {
         let name = match self {
             Derive::Clone => "Clone",
             Derive::Copy => "Copy",
+            Derive::Default => "Default",
+            Derive::Hash => "Hash",
+            Derive::PartialEq => "PartialEq",
         };
         Ident::new(name, Span::call_site()).to_tokens(tokens);
     }
 }
 
+impl ToTokens for Deprecated {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ident = &self.ident;
+        tokens.extend(match &self.note {
+            Some(note) => quote!(#[#ident = #note]),
+            None => quote!(#[#ident]),
+        });
+    }
+}
+
 impl ToTokens for ExternFn {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         self.sig.tokens.to_tokens(tokens);
@@ -69,6 +181,22 @@ impl ToTokens for ExternFn {
 
 impl ToTokens for Signature {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.tokens.to_tokens(tokens);
+        // Spelled out from the parsed types rather than the original tokens,
+        // so that cxx types in a function pointer get their ::cxx:: path and a
+        // Result gets a concrete error type, whose Display message is what C++
+        // receives in the rust::Error.
+        let fn_token = self.fn_token;
+        let args = self.args.iter().map(|arg| &arg.ty);
+        let ret = match &self.ret {
+            Some(ret) if self.throws => quote! {
+                -> ::std::result::Result<#ret, ::std::boxed::Box<dyn ::std::error::Error>>
+            },
+            None if self.throws => quote! {
+                -> ::std::result::Result<(), ::std::boxed::Box<dyn ::std::error::Error>>
+            },
+            Some(ret) => quote!(-> #ret),
+            None => TokenStream::new(),
+        };
+        tokens.extend(quote!(#fn_token(#(#args),*) #ret));
     }
 }
@@ -1,6 +1,6 @@
 use crate::syntax::atom::Atom::{self, *};
 use crate::syntax::set::OrderedSet as Set;
-use crate::syntax::{Api, Derive, ExternType, Struct, Type};
+use crate::syntax::{Api, Derive, Enum, ExceptionEnum, ExternType, Struct, Type};
 use proc_macro2::Ident;
 use quote::quote;
 use std::collections::BTreeMap as Map;
@@ -9,6 +9,8 @@ use syn::{Error, Result};
 pub struct Types<'a> {
     pub all: Set<'a, Type>,
     pub structs: Map<Ident, &'a Struct>,
+    pub enums: Map<Ident, &'a Enum>,
+    pub exceptions: Map<Ident, &'a ExceptionEnum>,
     pub cxx: Set<'a, Ident>,
     pub rust: Set<'a, Ident>,
 }
@@ -17,6 +19,8 @@ impl<'a> Types<'a> {
     pub fn collect(apis: &'a [Api]) -> Result<Self> {
         let mut all = Set::new();
         let mut structs = Map::new();
+        let mut enums = Map::new();
+        let mut exceptions = Map::new();
         let mut cxx = Set::new();
         let mut rust = Set::new();
 
@@ -24,8 +28,29 @@ impl<'a> Types<'a> {
             all.insert(ty);
             match ty {
                 Type::Ident(_) | Type::Str(_) | Type::Void(_) => {}
-                Type::RustBox(ty) | Type::UniquePtr(ty) => visit(all, &ty.inner),
-                Type::Ref(r) => visit(all, &r.inner),
+                Type::RustBox(ty)
+                | Type::RustArc(ty)
+                | Type::RustVec(ty)
+                | Type::UniquePtr(ty)
+                | Type::UniqueArray(ty)
+                | Type::SharedPtr(ty)
+                | Type::CxxVector(ty)
+                | Type::CxxSet(ty)
+                | Type::Borrowed(ty)
+                | Type::Optional(ty)
+                | Type::SliceBox(ty) => visit(all, &ty.inner),
+                Type::CxxMap(ty) => {
+                    visit(all, &ty.first);
+                    visit(all, &ty.second);
+                }
+                Type::Ref(r) | Type::SliceRef(r) => visit(all, &r.inner),
+                Type::Slice(s) => visit(all, &s.inner),
+                Type::Array(a) | Type::CxxArray(a) => visit(all, &a.inner),
+                Type::Tuple(t) => {
+                    for elem in &t.elems {
+                        visit(all, elem);
+                    }
+                }
                 Type::Fn(f) => {
                     if let Some(ret) = &f.ret {
                         visit(all, ret);
@@ -42,7 +67,12 @@ impl<'a> Types<'a> {
                 Api::Include(_) => {}
                 Api::Struct(strct) => {
                     let ident = &strct.ident;
-                    if structs.contains_key(ident) || cxx.contains(ident) || rust.contains(ident) {
+                    if structs.contains_key(ident)
+                        || enums.contains_key(ident)
+                        || exceptions.contains_key(ident)
+                        || cxx.contains(ident)
+                        || rust.contains(ident)
+                    {
                         return Err(duplicate_struct(strct));
                     }
                     structs.insert(strct.ident.clone(), strct);
@@ -50,16 +80,50 @@ impl<'a> Types<'a> {
                         visit(&mut all, &field.ty);
                     }
                 }
+                Api::Enum(enm) => {
+                    let ident = &enm.ident;
+                    if structs.contains_key(ident)
+                        || enums.contains_key(ident)
+                        || exceptions.contains_key(ident)
+                        || cxx.contains(ident)
+                        || rust.contains(ident)
+                    {
+                        return Err(duplicate_enum(enm));
+                    }
+                    enums.insert(enm.ident.clone(), enm);
+                }
+                Api::ExceptionEnum(enm) => {
+                    let ident = &enm.ident;
+                    if structs.contains_key(ident)
+                        || enums.contains_key(ident)
+                        || exceptions.contains_key(ident)
+                        || cxx.contains(ident)
+                        || rust.contains(ident)
+                    {
+                        return Err(duplicate_exception_enum(enm));
+                    }
+                    exceptions.insert(enm.ident.clone(), enm);
+                }
                 Api::CxxType(ety) => {
                     let ident = &ety.ident;
-                    if structs.contains_key(ident) || cxx.contains(ident) || rust.contains(ident) {
+                    if structs.contains_key(ident)
+                        || enums.contains_key(ident)
+                        || exceptions.contains_key(ident)
+                        || cxx.contains(ident)
+                        || rust.contains(ident)
+                    {
                         return Err(duplicate_type(ety));
                     }
                     cxx.insert(ident);
                 }
                 Api::RustType(ety) => {
                     let ident = &ety.ident;
-                    if structs.contains_key(ident) || cxx.contains(ident) || rust.contains(ident) {
+                    if structs.contains_key(ident)
+                        || enums.contains_key(ident)
+                        || exceptions.contains_key(ident)
+                        || cxx.contains(ident)
+                        || rust.contains(ident)
+                    {
                         return Err(duplicate_type(ety));
                     }
                     rust.insert(ident);
@@ -71,6 +135,11 @@ impl<'a> Types<'a> {
                     if let Some(ret) = &efn.ret {
                         visit(&mut all, ret);
                     }
+                    if let Some(template) = &efn.template {
+                        for arg in &template.args {
+                            visit(&mut all, arg);
+                        }
+                    }
                 }
             }
         }
@@ -78,6 +147,8 @@ impl<'a> Types<'a> {
         Ok(Types {
             all,
             structs,
+            enums,
+            exceptions,
             cxx,
             rust,
         })
@@ -92,6 +163,13 @@ impl<'a> Types<'a> {
                     Atom::from(ident) == Some(RustString)
                 }
             }
+            // Not trivially copyable, so it moves through a pointer.
+            Type::SharedPtr(_) | Type::RustVec(_) => true,
+            // Unpacked into one out-parameter per element.
+            Type::Tuple(_) => true,
+            // C has no arrays by value, so it moves through a pointer to the
+            // [T; N] which has the same layout as the std::array.
+            Type::CxxArray(_) => true,
             _ => false,
         }
     }
@@ -121,6 +199,20 @@ fn duplicate_struct(strct: &Struct) -> Error {
     Error::new_spanned(range, "duplicate type")
 }
 
+fn duplicate_enum(enm: &Enum) -> Error {
+    let enum_token = enm.enum_token;
+    let ident = &enm.ident;
+    let range = quote!(#enum_token #ident);
+    Error::new_spanned(range, "duplicate type")
+}
+
+fn duplicate_exception_enum(enm: &ExceptionEnum) -> Error {
+    let enum_token = enm.enum_token;
+    let ident = &enm.ident;
+    let range = quote!(#enum_token #ident);
+    Error::new_spanned(range, "duplicate type")
+}
+
 fn duplicate_type(ety: &ExternType) -> Error {
     let type_token = ety.type_token;
     let ident = &ety.ident;
@@ -1,11 +1,120 @@
+use std::env;
+
 fn main() {
     if cfg!(trybuild) {
         return;
     }
 
     cxx::Build::new()
+        .layout_constants()
+        .catch_unknown_exceptions()
         .bridge("lib.rs")
         .file("tests.cc")
         .flag("-std=c++11")
+        .flag_if_supported("-Werror=exit-time-destructors")
         .compile("cxx-test-suite");
+
+    cxx::Build::new()
+        .no_exceptions()
+        .bridge("no_exceptions.rs")
+        .file("no_exceptions.cc")
+        .flag("-std=c++11")
+        .flag("-fno-exceptions")
+        .compile("cxx-test-suite-no-exceptions");
+
+    cxx::Build::new()
+        .bridge("cpp17.rs")
+        .file("cpp17.cc")
+        .flag("-std=c++17")
+        .compile("cxx-test-suite-cpp17");
+
+    // Generated first so that namespaces.cc finds both headers.
+    cxx::Build::new()
+        .signatures()
+        .bridge("namespace_b.rs")
+        .flag("-std=c++11")
+        .compile("cxx-test-suite-namespace-b");
+
+    cxx::Build::new()
+        .bridge("namespace_a.rs")
+        .file("namespaces.cc")
+        .flag("-std=c++11")
+        .compile("cxx-test-suite-namespace-a");
+
+    // Generated first so that versioned.cc finds both headers.
+    cxx::Build::new()
+        .bridge("versioned_v1.rs")
+        .flag("-std=c++11")
+        .compile("cxx-test-suite-versioned-v1");
+
+    cxx::Build::new()
+        .bridge("versioned_v2.rs")
+        .file("versioned.cc")
+        .flag("-std=c++11")
+        .compile("cxx-test-suite-versioned-v2");
+
+    // Generated first so that modules.cc finds both headers.
+    cxx::Build::new()
+        .bridge("module_b.rs")
+        .flag("-std=c++11")
+        .compile("cxx-test-suite-module-b");
+
+    cxx::Build::new()
+        .bridge("module_a.rs")
+        .file("modules.cc")
+        .flag("-std=c++11")
+        .compile("cxx-test-suite-module-a");
+
+    cxx::Build::new()
+        .bridge("module_c.rs")
+        .flag("-std=c++11")
+        .compile("cxx-test-suite-module-c");
+
+    cxx::Build::new()
+        .bridge("module_d.rs")
+        .flag("-std=c++11")
+        .compile("cxx-test-suite-module-d");
+
+    cxx::Build::new()
+        .cxx_usize("uintptr_t")
+        .cxx_isize("intptr_t")
+        .bridge("pointer_sized.rs")
+        .file("pointer_sized.cc")
+        .flag("-std=c++11")
+        .compile("cxx-test-suite-pointer-sized");
+
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("linux") {
+        cxx::Build::new()
+            .bridge("link_section.rs")
+            .flag("-std=c++11")
+            .compile("cxx-test-suite-link-section");
+    }
+
+    println!("cargo:rerun-if-changed=lib.rs");
+    println!("cargo:rerun-if-changed=tests.cc");
+    println!("cargo:rerun-if-changed=tests.h");
+    println!("cargo:rerun-if-changed=no_exceptions.rs");
+    println!("cargo:rerun-if-changed=no_exceptions.cc");
+    println!("cargo:rerun-if-changed=no_exceptions.h");
+    println!("cargo:rerun-if-changed=cpp17.rs");
+    println!("cargo:rerun-if-changed=cpp17.cc");
+    println!("cargo:rerun-if-changed=cpp17.h");
+    println!("cargo:rerun-if-changed=namespace_a.rs");
+    println!("cargo:rerun-if-changed=namespace_b.rs");
+    println!("cargo:rerun-if-changed=namespaces.cc");
+    println!("cargo:rerun-if-changed=namespaces.h");
+    println!("cargo:rerun-if-changed=versioned_v1.rs");
+    println!("cargo:rerun-if-changed=versioned_v2.rs");
+    println!("cargo:rerun-if-changed=versioned.cc");
+    println!("cargo:rerun-if-changed=versioned.h");
+    println!("cargo:rerun-if-changed=module_a.rs");
+    println!("cargo:rerun-if-changed=module_b.rs");
+    println!("cargo:rerun-if-changed=module_c.rs");
+    println!("cargo:rerun-if-changed=module_d.rs");
+    println!("cargo:rerun-if-changed=pointer_sized.rs");
+    println!("cargo:rerun-if-changed=pointer_sized.cc");
+    println!("cargo:rerun-if-changed=pointer_sized.h");
+    println!("cargo:rerun-if-changed=link_section.rs");
+    println!("cargo:rerun-if-changed=modules.cc");
+    println!("cargo:rerun-if-changed=modules.h");
 }
@@ -0,0 +1,21 @@
+#include "tests/ffi/cpp17.h"
+#include "tests/ffi/cpp17.rs.h"
+
+#if __cplusplus < 201703L
+#error "expected to be compiled with -std=c++17"
+#endif
+
+namespace cpp17 {
+
+uint32_t c_optional_or(std::optional<uint32_t> opt, uint32_t fallback) {
+  return opt.value_or(fallback);
+}
+
+Options c_return_default_options() {
+  // Default-initialized, not value-initialized, so any zeros here come from
+  // the generated member initializers.
+  Options options;
+  return options;
+}
+
+} // namespace cpp17
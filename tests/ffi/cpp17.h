@@ -0,0 +1,12 @@
+#pragma once
+#include <cstdint>
+#include <optional>
+
+namespace cpp17 {
+
+struct Options;
+
+uint32_t c_optional_or(std::optional<uint32_t> opt, uint32_t fallback);
+Options c_return_default_options();
+
+} // namespace cpp17
@@ -0,0 +1,20 @@
+// Compiled with -std=c++17, for bindings to types that only exist from C++17.
+
+/// Bindings to tests/ffi/cpp17.h.
+#[cxx::bridge(namespace = cpp17)]
+#[allow(missing_docs)]
+pub mod ffi {
+    #[derive(Default)]
+    struct Options {
+        retries: u32,
+        ratio: f64,
+        name: String,
+    }
+
+    extern "C" {
+        include!("tests/ffi/cpp17.h");
+
+        fn c_optional_or(opt: Option<u32>, fallback: u32) -> u32;
+        fn c_return_default_options() -> Options;
+    }
+}
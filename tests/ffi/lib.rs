@@ -1,79 +1,454 @@
 #![allow(clippy::boxed_local, clippy::trivially_copy_pass_by_ref)]
 
-use cxx::{CxxString, UniquePtr};
+/// Bridge built without C++ exception support.
+#[deny(missing_docs)]
+pub mod no_exceptions;
+
+/// Bridge compiled as C++17.
+#[deny(missing_docs)]
+pub mod cpp17;
+
+/// Bridge in namespace org::example::a.
+#[deny(missing_docs)]
+pub mod namespace_a;
+
+/// Bridge in namespace org::example::b, with the same item names as
+/// namespace_a.
+#[deny(missing_docs)]
+pub mod namespace_b;
+
+/// Bridge in namespace org::versioned::v1.
+#[deny(missing_docs)]
+pub mod versioned_v1;
+
+/// Bridge in inline namespace org::versioned::v2, with the same item names as
+/// versioned_v1.
+#[deny(missing_docs)]
+pub mod versioned_v2;
+
+/// Bridge in the global namespace, with the same function name as module_b.
+#[deny(missing_docs)]
+pub mod module_a;
+
+/// Bridge in the global namespace, with the same function name as module_a.
+#[deny(missing_docs)]
+pub mod module_b;
+
+/// Bridge `mod ffi` in the global namespace, like module_d.
+#[deny(missing_docs)]
+pub mod module_c;
+
+/// Bridge `mod ffi` in the global namespace, like module_c.
+#[deny(missing_docs)]
+pub mod module_d;
+
+/// Bridge generated with usize as uintptr_t and isize as intptr_t.
+#[deny(missing_docs)]
+pub mod pointer_sized;
+
+/// Bridge whose exported Rust function is placed in a custom link section.
+#[cfg(target_os = "linux")]
+#[deny(missing_docs)]
+pub mod link_section;
+
+use cxx::{CxxMap, CxxString, SharedPtr, UniquePtr};
 use std::fmt::{self, Display};
+use std::sync::Arc;
 
 #[cxx::bridge(namespace = tests)]
 pub mod ffi {
+    // Declared ahead of the struct it contains.
+    #[cxx_constructor]
+    #[derive(PartialEq)]
+    struct Nested {
+        shared: Shared,
+        tag: u32,
+    }
+
+    #[derive(PartialEq)]
     struct Shared {
         z: usize,
     }
 
+    #[cxx_constructor]
+    struct SharedString {
+        msg: String,
+    }
+
+    #[derive(Copy, Clone, Default)]
+    struct Block {
+        a: u64,
+        b: u64,
+        c: u64,
+        d: u64,
+    }
+
+    // 256 bytes; only ever passed by reference.
+    struct LargeConfig {
+        block0: Block,
+        block1: Block,
+        block2: Block,
+        block3: Block,
+        block4: Block,
+        block5: Block,
+        block6: Block,
+        block7: Block,
+    }
+
+    #[derive(Default)]
+    struct Settings {
+        retries: u32,
+        ratio: f64,
+        name: String,
+        block: Block,
+    }
+
+    #[derive(Copy, Clone, Hash, PartialEq)]
+    struct Coord {
+        x: i32,
+        y: i32,
+    }
+
+    // Map key whose hash combines a nested struct's hash with its own fields.
+    #[derive(Copy, Clone, Hash, PartialEq)]
+    struct Tile {
+        coord: Coord,
+        level: Level,
+    }
+
+    #[derive(Hash)]
+    struct Named {
+        name: String,
+        tile: Tile,
+    }
+
+    // Padded after `flag` and `level` on 64-bit targets.
+    struct Padded {
+        flag: bool,
+        n: u64,
+        level: Level,
+        name: String,
+    }
+
+    #[derive(Copy, Clone)]
+    union Payload {
+        bits: u32,
+        real: f32,
+        wide: u64,
+    }
+
+    #[repr(u8)]
+    enum Color {
+        Red,
+        Green = 4,
+        Blue,
+    }
+
+    enum Level {
+        Low = -1,
+        Mid,
+        High = 10,
+    }
+
+    enum ClassifiedError {
+        #[cxx_exception(std::invalid_argument)]
+        InvalidArgument,
+        #[cxx_exception(std::out_of_range)]
+        OutOfRange,
+        Other,
+    }
+
+    #[cxx_fixed_width]
+    struct FixedWidth {
+        tag: u8,
+        value: u64,
+    }
+
+    // Padded after `rgba` so that `weights` is 4-byte aligned.
+    #[derive(Copy, Clone, Default)]
+    struct Pixel {
+        rgba: [u8; 3],
+        weights: [f32; 2],
+    }
+
+    #[non_exhaustive]
+    struct Extensible {
+        z: usize,
+    }
+
     extern "C" {
         include!("tests/ffi/tests.h");
 
+        #[cxx_debug]
         type C;
 
         fn c_return_primitive() -> usize;
+        fn c_sizeof_padded() -> usize;
+        #[deprecated = "use c_return_primitive instead"]
+        fn c_deprecated_return_primitive() -> usize;
         fn c_return_shared() -> Shared;
+        fn c_return_extensible() -> Extensible;
+        fn c_return_nested() -> Nested;
+        fn c_return_settings() -> Settings;
+        fn c_return_payload(real: f32) -> Payload;
+        fn c_brighten(pixel: Pixel) -> Pixel;
+        fn c_return_array() -> Array<f64, 3>;
+        fn c_sum_array(values: Array<i32, 4>, extra: &Array<i32, 2>) -> i32;
+        fn c_return_shared_string() -> SharedString;
+        fn c_return_enum() -> Color;
         fn c_return_box() -> Box<R>;
         fn c_return_unique_ptr() -> UniquePtr<C>;
+        fn c_return_optional_unique_ptr(some: bool) -> Option<UniquePtr<C>>;
+        fn c_return_shared_ptr() -> SharedPtr<C>;
+        fn c_return_borrowed(c: &C, found: bool) -> Borrowed<C>;
         fn c_return_ref(shared: &Shared) -> &usize;
+        fn c_return_optional_ref(shared: &Shared, some: bool) -> Option<&usize>;
         fn c_return_str(shared: &Shared) -> &str;
         fn c_return_rust_string() -> String;
         fn c_return_unique_ptr_string() -> UniquePtr<CxxString>;
+        fn c_return_unique_ptr_u16string() -> UniquePtr<CxxU16String>;
+        fn c_return_unique_array(len: usize) -> UniqueArray<u8>;
+        fn c_return_unique_ptr_vector_u8() -> UniquePtr<Vector<u8>>;
+        fn c_return_unique_ptr_vector_shared() -> UniquePtr<Vector<Shared>>;
+        fn c_return_unique_ptr_vector_bool() -> UniquePtr<Vector<bool>>;
+        fn c_return_rust_vec_shared() -> Vec<Shared>;
+        fn c_return_tuple(n: i32) -> (i32, f64, bool);
+        fn c_return_tuple_shared(n: usize) -> (Shared, SharedString);
+        fn c_next_char(c: char) -> char;
+        fn c_return_char_unchecked(value: u32) -> char;
+        #[cxx_abi = "system"]
+        fn c_system_abi(a: u32, b: u64, c: u8) -> u64;
 
         fn c_take_primitive(n: usize);
         fn c_take_shared(shared: Shared);
+        fn c_take_nested(nested: Nested);
+        fn c_take_payload(payload: Payload);
+        fn c_take_shared_string(shared: SharedString);
+        fn c_take_enum(color: Color);
         fn c_take_box(r: Box<R>);
+        fn c_take_box_shared(shared: Box<Shared>);
         fn c_take_unique_ptr(c: UniquePtr<C>);
+        fn c_take_shared_ptr(c: SharedPtr<C>);
+        fn c_return_arc(r: Arc<R>) -> Arc<R>;
+        fn c_arc_strong_count_with_copies(r: Arc<R>, copies: usize) -> usize;
+        fn c_shared_ptr_use_count(c: &SharedPtr<C>) -> usize;
+        fn c_vector_capacity_u8(v: &Vector<u8>) -> usize;
+        fn c_vector_count_true(v: &Vector<bool>) -> usize;
+        fn c_roundtrip_u128(n: u128) -> u128;
+        fn c_negate_i128(n: i128) -> i128;
+        fn c_make_unique_ptr(out: &mut UniquePtr<C>, n: usize);
         fn c_take_ref_r(r: &R);
         fn c_take_ref_c(c: &C);
+        fn c_take_ref_large_config(config: &LargeConfig) -> usize;
+        fn c_count_distinct_tiles(tiles: &[Tile]) -> usize;
+        fn c_hash_named(named: Named) -> usize;
+        fn c_take_borrowed(c: Borrowed<C>);
+        fn c_take_optional_ref(shared: Option<&Shared>) -> usize;
         fn c_take_str(s: &str);
+        fn c_take_mut_slice_shared(s: &mut [Shared]);
+        fn c_take_slice_u8(s: &[u8]);
+        fn c_zero_slice_u8(s: &mut [u8]);
+        fn c_sum_slice_i32(s: &[i32]) -> i64;
+        fn c_max_window_sum(s: &[i32], width: usize) -> i64;
+        fn c_try_subslice_len(s: &[u8], start: usize, len: usize) -> Result<usize>;
+        fn c_sum_slice_shared(s: &[Shared]) -> usize;
+        fn c_take_ref_vec(v: &Vec<u8>);
+        fn c_grow_vec(v: &mut Vec<u8>);
+        fn c_shrink_vec(v: &mut Vec<u8>);
+        fn c_tokenize(s: &str, tokens: &mut Vec<Vec<u8>>);
+        fn c_count_token_bytes(tokens: &Vec<Vec<u8>>) -> usize;
+        fn c_return_vec_string() -> Vec<String>;
+        fn c_join_vec_string(v: Vec<String>) -> String;
         fn c_take_rust_string(s: String);
         fn c_take_unique_ptr_string(s: UniquePtr<CxxString>);
+        fn c_sum_unique_array(array: UniqueArray<u8>, len: usize) -> usize;
+        fn c_take_binary_string(s: &CxxString);
+        fn c_take_u16string(s: &CxxU16String);
+        fn c_append_cxx_string(s: &mut CxxString);
+        fn c_append_to(s: Pin<&mut CxxString>, suffix: &str);
+        fn c_reset(s: &mut UniquePtr<CxxString>, value: &str);
         fn c_take_callback(callback: fn(String) -> usize);
+        fn c_for_each_mut(f: fn(&mut Shared)) -> usize;
+        fn c_process(cb: fn(u32) -> Result<u32>) -> u32;
+        fn c_apply_twice(f: fn(i32) -> i32, n: i32) -> i32;
+        fn c_greet(f: fn(&str) -> String) -> String;
+        fn c_build(f: fn(usize) -> UniquePtr<C>) -> usize;
+        fn c_call_logger(depth: usize) -> usize;
+        fn compute(n: usize) -> usize;
+        #[cxx_pure]
+        fn c_pure_square(n: u32) -> u32;
+        #[cxx_init_once]
+        fn c_init_once_config() -> usize;
+        fn c_init_once_calls() -> usize;
+        #[cxx_time_point(system_clock, nanoseconds)]
+        fn c_now() -> i64;
+        #[cxx_time_point(system_clock, nanoseconds)]
+        fn c_add_seconds(
+            #[cxx_time_point(system_clock, nanoseconds)] time: i64,
+            seconds: i64,
+        ) -> i64;
+
+        fn get(self: &C) -> usize;
+        fn set(self: Pin<&mut C>, n: usize) -> usize;
+        fn increment(self: Pin<&mut C>) -> usize;
+        fn get_fail(self: &C) -> Result<usize>;
+        fn get_bytes(self: &C) -> &[u8];
+        fn get_config(self: &C) -> &CxxMap<CxxString, i32>;
+        fn get_tags(self: &C) -> &CxxSet<u32>;
+        #[template(get_as<u16>)]
+        fn get_u16(self: &C) -> u16;
+        #[template(get_as<i64>)]
+        fn get_i64(self: &C) -> i64;
+
+        #[cxx::name = "Counter"]
+        type CxxCounter;
+
+        #[cxx::name = "cReturnCamelCase"]
+        fn c_return_camel_case() -> usize;
+        fn c_new_counter(start: usize) -> UniquePtr<CxxCounter>;
+        #[cxx::name = "getCount"]
+        fn count(self: &CxxCounter) -> usize;
+
+        #[cxx_placement]
+        type Component;
+
+        fn id(self: &Component) -> usize;
+        fn c_last_destroyed_component() -> usize;
 
         fn c_try_return_void() -> Result<()>;
         fn c_try_return_primitive() -> Result<usize>;
         fn c_fail_return_primitive() -> Result<usize>;
+        fn c_fail_throw_int() -> Result<usize>;
         fn c_try_return_box() -> Result<Box<R>>;
+        fn c_fail_return_box() -> Result<Box<R>>;
         fn c_try_return_ref(s: &String) -> Result<&String>;
         fn c_try_return_str(s: &str) -> Result<&str>;
         fn c_try_return_rust_string() -> Result<String>;
         fn c_try_return_unique_ptr_string() -> Result<UniquePtr<CxxString>>;
+        fn c_try_return_tuple() -> Result<(u8, usize)>;
+        fn c_fail_return_tuple() -> Result<(u8, usize)>;
+        fn c_fail_return_tuple_shared() -> Result<(SharedString, Shared)>;
+        fn c_try_new_c(n: usize) -> Result<UniquePtr<C>>;
+        fn c_throw_classified(kind: u8) -> Result<usize, ClassifiedError>;
+        fn c_throw_classified_string(kind: u8) -> Result<String, ClassifiedError>;
+        fn c_try_return_char(value: u32) -> Result<char>;
+        fn c_try_return_char_classified(value: u32) -> Result<char, ClassifiedError>;
     }
 
     extern "Rust" {
         type R;
+        type R2;
+        #[cxx::name = "RenamedR"]
+        type R3;
 
         fn r_return_primitive() -> usize;
+        #[deprecated = "use \"r_return_primitive\" instead"]
+        fn r_deprecated_return_primitive() -> usize;
         fn r_return_shared() -> Shared;
+        fn r_return_nested() -> Nested;
+        fn r_return_payload(bits: u32) -> Payload;
+        fn r_return_array() -> Array<f64, 3>;
+        fn r_sum_array(values: Array<i32, 4>) -> i32;
+        fn r_return_shared_string() -> SharedString;
+        fn r_return_enum(n: i32) -> Level;
         fn r_return_box() -> Box<R>;
         fn r_return_unique_ptr() -> UniquePtr<C>;
         fn r_return_ref(shared: &Shared) -> &usize;
+        fn r_return_optional_ref(shared: &Shared, some: bool) -> Option<&usize>;
         fn r_return_str(shared: &Shared) -> &str;
         fn r_return_rust_string() -> String;
         fn r_return_unique_ptr_string() -> UniquePtr<CxxString>;
+        fn r_return_r2(n: usize) -> Box<R2>;
+        fn r_return_r3(n: usize) -> Box<R3>;
+        #[cxx::name = "rReturnCamelCase"]
+        fn r_return_camel_case() -> usize;
 
         fn r_take_primitive(n: usize);
         fn r_take_shared(shared: Shared);
+        fn r_take_nested(nested: Nested);
+        fn r_take_shared_string(shared: SharedString);
+        fn r_take_enum(level: Level);
         fn r_take_box(r: Box<R>);
         fn r_take_unique_ptr(c: UniquePtr<C>);
+        fn r_take_shared_ptr(c: SharedPtr<C>);
+        fn r_arc_strong_count(r: Arc<R>) -> usize;
+        fn r_return_arc_r2(n: usize) -> Arc<R2>;
         fn r_take_ref_r(r: &R);
         fn r_take_ref_c(c: &C);
+        fn r_take_ref_large_config(config: &LargeConfig) -> usize;
+        fn r_take_optional_ref(shared: Option<&Shared>) -> usize;
         fn r_take_str(s: &str);
+        fn r_take_mut_slice_shared(s: &mut [Shared]);
+        fn r_take_slice_u8(s: &[u8]);
+        fn r_fill_slice_u8(s: &mut [u8]);
+        fn r_sum_slice_f64(s: &[f64]) -> f64;
+        fn r_roundtrip_u128(n: u128) -> u128;
         fn r_take_rust_string(s: String);
+        fn r_fill_jagged(v: &mut Vec<Vec<u8>>);
+        fn r_return_vec_string() -> Vec<String>;
+        fn r_return_rust_vec_shared() -> Vec<Shared>;
+        fn r_try_return_rust_vec_shared(fail: bool) -> Result<Vec<Shared>>;
+        fn r_return_boxed_bytes(n: usize) -> Box<[u8]>;
+        fn r_return_boxed_shared() -> Box<[Shared]>;
+        fn r_try_return_boxed_i32(fail: bool) -> Result<Box<[i32]>>;
+        fn r_join_vec_string(v: Vec<String>) -> String;
         fn r_take_unique_ptr_string(s: UniquePtr<CxxString>);
+        fn r_take_ref_cxx_string(s: &CxxString) -> usize;
+        fn r_append_cxx_string(s: &mut CxxString);
+        fn r_take_map_shared(m: &CxxMap<u32, Shared>) -> usize;
+        fn r_get_map_shared(m: &CxxMap<u32, Shared>, key: u32) -> usize;
+        #[cxx_non_reentrant]
+        fn r_log(depth: usize) -> usize;
+        fn compute(n: u32) -> u32;
+        #[cxx_pure]
+        fn r_pure_square(n: u32) -> u32;
+
+        fn get(self: &R2) -> usize;
+        fn set(self: &mut R2, n: usize) -> usize;
+        fn get_name(self: &R2) -> String;
+        #[cxx::name = "getValue"]
+        fn value(self: &R3) -> usize;
 
         fn r_try_return_void() -> Result<()>;
         fn r_try_return_primitive() -> Result<usize>;
+        fn r_next_char(c: char) -> char;
+        #[cxx_abi = "system"]
+        fn r_system_abi(a: u32, b: u64, c: u8) -> u64;
+        fn r_try_next_char(c: char) -> Result<char>;
         fn r_fail_return_primitive() -> Result<usize>;
+        #[cxx_error_convert(super::describe_error_code)]
+        fn r_fail_return_error_code() -> Result<usize>;
+        fn r_try_typed_error(fail: bool) -> Result<usize, SharedString>;
     }
 }
 
 pub type R = usize;
 
+pub struct R2(usize);
+
+impl R2 {
+    fn get(&self) -> usize {
+        self.0
+    }
+
+    fn set(&mut self, n: usize) -> usize {
+        self.0 = n;
+        n
+    }
+
+    fn get_name(&self) -> String {
+        format!("R2({})", self.0)
+    }
+}
+
+pub struct R3(usize);
+
+impl R3 {
+    fn value(&self) -> usize {
+        self.0
+    }
+}
+
 #[derive(Debug)]
 struct Error;
 
@@ -85,14 +460,46 @@ impl Display for Error {
     }
 }
 
+// Deliberately not Display; it crosses the bridge through describe_error_code.
+struct ErrorCode(u32);
+
+fn describe_error_code(code: ErrorCode) -> String {
+    format!("error code {}", code.0)
+}
+
 fn r_return_primitive() -> usize {
     2020
 }
 
+fn r_deprecated_return_primitive() -> usize {
+    2020
+}
+
 fn r_return_shared() -> ffi::Shared {
     ffi::Shared { z: 2020 }
 }
 
+fn r_return_nested() -> ffi::Nested {
+    ffi::Nested {
+        shared: ffi::Shared { z: 2020 },
+        tag: 1,
+    }
+}
+
+fn r_return_shared_string() -> ffi::SharedString {
+    ffi::SharedString {
+        msg: "2020".to_owned(),
+    }
+}
+
+fn r_return_enum(n: i32) -> ffi::Level {
+    match n {
+        n if n < 0 => ffi::Level::Low,
+        n if n < 10 => ffi::Level::Mid,
+        _ => ffi::Level::High,
+    }
+}
+
 fn r_return_box() -> Box<R> {
     Box::new(2020)
 }
@@ -108,6 +515,14 @@ fn r_return_ref(shared: &ffi::Shared) -> &usize {
     &shared.z
 }
 
+fn r_return_optional_ref(shared: &ffi::Shared, some: bool) -> Option<&usize> {
+    if some {
+        Some(&shared.z)
+    } else {
+        None
+    }
+}
+
 fn r_return_str(shared: &ffi::Shared) -> &str {
     let _ = shared;
     "2020"
@@ -124,14 +539,56 @@ fn r_return_unique_ptr_string() -> UniquePtr<CxxString> {
     unsafe { UniquePtr::from_raw(cxx_test_suite_get_unique_ptr_string()) }
 }
 
+fn r_return_r2(n: usize) -> Box<R2> {
+    Box::new(R2(n))
+}
+
+fn r_return_r3(n: usize) -> Box<R3> {
+    Box::new(R3(n))
+}
+
+fn r_return_camel_case() -> usize {
+    2020
+}
+
+fn r_return_arc_r2(n: usize) -> Arc<R2> {
+    Arc::new(R2(n))
+}
+
 fn r_take_primitive(n: usize) {
     assert_eq!(n, 2020);
 }
 
+fn r_return_payload(bits: u32) -> ffi::Payload {
+    ffi::Payload { bits }
+}
+
+fn r_return_array() -> [f64; 3] {
+    [0.5, 1.5, 2.5]
+}
+
+fn r_sum_array(values: [i32; 4]) -> i32 {
+    values.iter().sum()
+}
+
 fn r_take_shared(shared: ffi::Shared) {
     assert_eq!(shared.z, 2020);
 }
 
+fn r_take_nested(nested: ffi::Nested) {
+    assert_eq!(nested.shared.z, 2020);
+    assert_eq!(nested.tag, 1);
+}
+
+fn r_take_shared_string(shared: ffi::SharedString) {
+    assert_eq!(shared.msg, "2020");
+}
+
+fn r_take_enum(level: ffi::Level) {
+    assert_eq!(level, ffi::Level::High);
+    assert_eq!(level as i32, 10);
+}
+
 fn r_take_box(r: Box<R>) {
     let _ = r;
 }
@@ -140,6 +597,14 @@ fn r_take_unique_ptr(c: UniquePtr<ffi::C>) {
     let _ = c;
 }
 
+fn r_take_shared_ptr(c: SharedPtr<ffi::C>) {
+    assert_eq!(2020, c.get());
+}
+
+fn r_arc_strong_count(r: Arc<R>) -> usize {
+    Arc::strong_count(&r)
+}
+
 fn r_take_ref_r(r: &R) {
     let _ = r;
 }
@@ -148,10 +613,88 @@ fn r_take_ref_c(c: &ffi::C) {
     let _ = c;
 }
 
+fn r_take_ref_large_config(config: &ffi::LargeConfig) -> usize {
+    config as *const ffi::LargeConfig as usize
+}
+
+fn r_take_optional_ref(shared: Option<&ffi::Shared>) -> usize {
+    shared.map_or(0, |shared| shared.z)
+}
+
 fn r_take_str(s: &str) {
     assert_eq!(s, "2020");
 }
 
+fn r_take_slice_u8(s: &[u8]) {
+    assert_eq!(s, b"2020");
+}
+
+fn r_fill_slice_u8(s: &mut [u8]) {
+    s.copy_from_slice(b"2020");
+}
+
+fn r_sum_slice_f64(s: &[f64]) -> f64 {
+    s.iter().sum()
+}
+
+fn r_roundtrip_u128(n: u128) -> u128 {
+    n
+}
+
+fn r_take_mut_slice_shared(s: &mut [ffi::Shared]) {
+    assert_eq!(s.len(), 2);
+    assert_eq!(s[0].z, 2020);
+    assert_eq!(s[1].z, 2021);
+    for shared in s {
+        shared.z += 10;
+    }
+}
+
+fn r_fill_jagged(v: &mut Vec<Vec<u8>>) {
+    v.push(b"20".to_vec());
+    v.push(Vec::new());
+    v.push(b"20".to_vec());
+}
+
+fn r_return_rust_vec_shared() -> Vec<ffi::Shared> {
+    let mut v = Vec::with_capacity(8);
+    v.push(ffi::Shared { z: 2020 });
+    v.push(ffi::Shared { z: 2021 });
+    v
+}
+
+fn r_try_return_rust_vec_shared(fail: bool) -> Result<Vec<ffi::Shared>, Error> {
+    if fail {
+        Err(Error)
+    } else {
+        Ok(r_return_rust_vec_shared())
+    }
+}
+
+fn r_return_boxed_bytes(n: usize) -> Box<[u8]> {
+    (0..n).map(|i| i as u8).collect()
+}
+
+fn r_return_boxed_shared() -> Box<[ffi::Shared]> {
+    vec![ffi::Shared { z: 2020 }, ffi::Shared { z: 2021 }].into_boxed_slice()
+}
+
+fn r_try_return_boxed_i32(fail: bool) -> Result<Box<[i32]>, Error> {
+    if fail {
+        Err(Error)
+    } else {
+        Ok(Box::new([-1, 2020]))
+    }
+}
+
+fn r_return_vec_string() -> Vec<String> {
+    vec!["a".into(), "b".into()]
+}
+
+fn r_join_vec_string(v: Vec<String>) -> String {
+    v.join(",")
+}
+
 fn r_take_rust_string(s: String) {
     assert_eq!(s, "2020");
 }
@@ -160,6 +703,41 @@ fn r_take_unique_ptr_string(s: UniquePtr<CxxString>) {
     assert_eq!(s.as_ref().unwrap().to_str().unwrap(), "2020");
 }
 
+fn r_take_ref_cxx_string(s: &CxxString) -> usize {
+    s.len()
+}
+
+fn r_append_cxx_string(s: &mut CxxString) {
+    s.push_str("-rust");
+}
+
+// The keys in iteration order, as decimal digits.
+fn r_take_map_shared(m: &CxxMap<u32, ffi::Shared>) -> usize {
+    m.iter()
+        .fold(0, |digits, (key, _)| digits * 10 + *key as usize)
+}
+
+fn r_get_map_shared(m: &CxxMap<u32, ffi::Shared>, key: u32) -> usize {
+    m.get(&key).map_or(0, |shared| shared.z)
+}
+
+// Calls back into itself through C++ when depth is nonzero, which the
+// #[cxx_non_reentrant] guard rejects in debug builds.
+fn r_log(depth: usize) -> usize {
+    match depth {
+        0 => 1,
+        _ => 1 + ffi::c_call_logger(depth - 1),
+    }
+}
+
+fn compute(n: u32) -> u32 {
+    n + 1
+}
+
+fn r_pure_square(n: u32) -> u32 {
+    n * n
+}
+
 fn r_try_return_void() -> Result<(), Error> {
     Ok(())
 }
@@ -168,6 +746,32 @@ fn r_try_return_primitive() -> Result<usize, Error> {
     Ok(2020)
 }
 
+fn r_system_abi(a: u32, b: u64, c: u8) -> u64 {
+    a as u64 * b + c as u64
+}
+
+fn r_next_char(c: char) -> char {
+    std::char::from_u32(c as u32 + 1).unwrap()
+}
+
+fn r_try_next_char(c: char) -> Result<char, Error> {
+    Ok(r_next_char(c))
+}
+
 fn r_fail_return_primitive() -> Result<usize, Error> {
     Err(Error)
 }
+
+fn r_fail_return_error_code() -> Result<usize, ErrorCode> {
+    Err(ErrorCode(7))
+}
+
+fn r_try_typed_error(fail: bool) -> Result<usize, ffi::SharedString> {
+    if fail {
+        Err(ffi::SharedString {
+            msg: "typed".to_owned(),
+        })
+    } else {
+        Ok(2020)
+    }
+}
@@ -0,0 +1,17 @@
+// Only built on Linux: the section name has to suit ELF, and the linker there
+// defines __start_/__stop_ symbols around any section named like a C
+// identifier, which is how the test finds it.
+
+/// Bindings exporting a Rust function into a custom link section.
+#[cxx::bridge(namespace = tests::link_section)]
+#[allow(missing_docs)]
+pub mod ffi {
+    extern "Rust" {
+        #[link_section = "cxxbridge_test"]
+        fn r_in_link_section() -> usize;
+    }
+}
+
+fn r_in_link_section() -> usize {
+    2020
+}
@@ -0,0 +1,13 @@
+// Shares its namespace and function name with module_b.rs; only the bridge mod
+// name tells the generated symbols apart.
+
+/// Bindings to the nullary init in tests/ffi/modules.h.
+#[cxx::bridge]
+#[allow(missing_docs)]
+pub mod ffi_a {
+    extern "C" {
+        include!("tests/ffi/modules.h");
+
+        fn init() -> usize;
+    }
+}
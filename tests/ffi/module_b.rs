@@ -0,0 +1,13 @@
+// Shares its namespace and function name with module_a.rs; only the bridge mod
+// name tells the generated symbols apart.
+
+/// Bindings to the unary init in tests/ffi/modules.h.
+#[cxx::bridge]
+#[allow(missing_docs)]
+pub mod ffi_b {
+    extern "C" {
+        include!("tests/ffi/modules.h");
+
+        fn init(n: usize) -> usize;
+    }
+}
@@ -0,0 +1,13 @@
+// Shares its namespace, bridge mod name and function name with module_d.rs;
+// only the `module` argument tells the generated symbols apart.
+
+/// Bindings to the nullary init in tests/ffi/modules.h.
+#[cxx::bridge(module = module_c::ffi)]
+#[allow(missing_docs)]
+pub mod ffi {
+    extern "C" {
+        include!("tests/ffi/modules.h");
+
+        fn init() -> usize;
+    }
+}
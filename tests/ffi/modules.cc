@@ -0,0 +1,7 @@
+#include "tests/ffi/modules.h"
+#include "tests/ffi/module_a.rs.h"
+#include "tests/ffi/module_b.rs.h"
+
+size_t init() { return 2020; }
+
+size_t init(size_t n) { return n + 1; }
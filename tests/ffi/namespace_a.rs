@@ -0,0 +1,25 @@
+// Shares its item names with namespace_b.rs; only the namespace tells the
+// generated symbols apart.
+
+/// Bindings to the org::example::a half of tests/ffi/namespaces.h.
+#[cxx::bridge(namespace = "org::example::a")]
+#[allow(missing_docs)]
+pub mod ffi {
+    struct Shared {
+        z: usize,
+    }
+
+    extern "C" {
+        include!("tests/ffi/namespaces.h");
+
+        fn c_make_shared(z: usize) -> Shared;
+    }
+
+    extern "Rust" {
+        fn r_scale(z: usize) -> usize;
+    }
+}
+
+fn r_scale(z: usize) -> usize {
+    z * 2
+}
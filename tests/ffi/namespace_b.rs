@@ -0,0 +1,26 @@
+// Shares its item names with namespace_a.rs; only the namespace tells the
+// generated symbols apart.
+
+/// Bindings to the org::example::b half of tests/ffi/namespaces.h.
+#[cxx::bridge(namespace = org::example::b)]
+#[allow(missing_docs)]
+pub mod ffi {
+    struct Shared {
+        z: usize,
+        tag: u8,
+    }
+
+    extern "C" {
+        include!("tests/ffi/namespaces.h");
+
+        fn c_make_shared(z: usize) -> Shared;
+    }
+
+    extern "Rust" {
+        fn r_scale(z: usize) -> usize;
+    }
+}
+
+fn r_scale(z: usize) -> usize {
+    z * 3
+}
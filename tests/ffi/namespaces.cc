@@ -0,0 +1,15 @@
+#include "tests/ffi/namespaces.h"
+#include "tests/ffi/namespace_a.rs.h"
+#include "tests/ffi/namespace_b.rs.h"
+
+namespace org {
+namespace example {
+namespace a {
+Shared c_make_shared(size_t z) { return Shared{r_scale(z)}; }
+} // namespace a
+
+namespace b {
+Shared c_make_shared(size_t z) { return Shared{r_scale(z), 1}; }
+} // namespace b
+} // namespace example
+} // namespace org
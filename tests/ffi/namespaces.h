@@ -0,0 +1,16 @@
+#pragma once
+#include <cstddef>
+
+namespace org {
+namespace example {
+namespace a {
+struct Shared;
+Shared c_make_shared(size_t z);
+} // namespace a
+
+namespace b {
+struct Shared;
+Shared c_make_shared(size_t z);
+} // namespace b
+} // namespace example
+} // namespace org
@@ -0,0 +1,24 @@
+#include "tests/ffi/no_exceptions.h"
+#include "tests/ffi/no_exceptions.rs.h"
+
+#if defined(__cpp_exceptions)
+#error "expected to be compiled with -fno-exceptions"
+#endif
+
+namespace no_exceptions {
+
+Counter::Counter(size_t n) : n(n) {}
+
+size_t Counter::increment() { return ++this->n; }
+
+std::unique_ptr<Counter> c_new_counter(size_t start) {
+  return std::unique_ptr<Counter>(new Counter(start));
+}
+
+Point c_scale_twice(Point p, int32_t factor) {
+  return r_scale(r_scale(p, factor), factor);
+}
+
+size_t c_greeting_len() { return r_greeting().size(); }
+
+} // namespace no_exceptions
@@ -0,0 +1,22 @@
+#pragma once
+#include "rust/cxx.h"
+#include <memory>
+
+namespace no_exceptions {
+
+struct Point;
+
+class Counter {
+public:
+  Counter(size_t n);
+  size_t increment();
+
+private:
+  size_t n;
+};
+
+std::unique_ptr<Counter> c_new_counter(size_t start);
+Point c_scale_twice(Point p, int32_t factor);
+size_t c_greeting_len();
+
+} // namespace no_exceptions
@@ -0,0 +1,38 @@
+// Generated with cxx::Build::no_exceptions and compiled with -fno-exceptions.
+
+/// Bindings to tests/ffi/no_exceptions.h.
+#[cxx::bridge(namespace = no_exceptions)]
+#[allow(missing_docs)]
+pub mod ffi {
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    extern "C" {
+        include!("tests/ffi/no_exceptions.h");
+
+        type Counter;
+
+        fn c_new_counter(start: usize) -> UniquePtr<Counter>;
+        fn increment(self: Pin<&mut Counter>) -> usize;
+        fn c_scale_twice(p: Point, factor: i32) -> Point;
+        fn c_greeting_len() -> usize;
+    }
+
+    extern "Rust" {
+        fn r_scale(p: Point, factor: i32) -> Point;
+        fn r_greeting() -> String;
+    }
+}
+
+fn r_scale(p: ffi::Point, factor: i32) -> ffi::Point {
+    ffi::Point {
+        x: p.x * factor,
+        y: p.y * factor,
+    }
+}
+
+fn r_greeting() -> String {
+    "hello".to_owned()
+}
@@ -0,0 +1,17 @@
+#include "tests/ffi/pointer_sized.h"
+#include "tests/ffi/pointer_sized.rs.h"
+#include <type_traits>
+
+namespace tests {
+namespace pointer_sized {
+
+static_assert(std::is_same<decltype(r_offset(0, 0)), uintptr_t>::value, "");
+
+uintptr_t c_offset(uintptr_t base, intptr_t delta) { return base + delta; }
+
+uintptr_t c_call_r_offset(uintptr_t base, intptr_t delta) {
+  return r_offset(base, delta);
+}
+
+} // namespace pointer_sized
+} // namespace tests
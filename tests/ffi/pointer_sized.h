@@ -0,0 +1,11 @@
+#pragma once
+#include <cstdint>
+
+namespace tests {
+namespace pointer_sized {
+
+uintptr_t c_offset(uintptr_t base, intptr_t delta);
+uintptr_t c_call_r_offset(uintptr_t base, intptr_t delta);
+
+} // namespace pointer_sized
+} // namespace tests
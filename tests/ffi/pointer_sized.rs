@@ -0,0 +1,22 @@
+// Generated with usize spelled uintptr_t and isize spelled intptr_t; see
+// build.rs.
+
+/// Bindings to tests/ffi/pointer_sized.h.
+#[cxx::bridge(namespace = tests::pointer_sized)]
+#[allow(missing_docs)]
+pub mod ffi {
+    extern "C" {
+        include!("tests/ffi/pointer_sized.h");
+
+        fn c_offset(base: usize, delta: isize) -> usize;
+        fn c_call_r_offset(base: usize, delta: isize) -> usize;
+    }
+
+    extern "Rust" {
+        fn r_offset(base: usize, delta: isize) -> usize;
+    }
+}
+
+fn r_offset(base: usize, delta: isize) -> usize {
+    base.wrapping_add(delta as usize)
+}
@@ -1,7 +1,12 @@
 #include "tests/ffi/tests.h"
 #include "tests/ffi/lib.rs.h"
+#include <algorithm>
+#include <atomic>
+#include <chrono>
 #include <cstring>
 #include <stdexcept>
+#include <thread>
+#include <unordered_set>
 
 extern "C" void cxx_test_suite_set_correct() noexcept;
 extern "C" tests::R *cxx_test_suite_get_box() noexcept;
@@ -9,14 +14,127 @@ extern "C" bool cxx_test_suite_r_is_correct(const tests::R *) noexcept;
 
 namespace tests {
 
-C::C(size_t n) : n(n) {}
+C::C(size_t n)
+    : n(n), bytes{20, 2, 0, 0}, config{{"zeta", 26}, {"alpha", 1}, {"mid", 13}},
+      tags{8, 5, 1} {}
 
 size_t C::get() const { return this->n; }
 
+size_t C::set(size_t n) {
+  this->n = n;
+  return this->n;
+}
+
+size_t C::increment() { return ++this->n; }
+
+size_t C::get_fail() const { throw std::runtime_error("unimplemented"); }
+
+rust::Slice<const uint8_t> C::get_bytes() const {
+  return rust::Slice<const uint8_t>(this->bytes.data(), this->bytes.size());
+}
+
+const std::map<std::string, int32_t> &C::get_config() const {
+  return this->config;
+}
+
+const std::set<uint32_t> &C::get_tags() const { return this->tags; }
+
+std::string C::to_string() const {
+  return "C { n: " + std::to_string(this->n) + " }";
+}
+
+Counter::Counter(size_t n) : n(n) {}
+
+size_t Counter::getCount() const { return this->n; }
+
+static size_t next_component_id = 1;
+static size_t last_destroyed_component = 0;
+
+Component::Component() : n(next_component_id++) {}
+
+Component::~Component() { last_destroyed_component = this->n; }
+
+size_t Component::id() const { return this->n; }
+
+size_t c_last_destroyed_component() { return last_destroyed_component; }
+
 size_t c_return_primitive() { return 2020; }
 
+size_t c_sizeof_padded() { return sizeof(Padded); }
+
+size_t cReturnCamelCase() { return 2020; }
+
+std::unique_ptr<Counter> c_new_counter(size_t start) {
+  return std::unique_ptr<Counter>(new Counter(start));
+}
+
+size_t c_deprecated_return_primitive() { return 2020; }
+
 Shared c_return_shared() { return Shared{2020}; }
 
+Extensible c_return_extensible() { return Extensible{2020}; }
+
+static_assert(offsetof(Nested, tag) == sizeof(Shared), "");
+static_assert(sizeof(Nested) == 2 * sizeof(size_t), "");
+
+static_assert(Nested_size == sizeof(Nested), "");
+static_assert(Nested_align == alignof(Nested), "");
+static_assert(Nested_shared_offset == 0, "");
+static_assert(Nested_tag_offset == Shared_size, "");
+static_assert(Settings_block_offset == offsetof(Settings, block), "");
+
+static_assert(std::is_nothrow_constructible<Nested, Shared, uint32_t>::value,
+              "");
+static_assert(std::is_default_constructible<Nested>::value, "");
+
+Nested c_return_nested() { return Nested(Shared{2020}, 1); }
+
+// Compiled as C++11, where Settings and Block are still aggregates.
+Settings c_return_settings() {
+  return Settings{3, 0.5, rust::String("fast"), Block{1, 2, 3, 4}};
+}
+
+static_assert(
+    std::is_nothrow_constructible<SharedString, rust::String>::value, "");
+
+Payload c_return_payload(float real) {
+  Payload payload;
+  payload.wide = 0;
+  payload.real = real;
+  return payload;
+}
+
+Pixel c_brighten(Pixel pixel) {
+  for (auto &channel : pixel.rgba) {
+    channel += 1;
+  }
+  for (auto &weight : pixel.weights) {
+    weight *= 2;
+  }
+  return pixel;
+}
+
+std::array<double, 3> c_return_array() { return {0.5, 1.5, 2.5}; }
+
+int32_t c_sum_array(std::array<int32_t, 4> values,
+                    const std::array<int32_t, 2> &extra) {
+  int32_t sum = 0;
+  for (auto value : values) {
+    sum += value;
+  }
+  for (auto value : extra) {
+    sum += value;
+  }
+  return sum;
+}
+
+SharedString c_return_shared_string() { return SharedString("2020"); }
+
+static_assert(static_cast<uint8_t>(Color::Blue) == 5, "");
+static_assert(static_cast<int>(Level::Mid) == 0, "");
+
+Color c_return_enum() { return Color::Blue; }
+
 rust::Box<R> c_return_box() {
   return rust::Box<R>::from_raw(cxx_test_suite_get_box());
 }
@@ -25,8 +143,27 @@ std::unique_ptr<C> c_return_unique_ptr() {
   return std::unique_ptr<C>(new C{2020});
 }
 
+std::shared_ptr<C> c_return_shared_ptr() {
+  return std::shared_ptr<C>(new C{2020});
+}
+
+std::unique_ptr<C> c_return_optional_unique_ptr(bool some) {
+  if (!some) {
+    return nullptr;
+  }
+  return std::unique_ptr<C>(new C{2020});
+}
+
+const C *c_return_borrowed(const C &c, bool found) {
+  return found ? &c : nullptr;
+}
+
 const size_t &c_return_ref(const Shared &shared) { return shared.z; }
 
+const size_t *c_return_optional_ref(const Shared &shared, bool some) {
+  return some ? &shared.z : nullptr;
+}
+
 rust::Str c_return_str(const Shared &shared) {
   (void)shared;
   return "2020";
@@ -34,10 +171,70 @@ rust::Str c_return_str(const Shared &shared) {
 
 rust::String c_return_rust_string() { return "2020"; }
 
+std::unique_ptr<uint8_t[]> c_return_unique_array(size_t len) {
+  std::unique_ptr<uint8_t[]> array(new uint8_t[len]);
+  for (size_t i = 0; i < len; i++) {
+    array[i] = static_cast<uint8_t>(i * 2);
+  }
+  return array;
+}
+
 std::unique_ptr<std::string> c_return_unique_ptr_string() {
   return std::unique_ptr<std::string>(new std::string("2020"));
 }
 
+std::unique_ptr<std::u16string> c_return_unique_ptr_u16string() {
+  auto s = std::unique_ptr<std::u16string>(new std::u16string(u"2020\u00e9"));
+  s->push_back(0xD800); // unpaired high surrogate
+  return s;
+}
+
+std::unique_ptr<std::vector<uint8_t>> c_return_unique_ptr_vector_u8() {
+  auto vec = std::unique_ptr<std::vector<uint8_t>>(new std::vector<uint8_t>());
+  vec->push_back(86);
+  vec->push_back(75);
+  vec->push_back(30);
+  vec->push_back(9);
+  return vec;
+}
+
+std::unique_ptr<std::vector<bool>> c_return_unique_ptr_vector_bool() {
+  return std::unique_ptr<std::vector<bool>>(
+      new std::vector<bool>{true, false, true});
+}
+
+std::unique_ptr<std::vector<Shared>> c_return_unique_ptr_vector_shared() {
+  auto vec = std::unique_ptr<std::vector<Shared>>(new std::vector<Shared>());
+  vec->push_back(Shared{1010});
+  vec->push_back(Shared{1011});
+  return vec;
+}
+
+rust::Vec<Shared> c_return_rust_vec_shared() {
+  std::vector<Shared> shared = *c_return_unique_ptr_vector_shared();
+  shared.push_back(Shared{1012});
+  rust::Vec<Shared> vec;
+  vec.reserve(shared.size());
+  for (const Shared &elem : shared) {
+    vec.push_back(elem);
+  }
+  return vec;
+}
+
+std::tuple<int32_t, double, bool> c_return_tuple(int32_t n) {
+  return std::make_tuple(n * 2, n / 2.0, n > 0);
+}
+
+std::tuple<Shared, SharedString> c_return_tuple_shared(size_t n) {
+  return std::make_tuple(Shared{n}, SharedString(std::to_string(n)));
+}
+
+char32_t c_next_char(char32_t c) { return c + 1; }
+
+char32_t c_return_char_unchecked(uint32_t value) { return value; }
+
+uint64_t c_system_abi(uint32_t a, uint64_t b, uint8_t c) { return a * b + c; }
+
 void c_take_primitive(size_t n) {
   if (n == 2020) {
     cxx_test_suite_set_correct();
@@ -50,18 +247,86 @@ void c_take_shared(Shared shared) {
   }
 }
 
+void c_take_nested(Nested nested) {
+  if (nested == Nested(Shared{2020}, 1) && nested != Nested(Shared{2021}, 1) &&
+      nested != Nested(Shared{2020}, 2)) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+void c_take_payload(Payload payload) {
+  if (payload.bits == 0x3fc00000) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+void c_take_shared_string(SharedString shared) {
+  SharedString moved(std::move(shared));
+  if (std::string(moved.msg) == "2020" && shared.msg.size() == 0) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+void c_take_enum(Color color) {
+  if (color == Color::Green && static_cast<uint8_t>(color) == 4) {
+    cxx_test_suite_set_correct();
+  }
+}
+
 void c_take_box(rust::Box<R> r) {
   if (cxx_test_suite_r_is_correct(&*r)) {
     cxx_test_suite_set_correct();
   }
 }
 
+void c_take_box_shared(rust::Box<Shared> shared) {
+  if (shared->z == 2020 && (*shared).z == 2020) {
+    cxx_test_suite_set_correct();
+  }
+}
+
 void c_take_unique_ptr(std::unique_ptr<C> c) {
   if (c->get() == 2020) {
     cxx_test_suite_set_correct();
   }
 }
 
+void c_take_shared_ptr(std::shared_ptr<C> c) {
+  if (c->get() == 2020) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+rust::Arc<R> c_return_arc(rust::Arc<R> r) {
+  rust::Arc<R> copy = r;
+  return copy;
+}
+
+size_t c_arc_strong_count_with_copies(rust::Arc<R> r, size_t copies) {
+  std::vector<rust::Arc<R>> held(copies, r);
+  return r_arc_strong_count(r);
+}
+
+size_t c_shared_ptr_use_count(const std::shared_ptr<C> &c) {
+  return c.use_count();
+}
+
+size_t c_vector_capacity_u8(const std::vector<uint8_t> &v) {
+  return v.capacity();
+}
+
+size_t c_vector_count_true(const std::vector<bool> &v) {
+  return std::count(v.begin(), v.end(), true);
+}
+
+unsigned __int128 c_roundtrip_u128(unsigned __int128 n) { return n; }
+
+__int128 c_negate_i128(__int128 n) { return -n; }
+
+void c_make_unique_ptr(std::unique_ptr<C> &out, size_t n) {
+  out = std::unique_ptr<C>(new C{n});
+}
+
 void c_take_ref_r(const R &r) {
   if (cxx_test_suite_r_is_correct(&r)) {
     cxx_test_suite_set_correct();
@@ -74,36 +339,269 @@ void c_take_ref_c(const C &c) {
   }
 }
 
+size_t c_count_distinct_tiles(rust::Slice<const Tile> tiles) {
+  std::unordered_set<Tile> distinct(tiles.data(), tiles.data() + tiles.size());
+  return distinct.size();
+}
+
+size_t c_hash_named(Named named) { return std::hash<Named>{}(named); }
+
+static_assert(sizeof(LargeConfig) == 256, "");
+
+// Returns the address it was given so the caller can tell that no copy was
+// made on the way in.
+size_t c_take_ref_large_config(const LargeConfig &config) {
+  return reinterpret_cast<size_t>(&config);
+}
+
+void c_take_borrowed(const C *c) {
+  if (c != nullptr && c->get() == 2020) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+size_t c_take_optional_ref(const Shared *shared) {
+  return shared == nullptr ? 0 : shared->z;
+}
+
 void c_take_str(rust::Str s) {
   if (std::string(s) == "2020") {
     cxx_test_suite_set_correct();
   }
 }
 
+void c_take_mut_slice_shared(rust::Slice<Shared> s) {
+  if (s.size() == 2 && s.data()[0].z == 2020 && s.data()[1].z == 2021) {
+    for (size_t i = 0; i < s.size(); i++) {
+      s.data()[i].z += 10;
+    }
+    cxx_test_suite_set_correct();
+  }
+}
+
+void c_take_slice_u8(rust::Slice<const uint8_t> s) {
+  if (s.size() == 4 && std::memcmp(s.data(), "2020", 4) == 0) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+void c_zero_slice_u8(rust::Slice<uint8_t> s) {
+  std::memset(s.data(), 0, s.size());
+}
+
+int64_t c_sum_slice_i32(rust::Slice<const int32_t> s) {
+  int64_t sum = 0;
+  for (size_t i = 0; i < s.size(); i++) {
+    sum += s.data()[i];
+  }
+  return sum;
+}
+
+int64_t c_max_window_sum(rust::Slice<const int32_t> s, size_t width) {
+  int64_t max = 0;
+  for (size_t start = 0; start + width <= s.size(); start++) {
+    int64_t sum = c_sum_slice_i32(s.subslice(start, width));
+    if (start == 0 || sum > max) {
+      max = sum;
+    }
+  }
+  return max;
+}
+
+size_t c_try_subslice_len(rust::Slice<const uint8_t> s, size_t start,
+                          size_t len) {
+  return s.subslice(start, len).size();
+}
+
+size_t c_sum_slice_shared(rust::Slice<const Shared> s) {
+  size_t sum = 0;
+  for (size_t i = 0; i < s.size(); i++) {
+    sum += s.data()[i].z;
+  }
+  return sum;
+}
+
+void c_take_ref_vec(const rust::Vec<uint8_t> &v) {
+  if (v.size() == 4 && v[0] == 20 && v[1] == 2 && v[2] == 0 && v[3] == 0) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+void c_grow_vec(rust::Vec<uint8_t> &v) {
+  for (uint8_t i = 0; i < 100; i++) {
+    v.push_back(i);
+  }
+}
+
+void c_shrink_vec(rust::Vec<uint8_t> &v) { v.resize(2); }
+
+void c_tokenize(rust::Str s, rust::Vec<rust::Vec<uint8_t>> &tokens) {
+  tokens.clear();
+  rust::Vec<uint8_t> token;
+  for (char c : std::string(s)) {
+    if (c == ' ') {
+      tokens.push_back(std::move(token));
+    } else {
+      token.push_back(static_cast<uint8_t>(c));
+    }
+  }
+  tokens.push_back(std::move(token));
+}
+
+size_t c_count_token_bytes(const rust::Vec<rust::Vec<uint8_t>> &tokens) {
+  size_t count = 0;
+  for (size_t i = 0; i < tokens.size(); i++) {
+    count += tokens[i].size();
+  }
+  return count;
+}
+
+rust::Vec<rust::String> c_return_vec_string() {
+  rust::Vec<rust::String> v;
+  v.push_back(rust::String("2020"));
+  rust::String cxx("C++");
+  v.push_back(cxx);
+  return v;
+}
+
+rust::String c_join_vec_string(rust::Vec<rust::String> v) {
+  std::string joined;
+  for (size_t i = 0; i < v.size(); i++) {
+    if (i > 0) {
+      joined += ",";
+    }
+    joined += std::string(v[i]);
+  }
+  return joined;
+}
+
 void c_take_rust_string(rust::String s) {
   if (std::string(s) == "2020") {
     cxx_test_suite_set_correct();
   }
 }
 
+size_t c_sum_unique_array(std::unique_ptr<uint8_t[]> array, size_t len) {
+  size_t sum = 0;
+  for (size_t i = 0; i < len; i++) {
+    sum += array[i];
+  }
+  return sum;
+}
+
 void c_take_unique_ptr_string(std::unique_ptr<std::string> s) {
   if (*s == "2020") {
     cxx_test_suite_set_correct();
   }
 }
 
+void c_take_binary_string(const std::string &s) {
+  if (s == std::string("\0bin\0ary\xFF", 9)) {
+    cxx_test_suite_set_correct();
+  }
+}
+
+void c_take_u16string(const std::u16string &s) {
+  if (s == u"2020\U0001F980") {
+    cxx_test_suite_set_correct();
+  }
+}
+
+void c_append_cxx_string(std::string &s) { s += "-cxx"; }
+
+void c_append_to(std::string &s, rust::Str suffix) {
+  s += std::string(suffix);
+}
+
+void c_reset(std::unique_ptr<std::string> &s, rust::Str value) {
+  s = std::unique_ptr<std::string>(new std::string(value));
+}
+
+size_t c_call_logger(size_t depth) { return r_log(depth); }
+
 void c_take_callback(rust::Fn<size_t(rust::String)> callback) {
   callback("2020");
 }
 
+int32_t c_apply_twice(rust::Fn<int32_t(int32_t)> f, int32_t n) {
+  return f(f(n));
+}
+
+rust::String c_greet(rust::Fn<rust::String(rust::Str)> f) {
+  rust::String greeting = f("world");
+  return rust::String(std::string(greeting) + "!");
+}
+
+size_t c_build(rust::Fn<std::unique_ptr<C>(size_t)> f) {
+  std::unique_ptr<C> c = f(2020);
+  return c ? c->get() : 0;
+}
+
+size_t c_for_each_mut(rust::Fn<void(Shared &)> f) {
+  std::vector<Shared> v{Shared{1}, Shared{2}, Shared{3}};
+  size_t sum = 0;
+  for (auto &shared : v) {
+    f(shared);
+    sum += shared.z;
+  }
+  return sum;
+}
+
+// Sums the callback's results for 1 through 5. If the callback fails along the
+// way, gives back 1000 plus the sum so far.
+uint32_t c_process(rust::TryFn<uint32_t(uint32_t)> cb) {
+  uint32_t sum = 0;
+  try {
+    for (uint32_t n = 1; n <= 5; n++) {
+      sum += cb(n);
+    }
+  } catch (const rust::Error &e) {
+    return std::strcmp(e.what(), "3 is unlucky") == 0 ? 1000 + sum : 0;
+  }
+  return sum;
+}
+
+size_t compute(size_t n) { return n * 2; }
+
+uint32_t c_pure_square(uint32_t n) { return n * n; }
+
+static std::atomic<size_t> init_once_calls{0};
+
+size_t c_init_once_config() {
+  size_t call = ++init_once_calls;
+  // Widen the window for a racing second caller.
+  std::this_thread::sleep_for(std::chrono::milliseconds(10));
+  return call;
+}
+
+size_t c_init_once_calls() { return init_once_calls; }
+
+std::chrono::system_clock::time_point c_now() {
+  return std::chrono::system_clock::now();
+}
+
+std::chrono::system_clock::time_point
+c_add_seconds(std::chrono::system_clock::time_point time, int64_t seconds) {
+  return time + std::chrono::seconds(seconds);
+}
+
 void c_try_return_void() {}
 
 size_t c_try_return_primitive() { return 2020; }
 
 size_t c_fail_return_primitive() { throw std::logic_error("logic error"); }
 
+size_t c_fail_throw_int() { throw 2020; }
+
 rust::Box<R> c_try_return_box() { return c_return_box(); }
 
+rust::Box<R> c_fail_return_box() {
+  // The box allocated here is dropped during unwinding; nothing is handed
+  // back to Rust on the error path.
+  rust::Box<R> box = c_return_box();
+  throw std::logic_error("logic error");
+}
+
 const rust::String &c_try_return_ref(const rust::String &s) { return s; }
 
 rust::Str c_try_return_str(rust::Str s) { return s; }
@@ -114,6 +612,46 @@ std::unique_ptr<std::string> c_try_return_unique_ptr_string() {
   return c_return_unique_ptr_string();
 }
 
+std::tuple<uint8_t, size_t> c_try_return_tuple() {
+  return std::make_tuple(20, 2020);
+}
+
+std::tuple<uint8_t, size_t> c_fail_return_tuple() {
+  throw std::logic_error("logic error");
+}
+
+std::tuple<SharedString, Shared> c_fail_return_tuple_shared() {
+  throw std::logic_error("logic error");
+}
+
+std::unique_ptr<C> c_try_new_c(size_t n) {
+  if (n == 0) {
+    throw std::invalid_argument("n must be nonzero");
+  }
+  return std::unique_ptr<C>(new C(n));
+}
+
+size_t c_throw_classified(uint8_t kind) {
+  switch (kind) {
+  case 1:
+    throw std::invalid_argument("invalid argument");
+  case 2:
+    throw std::out_of_range("out of range");
+  case 3:
+    throw std::runtime_error("runtime error");
+  default:
+    return 2020;
+  }
+}
+
+rust::String c_throw_classified_string(uint8_t kind) {
+  return std::to_string(c_throw_classified(kind));
+}
+
+char32_t c_try_return_char(uint32_t value) { return value; }
+
+char32_t c_try_return_char_classified(uint32_t value) { return value; }
+
 extern "C" C *cxx_test_suite_get_unique_ptr() noexcept {
   return std::unique_ptr<C>(new C{2020}).release();
 }
@@ -134,21 +672,171 @@ extern "C" const char *cxx_run_test() noexcept {
 
   ASSERT(r_return_primitive() == 2020);
   ASSERT(r_return_shared().z == 2020);
+  ASSERT(r_return_nested().shared.z == 2020 && r_return_nested().tag == 1);
+  ASSERT(r_return_payload(0x3fc00000).real == 1.5f);
+  ASSERT((r_return_array() == std::array<double, 3>{0.5, 1.5, 2.5}));
+  ASSERT(r_sum_array({1, 2, 3, 4}) == 10);
+  ASSERT(std::string(r_return_shared_string().msg) == "2020");
+  ASSERT(r_return_r2(2020)->get() == 2020);
+  ASSERT(r_return_r2(2020)->set(2021) == 2021);
+  ASSERT(std::string(r_return_r2(2020)->get_name()) == "R2(2020)");
+  ASSERT(r_return_r3(2020)->getValue() == 2020);
+  ASSERT(rReturnCamelCase() == 2020);
+  rust::Arc<R2> arc = r_return_arc_r2(2020);
+  rust::Arc<R2> arc_copy = arc;
+  arc = r_return_arc_r2(2021);
+  ASSERT(arc->get() == 2021 && arc_copy->get() == 2020);
+  arc = arc_copy;
+  ASSERT(&*arc == &*arc_copy);
+  rust::Arc<R2> arc_moved = std::move(arc);
+  ASSERT(arc_moved && !arc);
+  ASSERT(r_return_enum(-5) == Level::Low);
+  ASSERT(r_return_enum(5) == Level::Mid);
+  ASSERT(static_cast<int>(r_return_enum(50)) == 10);
   ASSERT(cxx_test_suite_r_is_correct(&*r_return_box()));
+  {
+    rust::Box<R> box = r_return_box();
+    ASSERT(box);
+    rust::Box<R> moved = std::move(box);
+    ASSERT(moved && !box);
+  }
   ASSERT(r_return_unique_ptr()->get() == 2020);
   ASSERT(r_return_ref(Shared{2020}) == 2020);
+  Shared shared{2020};
+  ASSERT(r_return_optional_ref(shared, true) == &shared.z);
+  ASSERT(r_return_optional_ref(shared, false) == nullptr);
   ASSERT(std::string(r_return_str(Shared{2020})) == "2020");
   ASSERT(std::string(r_return_rust_string()) == "2020");
+  ASSERT(r_return_rust_string() == "2020");
+  ASSERT("2020" == r_return_rust_string());
+  ASSERT(r_return_rust_string() != "2021");
+  ASSERT(r_return_rust_string() == std::string("2020"));
+  ASSERT(std::string("2021") != r_return_rust_string());
+  ASSERT(r_return_rust_string() == rust::String("2020"));
+  ASSERT(r_return_rust_string() != rust::String("202"));
   ASSERT(*r_return_unique_ptr_string() == "2020");
 
   r_take_primitive(2020);
   r_take_shared(Shared{2020});
+  r_take_enum(Level::High);
+  r_take_nested(Nested{Shared{2020}, 1});
+  SharedString shared_string{rust::String("2020")};
+  SharedString other_string{rust::String()};
+  swap(shared_string.msg, other_string.msg);
+  ASSERT(shared_string.msg.size() == 0);
+  r_take_shared_string(std::move(other_string));
   r_take_unique_ptr(std::unique_ptr<C>(new C{2020}));
+  std::shared_ptr<C> shared_ptr(new C{2020});
+  r_take_shared_ptr(shared_ptr);
+  ASSERT(shared_ptr.use_count() == 1);
   r_take_ref_c(C{2020});
+  LargeConfig config{};
+  ASSERT(r_take_ref_large_config(config) ==
+         reinterpret_cast<size_t>(&config));
+  Shared optional{2020};
+  ASSERT(r_take_optional_ref(&optional) == 2020);
+  ASSERT(r_take_optional_ref(nullptr) == 0);
   r_take_str(rust::Str("2020"));
+  r_take_slice_u8(rust::Slice<const uint8_t>(
+      reinterpret_cast<const uint8_t *>("2020"), 4));
+  {
+    rust::Vec<rust::Vec<uint8_t>> jagged;
+    r_fill_jagged(jagged);
+    ASSERT(jagged.size() == 3);
+    ASSERT(jagged[0].size() == 2 && jagged[0][1] == '0');
+    ASSERT(jagged[1].empty());
+    jagged.resize(1);
+    ASSERT(jagged.size() == 1 && jagged[0].size() == 2);
+  }
+  {
+    rust::Vec<Shared> shared = r_return_rust_vec_shared();
+    ASSERT(shared.size() == 2 && shared.capacity() >= 8);
+    ASSERT(shared[0].z == 2020 && shared[1].z == 2021);
+    shared.resize(1);
+    ASSERT(shared.size() == 1 && shared.capacity() >= 8);
+  }
+  {
+    rust::Vec<Shared> shared = r_try_return_rust_vec_shared(false);
+    ASSERT(shared.size() == 2 && shared[1].z == 2021);
+    try {
+      r_try_return_rust_vec_shared(true);
+      ASSERT(false);
+    } catch (const rust::Error &e) {
+      ASSERT(std::strcmp(e.what(), "rust error") == 0);
+    }
+  }
+  {
+    rust::Box<uint8_t[]> bytes = r_return_boxed_bytes(3);
+    ASSERT(bytes.size() == 3 && bytes[0] == 0 && bytes[2] == 2);
+    rust::Box<uint8_t[]> empty = r_return_boxed_bytes(0);
+    ASSERT(empty.size() == 0);
+    bytes = std::move(empty);
+    ASSERT(bytes && bytes.size() == 0 && !empty);
+    rust::Box<Shared[]> shared = r_return_boxed_shared();
+    ASSERT(shared.size() == 2 && shared[1].z == 2021);
+    rust::Box<int32_t[]> ints = r_try_return_boxed_i32(false);
+    ASSERT(ints.size() == 2 && ints.data()[0] == -1);
+    try {
+      r_try_return_boxed_i32(true);
+      ASSERT(false);
+    } catch (const rust::Error &) {
+    }
+  }
+  {
+    rust::Vec<rust::String> strings = r_return_vec_string();
+    ASSERT(strings.size() == 2);
+    ASSERT(std::string(strings[0]) == "a" && std::string(strings[1]) == "b");
+    strings.push_back(strings[0]);
+    strings.push_back(rust::String("c"));
+    ASSERT(std::string(r_join_vec_string(std::move(strings))) == "a,b,a,c");
+    ASSERT(strings.empty());
+  }
+  {
+    rust::Vec<uint8_t> bytes;
+    size_t reallocations = 0;
+    for (size_t i = 0; i < 1000; i++) {
+      auto capacity = bytes.capacity();
+      bytes.push_back(static_cast<uint8_t>(i));
+      reallocations += bytes.capacity() != capacity;
+    }
+    ASSERT(bytes.size() == 1000 && bytes[999] == static_cast<uint8_t>(999));
+    ASSERT(reallocations < 20);
+  }
+  uint8_t buffer[4] = {};
+  r_fill_slice_u8(rust::Slice<uint8_t>(buffer, 4));
+  ASSERT(std::memcmp(buffer, "2020", 4) == 0);
+  const double f64s[] = {2000.5, 19.5};
+  ASSERT(r_sum_slice_f64(rust::Slice<const double>(f64s, 2)) == 2020);
+  std::vector<double> no_f64s;
+  ASSERT(rust::Slice<const double>(no_f64s.data(), 0).data() != nullptr);
+  ASSERT(r_sum_slice_f64(rust::Slice<const double>(no_f64s.data(), 0)) == 0);
+  unsigned __int128 u128 = static_cast<unsigned __int128>(2020) << 100;
+  ASSERT(r_roundtrip_u128(u128) == u128);
+#if __cplusplus >= 201703L
+  r_take_str(std::string_view("2020"));
+  ASSERT(std::string_view(r_return_str(Shared{2020})) == "2020");
+  ASSERT(r_return_rust_string() == std::string_view("2020"));
+  ASSERT(std::string_view("2021") != r_return_rust_string());
+#endif
+  Shared slice[] = {Shared{2020}, Shared{2021}};
+  r_take_mut_slice_shared(rust::Slice<Shared>(slice, 2));
+  ASSERT(slice[0].z == 2030 && slice[1].z == 2031);
   r_take_rust_string(rust::String("2020"));
   r_take_unique_ptr_string(
       std::unique_ptr<std::string>(new std::string("2020")));
+  std::string cxx_string = "2020";
+  ASSERT(r_take_ref_cxx_string(cxx_string) == 4);
+  r_append_cxx_string(cxx_string);
+  ASSERT(cxx_string == "2020-rust");
+  std::map<uint32_t, Shared> shared_map;
+  shared_map.emplace(3, Shared{30});
+  shared_map.emplace(1, Shared{10});
+  shared_map.emplace(2, Shared{20});
+  ASSERT(r_take_map_shared(shared_map) == 123);
+  ASSERT(r_get_map_shared(shared_map, 2) == 20);
+  ASSERT(r_get_map_shared(shared_map, 4) == 0);
+  ASSERT(compute(uint32_t(2020)) == 2021);
+  ASSERT(r_pure_square(45) + r_pure_square(45) == 4050);
 
   ASSERT(r_try_return_primitive() == 2020);
   try {
@@ -158,6 +846,32 @@ extern "C" const char *cxx_run_test() noexcept {
     ASSERT(std::strcmp(e.what(), "rust error") == 0);
   }
 
+  try {
+    r_fail_return_error_code();
+    ASSERT(false);
+  } catch (const rust::Error &e) {
+    ASSERT(std::strcmp(e.what(), "error code 7") == 0);
+  }
+
+  ASSERT(r_try_typed_error(false) == 2020);
+  try {
+    r_try_typed_error(true);
+    ASSERT(false);
+  } catch (const rust::TypedError<SharedString> &e) {
+    ASSERT(std::string(e.error().msg) == "typed");
+  }
+
+  ASSERT(r_system_abi(2, 1000, 20) == 2020);
+  ASSERT(r_next_char(U'a') == U'b');
+  ASSERT(r_try_next_char(U'\U0001F980') == U'\U0001F981');
+  try {
+    r_try_next_char(static_cast<char32_t>(0xD800));
+    ASSERT(false);
+  } catch (const rust::Error &e) {
+    ASSERT(std::strcmp(e.what(),
+                       "invalid Unicode scalar value 0xD800 from C++") == 0);
+  }
+
   cxx_test_suite_set_correct();
   return nullptr;
 }
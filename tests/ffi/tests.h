@@ -1,49 +1,196 @@
 #pragma once
 #include "rust/cxx.h"
+#include <array>
+#include <chrono>
+#include <map>
 #include <memory>
+#include <set>
+#include <stdexcept>
 #include <string>
+#include <tuple>
+#include <vector>
 
 namespace tests {
 
 struct R;
+struct R2;
 struct Shared;
+struct Nested;
+struct Extensible;
+struct SharedString;
+struct LargeConfig;
+struct Settings;
+struct Tile;
+struct Named;
+union Payload;
+struct Pixel;
+enum class Color : uint8_t;
+enum class Level : int;
 
 class C {
 public:
   C(size_t n);
+  // Never constructed by value from Rust, so cxx must not require these.
+  C(const C &) = delete;
+  C(C &&) = delete;
   size_t get() const;
+  size_t set(size_t n);
+  size_t increment();
+  size_t get_fail() const;
+  rust::Slice<const uint8_t> get_bytes() const;
+  const std::map<std::string, int32_t> &get_config() const;
+  const std::set<uint32_t> &get_tags() const;
+  std::string to_string() const;
+  template <typename T> T get_as() const { return static_cast<T>(this->n); }
 
 private:
   size_t n;
+  std::vector<uint8_t> bytes;
+  std::map<std::string, int32_t> config;
+  std::set<uint32_t> tags;
 };
 
+class Counter {
+public:
+  Counter(size_t n);
+  size_t getCount() const;
+
+private:
+  size_t n;
+};
+
+// Over-aligned so that Rust has to honor the C++ alignment of the storage.
+class alignas(16) Component {
+public:
+  Component();
+  ~Component();
+  size_t id() const;
+
+private:
+  size_t n;
+};
+
+size_t c_last_destroyed_component();
+
 size_t c_return_primitive();
+size_t c_sizeof_padded();
+size_t cReturnCamelCase();
+std::unique_ptr<Counter> c_new_counter(size_t start);
+size_t c_deprecated_return_primitive();
 Shared c_return_shared();
+Extensible c_return_extensible();
+Nested c_return_nested();
+Settings c_return_settings();
+Payload c_return_payload(float real);
+Pixel c_brighten(Pixel pixel);
+std::array<double, 3> c_return_array();
+int32_t c_sum_array(std::array<int32_t, 4> values,
+                    const std::array<int32_t, 2> &extra);
+SharedString c_return_shared_string();
+Color c_return_enum();
 rust::Box<R> c_return_box();
 std::unique_ptr<C> c_return_unique_ptr();
+std::unique_ptr<C> c_return_optional_unique_ptr(bool some);
+std::shared_ptr<C> c_return_shared_ptr();
+const C *c_return_borrowed(const C &c, bool found);
 const size_t &c_return_ref(const Shared &shared);
+const size_t *c_return_optional_ref(const Shared &shared, bool some);
 rust::Str c_return_str(const Shared &shared);
 rust::String c_return_rust_string();
 std::unique_ptr<std::string> c_return_unique_ptr_string();
+std::unique_ptr<std::u16string> c_return_unique_ptr_u16string();
+std::unique_ptr<uint8_t[]> c_return_unique_array(size_t len);
+std::unique_ptr<std::vector<uint8_t>> c_return_unique_ptr_vector_u8();
+std::unique_ptr<std::vector<bool>> c_return_unique_ptr_vector_bool();
+std::unique_ptr<std::vector<Shared>> c_return_unique_ptr_vector_shared();
+rust::Vec<Shared> c_return_rust_vec_shared();
+std::tuple<int32_t, double, bool> c_return_tuple(int32_t n);
+std::tuple<Shared, SharedString> c_return_tuple_shared(size_t n);
+char32_t c_next_char(char32_t c);
+char32_t c_return_char_unchecked(uint32_t value);
+uint64_t c_system_abi(uint32_t a, uint64_t b, uint8_t c);
 
 void c_take_primitive(size_t n);
 void c_take_shared(Shared shared);
+void c_take_nested(Nested nested);
+void c_take_payload(Payload payload);
+void c_take_shared_string(SharedString shared);
+void c_take_enum(Color color);
 void c_take_box(rust::Box<R> r);
+void c_take_box_shared(rust::Box<Shared> shared);
 void c_take_unique_ptr(std::unique_ptr<C> c);
+void c_take_shared_ptr(std::shared_ptr<C> c);
+rust::Arc<R> c_return_arc(rust::Arc<R> r);
+size_t c_arc_strong_count_with_copies(rust::Arc<R> r, size_t copies);
+size_t c_shared_ptr_use_count(const std::shared_ptr<C> &c);
+size_t c_vector_capacity_u8(const std::vector<uint8_t> &v);
+size_t c_vector_count_true(const std::vector<bool> &v);
+unsigned __int128 c_roundtrip_u128(unsigned __int128 n);
+__int128 c_negate_i128(__int128 n);
+void c_make_unique_ptr(std::unique_ptr<C> &out, size_t n);
 void c_take_ref_r(const R &r);
 void c_take_ref_c(const C &c);
+size_t c_take_ref_large_config(const LargeConfig &config);
+size_t c_count_distinct_tiles(rust::Slice<const Tile> tiles);
+size_t c_hash_named(Named named);
+void c_take_borrowed(const C *c);
+size_t c_take_optional_ref(const Shared *shared);
 void c_take_str(rust::Str s);
+void c_take_mut_slice_shared(rust::Slice<Shared> s);
+void c_take_slice_u8(rust::Slice<const uint8_t> s);
+void c_zero_slice_u8(rust::Slice<uint8_t> s);
+int64_t c_sum_slice_i32(rust::Slice<const int32_t> s);
+int64_t c_max_window_sum(rust::Slice<const int32_t> s, size_t width);
+size_t c_try_subslice_len(rust::Slice<const uint8_t> s, size_t start,
+                          size_t len);
+size_t c_sum_slice_shared(rust::Slice<const Shared> s);
+void c_take_ref_vec(const rust::Vec<uint8_t> &v);
+void c_grow_vec(rust::Vec<uint8_t> &v);
+void c_shrink_vec(rust::Vec<uint8_t> &v);
+void c_tokenize(rust::Str s, rust::Vec<rust::Vec<uint8_t>> &tokens);
+size_t c_count_token_bytes(const rust::Vec<rust::Vec<uint8_t>> &tokens);
+rust::Vec<rust::String> c_return_vec_string();
+rust::String c_join_vec_string(rust::Vec<rust::String> v);
 void c_take_rust_string(rust::String s);
 void c_take_unique_ptr_string(std::unique_ptr<std::string> s);
+size_t c_sum_unique_array(std::unique_ptr<uint8_t[]> array, size_t len);
+void c_take_binary_string(const std::string &s);
+void c_take_u16string(const std::u16string &s);
+void c_append_cxx_string(std::string &s);
+void c_append_to(std::string &s, rust::Str suffix);
+void c_reset(std::unique_ptr<std::string> &s, rust::Str value);
 void c_take_callback(rust::Fn<size_t(rust::String)> callback);
+size_t c_for_each_mut(rust::Fn<void(Shared &)> f);
+uint32_t c_process(rust::TryFn<uint32_t(uint32_t)> cb);
+int32_t c_apply_twice(rust::Fn<int32_t(int32_t)> f, int32_t n);
+rust::String c_greet(rust::Fn<rust::String(rust::Str)> f);
+size_t c_build(rust::Fn<std::unique_ptr<C>(size_t)> f);
+size_t c_call_logger(size_t depth);
+size_t compute(size_t n);
+uint32_t c_pure_square(uint32_t n);
+size_t c_init_once_config();
+size_t c_init_once_calls();
+std::chrono::system_clock::time_point c_now();
+std::chrono::system_clock::time_point
+c_add_seconds(std::chrono::system_clock::time_point time, int64_t seconds);
 
 void c_try_return_void();
 size_t c_try_return_primitive();
 size_t c_fail_return_primitive();
+size_t c_fail_throw_int();
 rust::Box<R> c_try_return_box();
+rust::Box<R> c_fail_return_box();
 const rust::String &c_try_return_ref(const rust::String &);
 rust::Str c_try_return_str(rust::Str);
 rust::String c_try_return_rust_string();
 std::unique_ptr<std::string> c_try_return_unique_ptr_string();
+std::tuple<uint8_t, size_t> c_try_return_tuple();
+std::tuple<uint8_t, size_t> c_fail_return_tuple();
+std::tuple<SharedString, Shared> c_fail_return_tuple_shared();
+std::unique_ptr<C> c_try_new_c(size_t n);
+size_t c_throw_classified(uint8_t kind);
+rust::String c_throw_classified_string(uint8_t kind);
+char32_t c_try_return_char(uint32_t value);
+char32_t c_try_return_char_classified(uint32_t value);
 
 } // namespace tests
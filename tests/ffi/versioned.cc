@@ -0,0 +1,17 @@
+#include "tests/ffi/versioned.h"
+#include "tests/ffi/versioned_v1.rs.h"
+#include "tests/ffi/versioned_v2.rs.h"
+
+namespace org {
+namespace versioned {
+namespace v1 {
+size_t c_call_r_version() { return r_version(); }
+} // namespace v1
+
+inline namespace v2 {
+// Reaches v2 through the enclosing namespace, as callers of a versioned
+// library would.
+size_t c_call_r_version() { return versioned::r_version(); }
+} // namespace v2
+} // namespace versioned
+} // namespace org
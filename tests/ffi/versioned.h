@@ -0,0 +1,14 @@
+#pragma once
+#include <cstddef>
+
+namespace org {
+namespace versioned {
+namespace v1 {
+size_t c_call_r_version();
+} // namespace v1
+
+inline namespace v2 {
+size_t c_call_r_version();
+} // namespace v2
+} // namespace versioned
+} // namespace org
@@ -0,0 +1,21 @@
+// Shares its item names with versioned_v2.rs, whose namespace is the inline
+// one that unversioned C++ names resolve to.
+
+/// Bindings to the org::versioned::v1 half of tests/ffi/versioned.h.
+#[cxx::bridge(namespace = org::versioned::v1)]
+#[allow(missing_docs)]
+pub mod ffi {
+    extern "C" {
+        include!("tests/ffi/versioned.h");
+
+        fn c_call_r_version() -> usize;
+    }
+
+    extern "Rust" {
+        fn r_version() -> usize;
+    }
+}
+
+fn r_version() -> usize {
+    1
+}
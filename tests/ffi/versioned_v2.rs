@@ -0,0 +1,21 @@
+// Spelled as a string to cover `inline` there; the unquoted form is
+// `org::versioned::inline v2`.
+
+/// Bindings to the org::versioned::v2 half of tests/ffi/versioned.h.
+#[cxx::bridge(namespace = "org::versioned::inline v2")]
+#[allow(missing_docs)]
+pub mod ffi {
+    extern "C" {
+        include!("tests/ffi/versioned.h");
+
+        fn c_call_r_version() -> usize;
+    }
+
+    extern "Rust" {
+        fn r_version() -> usize;
+    }
+}
+
+fn r_version() -> usize {
+    2
+}
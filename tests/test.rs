@@ -1,6 +1,13 @@
+#![allow(clippy::missing_const_for_thread_local)]
+
+use cxx::{CxxString, CxxU16String, UniquePtr};
 use cxx_test_suite::ffi;
 use std::cell::Cell;
+use std::convert::TryFrom;
+use std::error::Error;
 use std::ffi::CStr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 thread_local! {
     static CORRECT: Cell<bool> = Cell::new(false);
@@ -15,7 +22,11 @@ macro_rules! check {
     ($run:expr) => {{
         CORRECT.with(|correct| correct.set(false));
         $run;
-        assert!(CORRECT.with(|correct| correct.get()), stringify!($run));
+        assert!(
+            CORRECT.with(|correct| correct.get()),
+            "{}",
+            stringify!($run)
+        );
     }};
 }
 
@@ -24,10 +35,42 @@ fn test_c_return() {
     let shared = ffi::Shared { z: 2020 };
 
     assert_eq!(2020, ffi::c_return_primitive());
+    assert_eq!(2020, ffi::c_return_camel_case());
+    assert_eq!(2021, ffi::c_new_counter(2021).as_ref().unwrap().count());
+    assert_eq!(ffi::Color::Blue, ffi::c_return_enum());
+    assert_eq!(5, ffi::c_return_enum() as u8);
+    #[allow(deprecated)]
+    let deprecated = ffi::c_deprecated_return_primitive();
+    assert_eq!(2020, deprecated);
     assert_eq!(2020, ffi::c_return_shared().z);
+    // Outside the bridge crate the struct can only be matched with `..`.
+    let ffi::Extensible { z, .. } = ffi::c_return_extensible();
+    assert_eq!(2020, z);
+    let nested = ffi::c_return_nested();
+    assert_eq!(2020, nested.shared.z);
+    assert_eq!(1, nested.tag);
+    assert!(nested == ffi::c_return_nested());
+    let settings = ffi::c_return_settings();
+    assert_eq!(3, settings.retries);
+    assert_eq!(0.5, settings.ratio);
+    assert_eq!("fast", settings.name);
+    assert_eq!(4, settings.block.d);
+    assert_eq!("2020", ffi::c_return_shared_string().msg);
     assert_eq!(2020, *ffi::c_return_box());
     ffi::c_return_unique_ptr();
+    assert!(ffi::c_return_optional_unique_ptr(false).is_none());
+    assert_eq!(
+        2020,
+        ffi::c_return_optional_unique_ptr(true)
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .get()
+    );
     assert_eq!(2020, *ffi::c_return_ref(&shared));
+    let z = ffi::c_return_optional_ref(&shared, true).unwrap();
+    assert!(std::ptr::eq(z, &shared.z));
+    assert!(ffi::c_return_optional_ref(&shared, false).is_none());
     assert_eq!("2020", ffi::c_return_str(&shared));
     assert_eq!("2020", ffi::c_return_rust_string());
     assert_eq!(
@@ -38,6 +81,98 @@ fn test_c_return() {
             .to_str()
             .unwrap()
     );
+    assert_eq!(4, ffi::c_return_unique_ptr_vector_u8().len());
+    assert_eq!(
+        200_u8,
+        ffi::c_return_unique_ptr_vector_u8().into_iter().sum(),
+    );
+    let vector = ffi::c_return_unique_ptr_vector_u8();
+    assert_eq!(&[86, 75, 30, 9], vector.slice(0, 4));
+    assert_eq!(30, vector[2]);
+    assert_eq!(&[75, 30], vector.slice(1, 3));
+    assert_eq!(&[30, 9], vector.slice(2, 4));
+    assert!(vector.slice(4, 4).is_empty());
+    let vector = ffi::c_return_unique_ptr_vector_shared();
+    assert_eq!(2, vector.len());
+    assert_eq!(Some(1011), vector.get(1).map(|shared| shared.z));
+    let mut sum = 0;
+    for shared in &*vector {
+        sum += shared.z;
+    }
+    assert_eq!(2021, sum);
+    assert_eq!(1011, vector.slice(1, 2)[0].z);
+    assert_eq!(1010, vector[0].z);
+
+    let mut vec = ffi::c_return_rust_vec_shared();
+    assert_eq!(3, vec.len());
+    assert!(vec.capacity() >= 3);
+    assert_eq!(1012, vec[2].z);
+    vec.push(ffi::Shared { z: 1013 });
+    let sum: usize = vec.iter().map(|shared| shared.z).sum();
+    assert_eq!(4046, sum);
+    assert_eq!((42, 10.5, true), ffi::c_return_tuple(21));
+    assert_eq!((-42, -10.5, false), ffi::c_return_tuple(-21));
+    let (shared, string) = ffi::c_return_tuple_shared(2020);
+    assert_eq!((2020, "2020"), (shared.z, &*string.msg));
+    assert_eq!(2020 << 100, ffi::c_roundtrip_u128(2020 << 100));
+    assert_eq!(!0, ffi::c_roundtrip_u128(!0));
+    assert_eq!(-(2020 << 100), ffi::c_negate_i128(2020 << 100));
+    assert_eq!(1 << 126, ffi::c_negate_i128(-1 << 126));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 4 but the index is 4")]
+fn test_vector_index_out_of_bounds() {
+    let vector = ffi::c_return_unique_ptr_vector_u8();
+    let _ = vector[4];
+}
+
+#[test]
+fn test_vector_mutation() {
+    let mut vector = ffi::c_return_unique_ptr_vector_u8();
+    vector.pop_back();
+    assert_eq!(&[86, 75, 30], vector.slice(0, 3));
+    vector.reserve(100);
+    assert!(ffi::c_vector_capacity_u8(&vector) >= 100);
+    assert_eq!(3, vector.len());
+    vector.clear();
+    assert!(vector.is_empty());
+    vector.pop_back();
+    assert!(vector.is_empty());
+
+    let mut vector = ffi::c_return_unique_ptr_vector_shared();
+    vector.reserve(8);
+    vector.pop_back();
+    assert_eq!(1, vector.len());
+    assert_eq!(1010, vector.get(0).unwrap().z);
+    vector.clear();
+    assert!(vector.is_empty());
+}
+
+#[test]
+fn test_vector_bool() {
+    let mut vector = ffi::c_return_unique_ptr_vector_bool();
+    assert_eq!(3, vector.len());
+    assert_eq!(Some(true), vector.get(0));
+    assert_eq!(Some(false), vector.get(1));
+    assert_eq!(None, vector.get(3));
+    assert_eq!(vector.iter().collect::<Vec<_>>(), [true, false, true]);
+
+    vector.push_back(true);
+    vector.push_back(false);
+    assert_eq!(5, vector.len());
+    assert_eq!(3, ffi::c_vector_count_true(&vector));
+    vector.pop_back();
+    assert_eq!(Some(true), vector.get(3));
+    vector.clear();
+    assert!(vector.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "range end index 5 out of range for Vector of length 4")]
+fn test_vector_slice_out_of_bounds() {
+    let vector = ffi::c_return_unique_ptr_vector_u8();
+    let _ = vector.slice(2, 5);
 }
 
 #[test]
@@ -48,7 +183,21 @@ fn test_c_try_return() {
         "logic error",
         ffi::c_fail_return_primitive().unwrap_err().what(),
     );
+    assert_eq!(
+        "unknown C++ exception",
+        ffi::c_fail_throw_int().unwrap_err().what(),
+    );
     assert_eq!(2020, *ffi::c_try_return_box().unwrap());
+    assert_eq!("logic error", ffi::c_fail_return_box().unwrap_err().what(),);
+    assert_eq!((20, 2020), ffi::c_try_return_tuple().unwrap());
+    assert_eq!(
+        "logic error",
+        ffi::c_fail_return_tuple().unwrap_err().what(),
+    );
+    match ffi::c_fail_return_tuple_shared() {
+        Ok(_) => panic!("expected an error"),
+        Err(err) => assert_eq!("logic error", err.what()),
+    }
     assert_eq!("2020", *ffi::c_try_return_ref(&"2020".to_owned()).unwrap());
     assert_eq!("2020", ffi::c_try_return_str("2020").unwrap());
     assert_eq!("2020", ffi::c_try_return_rust_string().unwrap());
@@ -59,6 +208,310 @@ fn test_c_try_return() {
             .as_ref()
             .unwrap()
     );
+    let c = ffi::c_try_new_c(2020).unwrap();
+    assert_eq!(2020, c.as_ref().unwrap().get());
+    match ffi::c_try_new_c(0) {
+        Ok(_) => panic!("expected c_try_new_c(0) to throw"),
+        Err(err) => assert_eq!("n must be nonzero", err.what()),
+    }
+}
+
+#[test]
+fn test_c_throw_classified() {
+    use ffi::ClassifiedError;
+
+    assert_eq!(2020, ffi::c_throw_classified(0).unwrap());
+    match ffi::c_throw_classified(1) {
+        Err(ClassifiedError::InvalidArgument(e)) => assert_eq!("invalid argument", e.what()),
+        other => panic!("expected InvalidArgument, got {:?}", other),
+    }
+    match ffi::c_throw_classified(2) {
+        Err(ClassifiedError::OutOfRange(e)) => assert_eq!("out of range", e.what()),
+        other => panic!("expected OutOfRange, got {:?}", other),
+    }
+    match ffi::c_throw_classified(3) {
+        Err(ClassifiedError::Other(e)) => assert_eq!("runtime error", e.what()),
+        other => panic!("expected Other, got {:?}", other),
+    }
+
+    assert_eq!("2020", ffi::c_throw_classified_string(0).unwrap());
+    let err = ffi::c_throw_classified_string(2).unwrap_err();
+    assert!(matches!(err, ClassifiedError::OutOfRange(_)));
+    assert_eq!("out of range", err.to_string());
+}
+
+#[test]
+fn test_c_borrowed() {
+    let unique_ptr = ffi::c_return_unique_ptr();
+    let c = unique_ptr.as_ref().unwrap();
+
+    // Handles are plain values that can be stored without taking ownership.
+    let handles = vec![
+        ffi::c_return_borrowed(c, true),
+        ffi::c_return_borrowed(c, false),
+    ];
+    assert_eq!(2020, handles[0].get());
+    assert_eq!(c as *const ffi::C, handles[0].as_ptr());
+    assert!(handles[1].is_null());
+    assert!(handles[1].as_ref().is_none());
+
+    check!(ffi::c_take_borrowed(handles[0]));
+    drop(handles);
+    assert_eq!(2020, c.get());
+}
+
+#[test]
+fn test_shared_struct_layout() {
+    assert_eq!(std::mem::size_of::<ffi::Padded>(), ffi::c_sizeof_padded());
+}
+
+#[test]
+fn test_c_take_ref_large_struct() {
+    let block = ffi::Block {
+        a: 1,
+        b: 2,
+        c: 3,
+        d: 4,
+    };
+    let config = ffi::LargeConfig {
+        block0: block,
+        block1: block,
+        block2: block,
+        block3: block,
+        block4: block,
+        block5: block,
+        block6: block,
+        block7: block,
+    };
+    assert_eq!(256, std::mem::size_of::<ffi::LargeConfig>());
+    let address = &config as *const ffi::LargeConfig as usize;
+    assert_eq!(address, ffi::c_take_ref_large_config(&config));
+}
+
+#[test]
+fn test_c_shared_ptr() {
+    let shared_ptr = ffi::c_return_shared_ptr();
+    assert_eq!(2020, shared_ptr.get());
+    assert_eq!(1, ffi::c_shared_ptr_use_count(&shared_ptr));
+    let clone = shared_ptr.clone();
+    assert_eq!(2, ffi::c_shared_ptr_use_count(&shared_ptr));
+    drop(clone);
+    assert_eq!(1, ffi::c_shared_ptr_use_count(&shared_ptr));
+    assert!(cxx::SharedPtr::<ffi::C>::null().is_null());
+}
+
+#[test]
+fn test_cxx_string_mutation() {
+    let mut s = ffi::c_return_unique_ptr_string();
+    let string = s.as_mut().unwrap();
+    string.push_str("-next");
+    assert_eq!("2020-next", string.to_str().unwrap());
+    string.push_bytes(b"\0\xFF");
+    assert_eq!(b"2020-next\0\xFF", string.as_bytes());
+    string.clear();
+    assert!(string.is_empty());
+    string.reserve(64);
+    string.push_str("2020");
+    check!(ffi::c_take_unique_ptr_string(s));
+}
+
+#[test]
+fn test_hash_nested_struct_keys() {
+    let tile = |x, y, level| ffi::Tile {
+        coord: ffi::Coord { x, y },
+        level,
+    };
+    let tiles = [
+        tile(0, 0, ffi::Level::Low),
+        tile(0, 1, ffi::Level::Low),
+        tile(0, 0, ffi::Level::Low),
+        tile(0, 0, ffi::Level::High),
+        tile(1, 0, ffi::Level::Mid),
+        tile(0, 1, ffi::Level::Low),
+    ];
+    assert_eq!(4, ffi::c_count_distinct_tiles(&tiles));
+
+    let named = |name: &str, tile| ffi::Named {
+        name: name.to_owned(),
+        tile,
+    };
+    let origin = tile(0, 0, ffi::Level::Mid);
+    assert_eq!(
+        ffi::c_hash_named(named("origin", origin)),
+        ffi::c_hash_named(named("origin", origin)),
+    );
+    assert_ne!(
+        ffi::c_hash_named(named("origin", origin)),
+        ffi::c_hash_named(named("elsewhere", origin)),
+    );
+}
+
+#[test]
+fn test_unique_array() {
+    let mut array = ffi::c_return_unique_array(4);
+    assert!(!array.is_null());
+    assert_eq!([0, 2, 4, 6], unsafe { array.as_slice(4) });
+    assert_eq!(4, *unsafe { array.get_unchecked(2) });
+    unsafe { array.as_mut_slice(4)[3] = 100 };
+    assert_eq!(106, ffi::c_sum_unique_array(array, 4));
+    assert!(cxx::UniqueArray::<u8>::null().is_null());
+}
+
+#[test]
+fn test_c_take_mut_cxx_string() {
+    let mut s = CxxString::from_bytes(b"2020");
+    ffi::c_append_cxx_string(s.as_mut().unwrap());
+    assert_eq!("2020-cxx", s.to_str().unwrap());
+}
+
+#[test]
+fn test_c_append_to_pinned_cxx_string() {
+    let mut s = CxxString::from_bytes(b"2020");
+    ffi::c_append_to(s.pin_mut(), "-pin");
+    ffi::c_append_to(s.pin_mut(), "ned");
+    assert_eq!("2020-pinned", s.to_str().unwrap());
+}
+
+#[test]
+fn test_c_reset_unique_ptr_cxx_string() {
+    let mut s = CxxString::from_bytes(b"front");
+    ffi::c_reset(&mut s, "back");
+    assert_eq!("back", s.to_str().unwrap());
+
+    // Also fills in a null UniquePtr; the replaced strings are freed by the
+    // C++ assignment.
+    let mut s = UniquePtr::<CxxString>::null();
+    ffi::c_reset(&mut s, "front");
+    assert_eq!("front", s.to_str().unwrap());
+}
+
+#[test]
+fn test_cxx_string_from_bytes() {
+    let bytes = b"\0bin\0ary\xFF";
+    let s = CxxString::from_bytes(bytes);
+    assert_eq!(9, s.len());
+    assert_eq!(bytes, s.as_bytes());
+    check!(ffi::c_take_binary_string(&s));
+    assert!(CxxString::from_bytes(b"").is_empty());
+}
+
+#[test]
+fn test_cxx_u16string() {
+    let s = ffi::c_return_unique_ptr_u16string();
+    let s = s.as_ref().unwrap();
+    assert_eq!(6, s.len());
+    assert_eq!(0xD800, s.as_units()[5]);
+    assert_eq!("2020\u{e9}\u{fffd}", s.to_string_lossy());
+
+    let s = UniquePtr::<CxxU16String>::from("2020\u{1f980}");
+    assert_eq!(6, s.len());
+    check!(ffi::c_take_u16string(&s));
+    assert!(CxxU16String::from_units(&[]).is_empty());
+}
+
+#[test]
+fn test_cxx_string_eq_string() {
+    let s = CxxString::from_bytes(b"2020");
+    let equal = String::from("2020");
+    let unequal = String::from("2021");
+    let prefix = String::from("202");
+    assert_eq!(*s, equal);
+    assert_eq!(equal, *s);
+    assert_ne!(*s, unequal);
+    assert_ne!(unequal, *s);
+    assert_ne!(*s, prefix);
+    assert_ne!(prefix, *s);
+    // No String holds these bytes, and the lossy conversion does not compare
+    // equal since the comparison is byte for byte.
+    let binary = CxxString::from_bytes(b"20\xFF20");
+    let lossy = binary.to_string_lossy().into_owned();
+    assert_ne!(*binary, lossy);
+    assert_ne!(lossy, *binary);
+    assert_ne!(*binary, *"2020");
+}
+
+#[test]
+fn test_c_system_abi() {
+    assert_eq!(2020, ffi::c_system_abi(2, 1000, 20));
+}
+
+#[test]
+fn test_c_char() {
+    use ffi::ClassifiedError;
+
+    assert_eq!('\u{1F981}', ffi::c_next_char('\u{1F980}'));
+    assert_eq!('x', ffi::c_try_return_char(0x78).unwrap());
+
+    let err = ffi::c_try_return_char(0x110000).unwrap_err();
+    assert_eq!("invalid Unicode scalar value 0x110000 from C++", err.what());
+    match ffi::c_try_return_char_classified(0xDC00) {
+        Err(ClassifiedError::Other(e)) => {
+            assert_eq!("invalid Unicode scalar value 0xDC00 from C++", e.what());
+        }
+        other => panic!("expected Other, got {:?}", other),
+    }
+}
+
+#[test]
+#[should_panic(expected = "invalid Unicode scalar value 0xD800 from C++")]
+fn test_c_return_invalid_char() {
+    ffi::c_return_char_unchecked(0xD800);
+}
+
+#[test]
+fn test_c_arc() {
+    let arc = Arc::new(2020);
+    let back = ffi::c_return_arc(arc.clone());
+    assert!(Arc::ptr_eq(&arc, &back));
+    assert_eq!(2, Arc::strong_count(&arc));
+    drop(back);
+    assert_eq!(1, Arc::strong_count(&arc));
+    // The Rust original, C++'s argument, 3 copies, and the one passed back.
+    assert_eq!(6, ffi::c_arc_strong_count_with_copies(arc.clone(), 3));
+    assert_eq!(1, Arc::strong_count(&arc));
+}
+
+#[test]
+fn test_c_take_optional_ref() {
+    let shared = ffi::Shared { z: 2020 };
+    assert_eq!(2020, ffi::c_take_optional_ref(Some(&shared)));
+    assert_eq!(0, ffi::c_take_optional_ref(None));
+}
+
+#[test]
+fn test_c_debug() {
+    let unique_ptr = ffi::c_return_unique_ptr();
+    assert_eq!("C { n: 2020 }", format!("{:?}", unique_ptr));
+    assert_eq!("C { n: 2020 }", format!("{:?}", *unique_ptr));
+}
+
+#[test]
+fn test_c_placement() {
+    use cxx::Placement;
+    use std::alloc::{self, Layout};
+
+    let layout = ffi::Component::layout();
+    assert_eq!(16, layout.align());
+
+    // Two components side by side in one Rust-owned arena, destroyed in the
+    // reverse order of construction.
+    let stride = layout.pad_to_align().size();
+    let arena_layout = Layout::from_size_align(2 * stride, layout.align()).unwrap();
+    unsafe {
+        let arena = alloc::alloc(arena_layout);
+        let first = arena as *mut ffi::Component;
+        let second = arena.add(stride) as *mut ffi::Component;
+        ffi::Component::construct_at(first);
+        ffi::Component::construct_at(second);
+        let first_id = (*first).id();
+        let second_id = (*second).id();
+        assert_eq!(first_id + 1, second_id);
+        ffi::Component::destroy_at(second);
+        assert_eq!(second_id, ffi::c_last_destroyed_component());
+        ffi::Component::destroy_at(first);
+        assert_eq!(first_id, ffi::c_last_destroyed_component());
+        alloc::dealloc(arena, arena_layout);
+    }
 }
 
 #[test]
@@ -67,16 +520,131 @@ fn test_c_take() {
 
     check!(ffi::c_take_primitive(2020));
     check!(ffi::c_take_shared(ffi::Shared { z: 2020 }));
+    check!(ffi::c_take_enum(ffi::Color::Green));
+    check!(ffi::c_take_nested(ffi::Nested {
+        shared: ffi::Shared { z: 2020 },
+        tag: 1,
+    }));
+    check!(ffi::c_take_shared_string(ffi::SharedString {
+        msg: "2020".to_owned(),
+    }));
     check!(ffi::c_take_box(Box::new(2020)));
+    check!(ffi::c_take_box_shared(Box::new(ffi::Shared { z: 2020 })));
     check!(ffi::c_take_ref_c(unique_ptr.as_ref().unwrap()));
     check!(ffi::c_take_unique_ptr(unique_ptr));
+    check!(ffi::c_take_shared_ptr(ffi::c_return_shared_ptr()));
     check!(ffi::c_take_str("2020"));
+    check!(ffi::c_take_slice_u8(b"2020"));
+    let mut buffer = *b"2020";
+    ffi::c_zero_slice_u8(&mut buffer[1..]);
+    assert_eq!(buffer, *b"2\0\0\0");
+    assert_eq!(2020, ffi::c_sum_slice_i32(&[-1, 2021, 0]));
+    assert_eq!(0, ffi::c_sum_slice_i32(&[]));
+    assert_eq!(2020, ffi::c_max_window_sum(&[1000, -5, 2000, 20, 0, -1], 2));
+    assert_eq!(2, ffi::c_try_subslice_len(b"2020", 2, 2).unwrap());
+    assert_eq!(0, ffi::c_try_subslice_len(b"2020", 4, 0).unwrap());
+    assert_eq!(
+        "rust::Slice index out of range",
+        ffi::c_try_subslice_len(b"2020", 3, 2).unwrap_err().what(),
+    );
+    assert!(ffi::c_try_subslice_len(b"2020", 5, 0).is_err());
+    let shared = [ffi::Shared { z: 2000 }, ffi::Shared { z: 20 }];
+    assert_eq!(2020, ffi::c_sum_slice_shared(&shared));
+    let mut slice = [ffi::Shared { z: 2020 }, ffi::Shared { z: 2021 }];
+    check!(ffi::c_take_mut_slice_shared(&mut slice));
+    assert_eq!(slice[0].z, 2030);
+    assert_eq!(slice[1].z, 2031);
+    check!(ffi::c_take_ref_vec(&vec![20, 2, 0, 0]));
     check!(ffi::c_take_rust_string("2020".to_owned()));
     check!(ffi::c_take_unique_ptr_string(
         ffi::c_return_unique_ptr_string()
     ));
 }
 
+#[test]
+fn test_c_mut_vec() {
+    let mut vec = Vec::new();
+    ffi::c_grow_vec(&mut vec);
+    assert_eq!(vec, (0..100).collect::<Vec<u8>>());
+    assert!(vec.capacity() >= 100);
+    ffi::c_shrink_vec(&mut vec);
+    assert_eq!(vec, [0, 1]);
+}
+
+#[test]
+fn test_c_jagged_vec() {
+    let mut tokens = vec![b"stale".to_vec()];
+    ffi::c_tokenize("20 2  0", &mut tokens);
+    assert_eq!(tokens, [&b"20"[..], b"2", b"", b"0"]);
+    assert_eq!(4, ffi::c_count_token_bytes(&tokens));
+}
+
+#[test]
+fn test_c_vec_string() {
+    let strings = ffi::c_return_vec_string();
+    assert_eq!(strings, ["2020", "C++"]);
+
+    let strings = vec!["a".to_owned(), String::new(), "b".to_owned()];
+    assert_eq!("a,,b", ffi::c_join_vec_string(strings));
+}
+
+#[test]
+fn test_c_out_param() {
+    let mut unique_ptr = UniquePtr::null();
+    ffi::c_make_unique_ptr(&mut unique_ptr, 2020);
+    assert_eq!(2020, unique_ptr.as_ref().unwrap().get());
+
+    // The previously owned object is destroyed by the C++ assignment.
+    ffi::c_make_unique_ptr(&mut unique_ptr, 2021);
+    assert_eq!(2021, unique_ptr.as_ref().unwrap().get());
+}
+
+#[test]
+fn test_fixed_width_layout() {
+    // Matches the layout asserted on the C++ side.
+    assert_eq!(16, std::mem::size_of::<ffi::FixedWidth>());
+    assert_eq!(8, std::mem::align_of::<ffi::FixedWidth>());
+}
+
+#[test]
+fn test_array_fields() {
+    assert_eq!(12, std::mem::size_of::<ffi::Pixel>());
+    assert_eq!(4, std::mem::align_of::<ffi::Pixel>());
+
+    let pixel = ffi::Pixel {
+        rgba: [1, 2, 3],
+        weights: [0.5, 1.5],
+    };
+    let pixel = ffi::c_brighten(pixel);
+    assert_eq!([2, 3, 4], pixel.rgba);
+    assert_eq!([1.0, 3.0], pixel.weights);
+    assert_eq!([0; 3], ffi::Pixel::default().rgba);
+}
+
+#[test]
+fn test_std_array() {
+    assert_eq!([0.5, 1.5, 2.5], ffi::c_return_array());
+    assert_eq!(2020, ffi::c_sum_array([2000, 10, 5, 3], &[1, 1]));
+}
+
+#[test]
+fn test_enum_try_from() {
+    assert_eq!(Ok(ffi::Color::Green), ffi::Color::try_from(4));
+    assert_eq!(Err(3), ffi::Color::try_from(3));
+    assert_eq!(Ok(ffi::Level::Low), ffi::Level::try_from(-1));
+    assert_eq!(Err(11), ffi::Level::try_from(11));
+}
+
+#[test]
+fn test_union() {
+    assert_eq!(8, std::mem::size_of::<ffi::Payload>());
+    assert_eq!(8, std::mem::align_of::<ffi::Payload>());
+
+    let payload = ffi::c_return_payload(1.5);
+    assert_eq!(1.5_f32.to_bits(), unsafe { payload.bits });
+    check!(ffi::c_take_payload(ffi::Payload { real: 1.5 }));
+}
+
 #[test]
 fn test_c_callback() {
     fn callback(s: String) -> usize {
@@ -87,6 +655,264 @@ fn test_c_callback() {
     }
 
     check!(ffi::c_take_callback(callback));
+
+    fn scale(shared: &mut ffi::Shared) {
+        shared.z *= 10;
+    }
+    assert_eq!(60, ffi::c_for_each_mut(scale));
+}
+
+#[test]
+fn test_c_callback_result() {
+    fn double(n: u32) -> Result<u32, Box<dyn Error>> {
+        Ok(n * 2)
+    }
+    assert_eq!(30, ffi::c_process(double));
+
+    fn double_unless_three(n: u32) -> Result<u32, Box<dyn Error>> {
+        if n == 3 {
+            Err("3 is unlucky".into())
+        } else {
+            Ok(n * 2)
+        }
+    }
+    assert_eq!(1006, ffi::c_process(double_unless_three));
+}
+
+#[test]
+fn test_c_callback_returning_value() {
+    fn plus_one(n: i32) -> i32 {
+        n + 1
+    }
+    assert_eq!(2022, ffi::c_apply_twice(plus_one, 2020));
+
+    fn hello(name: &str) -> String {
+        format!("hello {}", name)
+    }
+    assert_eq!("hello world!", ffi::c_greet(hello));
+
+    fn make_c(n: usize) -> UniquePtr<ffi::C> {
+        let mut c = ffi::c_return_unique_ptr();
+        c.pin_mut().set(n);
+        c
+    }
+    assert_eq!(2020, ffi::c_build(make_c));
+}
+
+#[test]
+fn test_r_non_reentrant() {
+    // A reentrant call panics inside the shim, which aborts, so it has to be
+    // observed from a child process.
+    if std::env::var_os("CXX_TEST_REENTER").is_some() {
+        ffi::c_call_logger(1);
+        return;
+    }
+
+    assert_eq!(1, ffi::c_call_logger(0));
+    assert_eq!(1, ffi::c_call_logger(0));
+
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .arg("test_r_non_reentrant")
+        .arg("--exact")
+        .arg("--nocapture")
+        .env("CXX_TEST_REENTER", "1")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if cfg!(debug_assertions) {
+        assert!(!output.status.success());
+        assert!(stderr.contains("reentrant call to"), "{}", stderr);
+    } else {
+        assert!(output.status.success());
+    }
+}
+
+#[test]
+fn test_c_same_name_as_rust() {
+    assert_eq!(4040, ffi::compute(2020));
+}
+
+#[test]
+fn test_c_init_once() {
+    let threads: Vec<_> = (0..8)
+        .map(|_| std::thread::spawn(ffi::c_init_once_config))
+        .collect();
+    for thread in threads {
+        assert_eq!(1, thread.join().unwrap());
+    }
+    assert_eq!(1, ffi::c_init_once_config());
+    assert_eq!(1, ffi::c_init_once_calls());
+}
+
+#[test]
+fn test_c_time_point() {
+    let before = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let now = ffi::c_now();
+    let after = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    // Rounded down to the resolution of system_clock, which may be coarser.
+    assert!(before.as_nanos() as i64 - 1000 <= now && now <= after.as_nanos() as i64);
+
+    let time = 1_600_000_000_123_456_000;
+    assert_eq!(time + 60_000_000_000, ffi::c_add_seconds(time, 60));
+    assert_eq!(-2_000_000_000, ffi::c_add_seconds(0, -2));
+}
+
+#[test]
+fn test_c_pure() {
+    assert_eq!(2025, ffi::c_pure_square(45));
+
+    // The Rust function is declared with the attribute in the generated
+    // header, which is where C++ callers pick it up.
+    let target_dir = option_env!("CARGO_TARGET_DIR")
+        .map(str::to_owned)
+        .unwrap_or_else(|| concat!(env!("CARGO_MANIFEST_DIR"), "/target").to_owned());
+    let header = format!("{}/cxxbridge/tests/ffi/lib.rs.h", target_dir);
+    let header = std::fs::read_to_string(header).unwrap();
+    assert!(header.contains("[[gnu::pure]]\n#endif\nuint32_t r_pure_square(uint32_t n) noexcept;"));
+    assert!(!header.contains("[[gnu::pure]]\n#endif\nsize_t r_return_primitive()"));
+}
+
+#[test]
+fn test_no_exceptions() {
+    use cxx_test_suite::no_exceptions::ffi as no_exceptions;
+
+    let mut counter = no_exceptions::c_new_counter(2020);
+    assert_eq!(2021, counter.pin_mut().increment());
+    let point = no_exceptions::c_scale_twice(no_exceptions::Point { x: 1, y: -2 }, 3);
+    assert_eq!((9, -18), (point.x, point.y));
+    assert_eq!(5, no_exceptions::c_greeting_len());
+}
+
+#[test]
+fn test_c_take_optional() {
+    use cxx_test_suite::cpp17::ffi as cpp17;
+
+    assert_eq!(5, cpp17::c_optional_or(Some(5), 2020));
+    assert_eq!(2020, cpp17::c_optional_or(None, 2020));
+}
+
+#[test]
+fn test_c_return_default_options() {
+    use cxx_test_suite::cpp17::ffi as cpp17;
+
+    let options = cpp17::c_return_default_options();
+    let default = cpp17::Options::default();
+    assert_eq!(default.retries, options.retries);
+    assert_eq!(default.ratio, options.ratio);
+    assert_eq!(default.name, options.name);
+}
+
+#[test]
+fn test_namespaces() {
+    use cxx_test_suite::{namespace_a, namespace_b};
+
+    assert_eq!(4040, namespace_a::ffi::c_make_shared(2020).z);
+    let shared = namespace_b::ffi::c_make_shared(2020);
+    assert_eq!((6060, 1), (shared.z, shared.tag));
+}
+
+#[test]
+fn test_inline_namespaces() {
+    use cxx_test_suite::{versioned_v1, versioned_v2};
+
+    assert_eq!(1, versioned_v1::ffi::c_call_r_version());
+    assert_eq!(2, versioned_v2::ffi::c_call_r_version());
+}
+
+#[test]
+fn test_bridge_modules() {
+    use cxx_test_suite::{module_a, module_b};
+
+    assert_eq!(2020, module_a::ffi_a::init());
+    assert_eq!(2021, module_b::ffi_b::init(2020));
+}
+
+#[test]
+fn test_bridge_module_paths() {
+    use cxx_test_suite::{module_c, module_d};
+
+    assert_eq!(2020, module_c::ffi::init());
+    assert_eq!(2021, module_d::ffi::init(2020));
+}
+
+#[test]
+fn test_pointer_sized_overrides() {
+    use cxx_test_suite::pointer_sized::ffi;
+
+    assert_eq!(2020, ffi::c_offset(2021, -1));
+    assert_eq!(2020, ffi::c_call_r_offset(2000, 20));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_link_section() {
+    extern "C" {
+        static __start_cxxbridge_test: u8;
+        static __stop_cxxbridge_test: u8;
+        #[link_name = "tests$link_section$cxxbridge02$ffi$rust$r_in_link_section"]
+        fn shim();
+    }
+
+    let shim = shim as unsafe extern "C" fn() as usize;
+    let (start, stop) = unsafe {
+        (
+            &__start_cxxbridge_test as *const u8 as usize,
+            &__stop_cxxbridge_test as *const u8 as usize,
+        )
+    };
+    assert!(start <= shim && shim < stop);
+}
+
+#[test]
+fn test_c_method_calls() {
+    let mut unique_ptr = ffi::c_return_unique_ptr();
+
+    let old_value = unique_ptr.as_ref().unwrap().get();
+    assert_eq!(2020, old_value);
+    assert_eq!(2021, unique_ptr.pin_mut().set(2021));
+    assert_eq!(2021, unique_ptr.as_ref().unwrap().get());
+    assert_eq!(2022, unique_ptr.pin_mut().increment());
+    assert_eq!(2022, unique_ptr.as_ref().unwrap().get());
+    unique_ptr.pin_mut().set(2021);
+    assert!(unique_ptr.as_ref().unwrap().get_fail().is_err());
+    assert_eq!(2021, unique_ptr.as_ref().unwrap().get_u16());
+    assert_eq!(2021, unique_ptr.as_ref().unwrap().get_i64());
+
+    // The returned bytes borrow from the C++ object rather than being copied.
+    let c = unique_ptr.as_ref().unwrap();
+    let bytes = c.get_bytes();
+    assert_eq!(bytes, [20, 2, 0, 0]);
+    assert_eq!(bytes.as_ptr(), c.get_bytes().as_ptr());
+}
+
+#[test]
+fn test_c_map() {
+    let c = ffi::c_return_unique_ptr();
+    let config = c.as_ref().unwrap().get_config();
+    assert_eq!(3, config.len());
+
+    // Visited in the order of std::map, not of insertion.
+    let entries: Vec<_> = config
+        .iter()
+        .map(|(key, value)| (key.to_str().unwrap(), *value))
+        .collect();
+    assert_eq!(entries, [("alpha", 1), ("mid", 13), ("zeta", 26)]);
+
+    let mid = CxxString::from_bytes(b"mid");
+    assert_eq!(Some(&13), config.get(&mid));
+    let missing = CxxString::from_bytes(b"missing");
+    assert!(config.get(&missing).is_none());
+}
+
+#[test]
+fn test_c_set() {
+    let c = ffi::c_return_unique_ptr();
+    let tags = c.as_ref().unwrap().get_tags();
+    assert_eq!(3, tags.len());
+    assert!(tags.contains(&5));
+    assert!(!tags.contains(&4));
+    let elements: Vec<u32> = tags.into_iter().copied().collect();
+    assert_eq!(elements, [1, 5, 8]);
 }
 
 #[test]
@@ -104,6 +930,42 @@ fn test_c_call_r() {
     check!(cxx_run_test());
 }
 
+#[test]
+fn test_generate_bridge() {
+    let dir = std::env::temp_dir().join(format!("cxx-generate-bridge-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let good = dir.join("good.rs");
+    std::fs::write(
+        &good,
+        "#[cxx::bridge]\nmod ffi {\n    extern \"C\" {\n        fn f() -> usize;\n    }\n}\n",
+    )
+    .unwrap();
+    let out_dir = dir.join("out");
+    let (header, source) = cxx::generate_bridge(&good, &out_dir).unwrap();
+    assert_eq!(out_dir.join("good.rs.h"), header);
+    assert_eq!(out_dir.join("good.rs.cc"), source);
+    assert!(std::fs::read_to_string(&header)
+        .unwrap()
+        .contains("#pragma once"));
+    assert!(std::fs::read_to_string(&source)
+        .unwrap()
+        .contains("cxxbridge02$ffi$cxx$f"));
+
+    let bad = dir.join("bad.rs");
+    std::fs::write(
+        &bad,
+        "#[cxx::bridge]\nmod ffi {\n    extern \"C\" {\n        fn f(x: &Unknown);\n    }\n}\n",
+    )
+    .unwrap();
+    let err = cxx::generate_bridge(&bad, &out_dir).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    let prefix = format!("{}:4:", bad.display());
+    assert!(err.to_string().starts_with(&prefix), "{}", err);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 #[no_mangle]
 extern "C" fn cxx_test_suite_get_box() -> *mut cxx_test_suite::R {
     Box::into_raw(Box::new(2020usize))
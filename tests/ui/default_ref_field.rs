@@ -0,0 +1,9 @@
+#[cxx::bridge]
+mod ffi {
+    #[derive(Default)]
+    struct Borrowing {
+        value: &u32,
+    }
+}
+
+fn main() {}
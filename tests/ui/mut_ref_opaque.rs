@@ -0,0 +1,11 @@
+#[cxx::bridge]
+mod ffi {
+    extern "C" {
+        type Opaque;
+
+        fn f(arg: &mut Opaque);
+        fn g(self: &mut Opaque);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#[cxx::bridge]
+mod ffi {
+    struct Node {
+        value: i32,
+        next: Node,
+    }
+
+    struct A {
+        b: B,
+    }
+
+    struct B {
+        a: A,
+    }
+
+    struct List {
+        head: UniquePtr<Node>,
+    }
+}
+
+fn main() {}
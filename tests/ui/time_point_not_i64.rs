@@ -0,0 +1,9 @@
+#[cxx::bridge]
+mod ffi {
+    extern "C" {
+        #[cxx_time_point(system_clock, nanoseconds)]
+        fn c_deadline(#[cxx_time_point(system_clock, nanoseconds)] start: u64) -> u64;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+#[cxx::bridge]
+mod ffi {
+    extern "C" {
+        type Opaque;
+
+        fn f() -> UniquePtr<Opaque>;
+    }
+}
+
+fn as_mut(ptr: &mut cxx::UniquePtr<ffi::Opaque>) -> &mut ffi::Opaque {
+    ptr.as_mut().unwrap()
+}
+
+fn main() {}
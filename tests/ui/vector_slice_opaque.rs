@@ -0,0 +1,14 @@
+#[cxx::bridge]
+mod ffi {
+    extern "C" {
+        type Opaque;
+
+        fn f(v: &Vector<Opaque>);
+    }
+}
+
+fn slice(v: &cxx::Vector<ffi::Opaque>) -> &[ffi::Opaque] {
+    v.slice(0, 1)
+}
+
+fn main() {}